@@ -0,0 +1,50 @@
+//! Benchmarks for editor kind detection.
+//!
+//! Run with `cargo bench --bench kind`. Each input is a separate benchmark
+//! in the `from_binary` and `from_name` groups.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use opensesame::EditorKind;
+
+/// Binary names as they appear in `$EDITOR` and on `PATH`.
+const BINARIES: &[&str] = &[
+    "vim",
+    "/usr/local/bin/nvim",
+    "C:\\Program Files\\Microsoft VS Code\\Code.exe",
+    "android-studio",
+    "not-an-editor",
+];
+
+/// Names as they appear in configuration files.
+const NAMES: &[&str] = &[
+    "NeoVim",
+    "vs-code",
+    "Visual_Studio_Code",
+    "IntelliJIdea",
+    "unknown",
+];
+
+fn from_binary(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_binary");
+    for binary in BINARIES {
+        group.bench_with_input(*binary, binary, |b, binary| {
+            b.iter(|| EditorKind::from_binary(black_box(binary)));
+        });
+    }
+    group.finish();
+}
+
+fn from_name(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_name");
+    for name in NAMES {
+        group.bench_with_input(*name, name, |b, name| {
+            b.iter(|| EditorKind::from_name(black_box(name)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, from_binary, from_name);
+criterion_main!(benches);
@@ -0,0 +1,96 @@
+//! Anchor-based cursor placement.
+//!
+//! Documentation tooling marks regions of source files with anchor comments
+//! such as `// ANCHOR: setup`. These helpers find the line holding an anchor
+//! so the editor can be opened on the region that follows it. The same
+//! line scan finds git conflict markers.
+
+use std::io::BufRead;
+use std::path::Path;
+
+/// Finds the first line of a file that contains `anchor`.
+///
+/// The match is a plain substring search on each line, without its line
+/// terminator. Returns the 1-indexed line number, or `Ok(None)` if no line
+/// contains the anchor.
+pub fn find_anchor(path: &Path, anchor: &str) -> std::io::Result<Option<u32>> {
+    find_line(path, |line| line.contains(anchor))
+}
+
+/// Finds the first line of a file that opens a git conflict (`<<<<<<<`).
+///
+/// The marker must start the line and be followed by whitespace or the end
+/// of the line, so longer runs of `<` don't count. Returns the 1-indexed
+/// line number, or `Ok(None)` if the file has no conflict.
+pub fn find_conflict(path: &Path) -> std::io::Result<Option<u32>> {
+    find_line(path, |line| {
+        line.strip_prefix("<<<<<<<")
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    })
+}
+
+/// Returns the 1-indexed number of the first line matching `matches`.
+fn find_line(path: &Path, mut matches: impl FnMut(&str) -> bool) -> std::io::Result<Option<u32>> {
+    let file = std::fs::File::open(path)?;
+    let lines = std::io::BufReader::new(file).lines();
+
+    for (line_number, line) in (1..=u32::MAX).zip(lines) {
+        if matches(&line?) {
+            return Ok(Some(line_number));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(contents: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_find_anchor_found() {
+        let (_dir, path) =
+            write("use std::io;\n\n// ANCHOR: setup\nfn setup() {}\n// ANCHOR_END: setup\n");
+
+        assert_eq!(find_anchor(&path, "ANCHOR: setup").unwrap(), Some(3));
+        assert_eq!(find_anchor(&path, "ANCHOR_END").unwrap(), Some(5));
+    }
+
+    #[test]
+    fn test_find_anchor_first_occurrence() {
+        let (_dir, path) = write("// ANCHOR: a\n// ANCHOR: a\n");
+        assert_eq!(find_anchor(&path, "ANCHOR: a").unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_find_conflict() {
+        let (_dir, path) = write(
+            "fn main() {\n<<<<<<< HEAD\n    ours();\n=======\n    theirs();\n>>>>>>> topic\n}\n",
+        );
+        assert_eq!(find_conflict(&path).unwrap(), Some(2));
+
+        let (_dir, path) = write("let shifted = x <<<<<<<< 2;\n<<<<<<<<\n<<<<<<<\n");
+        assert_eq!(find_conflict(&path).unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_find_conflict_missing() {
+        let (_dir, path) = write("fn main() {}\n// <<<<<<< not at line start\n");
+        assert_eq!(find_conflict(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn test_find_anchor_missing() {
+        let (dir, path) = write("fn main() {}\n");
+
+        assert_eq!(find_anchor(&path, "ANCHOR: setup").unwrap(), None);
+        assert!(find_anchor(&dir.path().join("missing.rs"), "ANCHOR").is_err());
+    }
+}
@@ -0,0 +1,1869 @@
+//! Editor argument building.
+//!
+//! This module turns an editor kind, a file, and positioning options into
+//! the editor's argument list. Like the modules it builds on (`kind`,
+//! `options`, `column`, and `remote`), it only needs `core` and `alloc`, so
+//! the argv can be built in one place and spawned elsewhere.
+//! `tests/no_std.rs` builds these modules as a `no_std` crate to keep it so.
+
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::kind::EditorKind;
+use crate::options::{ScrollPosition, Split, VimPositionStyle, WindowMode};
+use crate::remote::RemoteFile;
+
+/// Options that shape the editor-specific argument list.
+#[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)] // Independent on/off options
+pub struct ArgOptions {
+    /// Line to open at (1-indexed).
+    pub line: Option<u32>,
+    /// Column to open at (1-indexed).
+    pub column: Option<u32>,
+    /// Whether the editor should block until the file is closed.
+    pub wait: bool,
+    /// How to lay out `file` and `extra_files` side by side, for editors
+    /// that support it. Ignored without `extra_files`.
+    pub split: Option<Split>,
+    /// Whether to reuse a window, for editors that support it.
+    pub window_mode: WindowMode,
+    /// Whether the target is a folder; positions are ignored.
+    ///
+    /// Callers check [`EditorKind::supports_folders()`] first.
+    pub folder: bool,
+    /// Additional `(line, column)` targets after the primary one, for
+    /// editors that accept several in one invocation.
+    pub extra_positions: Vec<(u32, u32)>,
+    /// Remote host the file lives on, for editors that support remote files.
+    pub remote: Option<RemoteFile>,
+    /// Whether to open the file read-only, for editors that support it.
+    pub read_only: bool,
+    /// Whether to open the file without taking focus, for editors that
+    /// support it.
+    pub background: bool,
+    /// Scratch directory for an isolated launch that skips user config.
+    ///
+    /// `None` launches normally. Only editors that keep their state in a
+    /// directory (VS Code's `--user-data-dir`) use the path itself.
+    pub isolated: Option<String>,
+    /// Position unknown editors with the `+LINE file` convention.
+    ///
+    /// Set for binaries known to accept it, or when the caller opts in for
+    /// every unknown editor.
+    pub plus_line_fallback: bool,
+    /// Start an Emacs daemon if none is running (`emacsclient` only).
+    pub start_emacs_daemon: bool,
+    /// Character encoding to open the file with, for editors that support it.
+    pub encoding: Option<String>,
+    /// How Vim-family editors position the cursor.
+    pub vim_position_style: VimPositionStyle,
+    /// Where the target line should appear, for editors that support it.
+    pub scroll: Option<ScrollPosition>,
+    /// Last line of a selection starting at `line`, for editors that
+    /// support it.
+    pub select_to: Option<u32>,
+    /// Argument template replacing the built-in arguments (see
+    /// [`build_template_args()`]).
+    pub template: Option<Vec<String>>,
+    /// More files to open in the same invocation, without positions.
+    ///
+    /// They follow the primary file, so its position arguments still apply
+    /// to it. Callers check [`EditorKind::supports_multiple_files()`] first.
+    pub extra_files: Vec<String>,
+}
+
+/// Builds the argument list for an editor.
+pub fn build_args(kind: EditorKind, file: &str, options: &ArgOptions) -> Vec<String> {
+    let mut args = Vec::new();
+    build_args_into(&mut args, kind, file, options);
+    args
+}
+
+/// Appends the argument list for an editor to `args`.
+///
+/// Works with any buffer of string-convertible arguments (`Vec<String>`,
+/// `Vec<OsString>`), so callers opening many files can clear and reuse one
+/// buffer instead of allocating a new list per file.
+pub fn build_args_into<A: From<String>>(
+    args: &mut Vec<A>,
+    kind: EditorKind,
+    file: &str,
+    options: &ArgOptions,
+) {
+    if let Some(ref template) = options.template {
+        args.extend(
+            build_template_args(template, file, options.line, options.column)
+                .into_iter()
+                .map(A::from),
+        );
+        args.extend(options.extra_files.iter().cloned().map(A::from));
+        return;
+    }
+
+    // The split flags only arrange several files; one file is never split
+    if let (Some(split), false) = (options.split, options.extra_files.is_empty()) {
+        args.extend(
+            split_args(kind, split)
+                .iter()
+                .map(|s| A::from((*s).to_string())),
+        );
+    }
+
+    args.extend(
+        window_mode_args(kind, options.window_mode)
+            .iter()
+            .map(|s| A::from((*s).to_string())),
+    );
+
+    if options.background {
+        args.extend(
+            kind.background_args()
+                .iter()
+                .map(|s| A::from((*s).to_string())),
+        );
+    }
+
+    if options.read_only {
+        args.extend(
+            kind.read_only_args()
+                .iter()
+                .map(|s| A::from((*s).to_string())),
+        );
+    }
+
+    if let Some(ref data_dir) = options.isolated {
+        args.extend(isolation_args(kind, data_dir).into_iter().map(A::from));
+    }
+
+    if let Some(ref encoding) = options.encoding {
+        args.extend(encoding_args(kind, encoding).into_iter().map(A::from));
+    }
+
+    // An empty alternate editor makes emacsclient start a daemon
+    if options.start_emacs_daemon && kind == EditorKind::EmacsClient {
+        args.push(A::from("-a".to_string()));
+        args.push(A::from(String::new()));
+    }
+
+    let remote_file: String;
+    let file = match options.remote {
+        Some(ref remote) => {
+            let (remote_args, target) = build_remote_args(kind, remote);
+            args.extend(remote_args.into_iter().map(A::from));
+            remote_file = target;
+            &remote_file
+        }
+        None => file,
+    };
+
+    let mut position_args = match options.line {
+        _ if options.folder => build_folder_args(kind, file, options.wait),
+        // Most Unix editors (vi, nano, joe, micro, ...) accept `+LINE file`
+        Some(line) if kind == EditorKind::Unknown && options.plus_line_fallback => {
+            vec![format!("+{line}"), file.to_string()]
+        }
+        Some(line)
+            if options.vim_position_style == VimPositionStyle::GotoLineNormal
+                && matches!(
+                    kind,
+                    EditorKind::Vim | EditorKind::NeoVim | EditorKind::Vi | EditorKind::GVim
+                ) =>
+        {
+            build_vim_normal_args(file, line, options.column)
+        }
+        _ => build_position_args(kind, file, options.line, options.column, options.wait),
+    };
+    if !options.extra_files.is_empty() {
+        insert_extra_files(&mut position_args, file, &options.extra_files);
+    }
+    args.extend(position_args.into_iter().map(A::from));
+
+    // Select the range before scrolling, so the scroll still targets `line`
+    if let (Some(end), Some(_)) = (options.select_to, options.line) {
+        args.extend(selection_args(kind, end).into_iter().map(A::from));
+    }
+
+    // Scroll commands run after the cursor has moved to the line
+    if let (Some(scroll), Some(_)) = (options.scroll, options.line) {
+        args.extend(scroll_args(kind, scroll).into_iter().map(A::from));
+    }
+
+    if kind.supports_multiple_positions() {
+        for (line, column) in &options.extra_positions {
+            args.push(A::from("--goto".to_string()));
+            args.push(A::from(format!("{file}:{line}:{column}")));
+        }
+    }
+}
+
+/// Builds the arguments that open `folder` as a workspace.
+///
+/// Editors take a folder where they take a file; the VS Code family drops
+/// `--goto`, which expects a file.
+fn build_folder_args(kind: EditorKind, folder: &str, wait: bool) -> Vec<String> {
+    match kind {
+        EditorKind::VsCode
+        | EditorKind::VsCodeInsiders
+        | EditorKind::VSCodium
+        | EditorKind::Cursor
+        | EditorKind::Windsurf => {
+            let mut args = vec![folder.to_string()];
+            if wait {
+                args.push("--wait".to_string());
+            }
+            args
+        }
+        _ => build_position_args(kind, folder, None, None, wait),
+    }
+}
+
+/// Inserts `extra_files` right after the argument naming the primary `file`.
+///
+/// The primary file may carry a position suffix (`file:42:7`). Trailing
+/// flags such as `--wait` stay at the end.
+fn insert_extra_files(position_args: &mut Vec<String>, file: &str, extra_files: &[String]) {
+    let names_file = |arg: &String| {
+        arg.strip_prefix(file)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+    };
+    let at = position_args
+        .iter()
+        .rposition(names_file)
+        .map_or(position_args.len(), |index| index + 1);
+    position_args.splice(at..at, extra_files.iter().cloned());
+}
+
+/// Returns the flags that open several files in splits, if the editor has them.
+///
+/// Vim, NeoVim, and GVim use `-O`/`-o` and Helix uses `--vsplit`/`--hsplit`.
+/// Other editors have no CLI split flag and get no extra arguments.
+const fn split_args(kind: EditorKind, split: Split) -> &'static [&'static str] {
+    match (kind, split) {
+        (EditorKind::Vim | EditorKind::NeoVim | EditorKind::GVim, Split::Right) => &["-O"],
+        (EditorKind::Vim | EditorKind::NeoVim | EditorKind::GVim, Split::Below) => &["-o"],
+        (EditorKind::Helix, Split::Right) => &["--vsplit"],
+        (EditorKind::Helix, Split::Below) => &["--hsplit"],
+        _ => &[],
+    }
+}
+
+/// Returns the flag that picks a window for the file, if the editor has one.
+///
+/// The VS Code family uses `--reuse-window`/`--new-window`; Sublime Text
+/// reuses a window by default and uses `--new-window` otherwise.
+const fn window_mode_args(kind: EditorKind, mode: WindowMode) -> &'static [&'static str] {
+    match (kind, mode) {
+        (
+            EditorKind::VsCode
+            | EditorKind::VsCodeInsiders
+            | EditorKind::VSCodium
+            | EditorKind::Cursor
+            | EditorKind::Windsurf,
+            WindowMode::Reuse,
+        ) => &["--reuse-window"],
+        (
+            EditorKind::VsCode
+            | EditorKind::VsCodeInsiders
+            | EditorKind::VSCodium
+            | EditorKind::Cursor
+            | EditorKind::Windsurf
+            | EditorKind::Sublime,
+            WindowMode::New,
+        ) => &["--new-window"],
+        _ => &[],
+    }
+}
+
+/// Returns the flags that skip the user's config files, if the editor has them.
+///
+/// - Vim, GVim: `-u NONE -U NONE` (no vimrc or gvimrc)
+/// - NeoVim: `--clean`
+/// - Emacs: `-Q`
+/// - Nano: `--ignorercfiles`
+/// - VS Code family: `--user-data-dir <data_dir>`
+///
+/// Other editors get no extra arguments.
+fn isolation_args(kind: EditorKind, data_dir: &str) -> Vec<String> {
+    let flags: &[&str] = match kind {
+        EditorKind::Vim | EditorKind::GVim => &["-u", "NONE", "-U", "NONE"],
+        EditorKind::NeoVim => &["--clean"],
+        EditorKind::Emacs => &["-Q"],
+        EditorKind::Nano => &["--ignorercfiles"],
+        EditorKind::VsCode
+        | EditorKind::VsCodeInsiders
+        | EditorKind::VSCodium
+        | EditorKind::Cursor
+        | EditorKind::Windsurf => {
+            return vec!["--user-data-dir".to_string(), data_dir.to_string()];
+        }
+        _ => &[],
+    };
+    flags.iter().map(|s| (*s).to_string()).collect()
+}
+
+/// Returns the arguments that open the file in a given encoding.
+///
+/// - VS Code family: `--encoding <encoding>`
+/// - Vim, GVim, NeoVim: `-c "e ++enc=<encoding>"`, which re-reads the file
+///
+/// Other editors get no extra arguments.
+fn encoding_args(kind: EditorKind, encoding: &str) -> Vec<String> {
+    match kind {
+        EditorKind::VsCode
+        | EditorKind::VsCodeInsiders
+        | EditorKind::VSCodium
+        | EditorKind::Cursor
+        | EditorKind::Windsurf => vec!["--encoding".to_string(), encoding.to_string()],
+        EditorKind::Vim | EditorKind::GVim | EditorKind::NeoVim => {
+            vec!["-c".to_string(), format!("e ++enc={encoding}")]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Expands an argument template with `{file}`, `{line}`, and `{column}`.
+///
+/// An argument referencing a missing line or column is dropped, together
+/// with a preceding option (starting with `-`, without tokens). The file is
+/// appended if no argument references it.
+pub fn build_template_args(
+    template: &[String],
+    file: &str,
+    line: Option<u32>,
+    column: Option<u32>,
+) -> Vec<String> {
+    let mut args: Vec<String> = Vec::with_capacity(template.len() + 1);
+    let mut has_file = false;
+    let mut previous_is_option = false;
+    for arg in template {
+        let missing = (arg.contains("{line}") && line.is_none())
+            || (arg.contains("{column}") && column.is_none());
+        if missing {
+            if previous_is_option {
+                args.pop();
+            }
+            previous_is_option = false;
+            continue;
+        }
+        has_file |= arg.contains("{file}");
+        previous_is_option = arg.starts_with('-') && !arg.contains('{');
+        let mut expanded = arg.replace("{file}", file);
+        if let Some(line) = line {
+            expanded = expanded.replace("{line}", &line.to_string());
+        }
+        if let Some(column) = column {
+            expanded = expanded.replace("{column}", &column.to_string());
+        }
+        args.push(expanded);
+    }
+    if !has_file {
+        args.push(file.to_string());
+    }
+    args
+}
+
+/// Returns the arguments that select the lines from the cursor to `end`.
+///
+/// - Vim, GVim, NeoVim: `-c "normal! V{end}Go"`, a linewise Visual selection
+///   with the cursor moved back to its first line
+///
+/// Other editors get no extra arguments.
+fn selection_args(kind: EditorKind, end: u32) -> Vec<String> {
+    if !kind.supports_selection() {
+        return Vec::new();
+    }
+    vec!["-c".to_string(), format!("normal! V{end}Go")]
+}
+
+/// Returns the arguments that scroll the target line into place.
+///
+/// - Vim, GVim, NeoVim: `-c "normal! zz"` (or `zt`, `zb`)
+///
+/// Other editors get no extra arguments.
+fn scroll_args(kind: EditorKind, scroll: ScrollPosition) -> Vec<String> {
+    if !kind.supports_scroll() {
+        return Vec::new();
+    }
+    let command = match scroll {
+        ScrollPosition::Center => "normal! zz",
+        ScrollPosition::Top => "normal! zt",
+        ScrollPosition::Bottom => "normal! zb",
+    };
+    vec!["-c".to_string(), command.to_string()]
+}
+
+/// Maximum number of `-c` commands Vim accepts on one command line.
+const MAX_VIM_COMMANDS: usize = 10;
+
+/// Builds the arguments for running ex commands on a file without a UI.
+///
+/// Vim and NeoVim run in silent ex mode: `vim -es -c cmd... -c wq file`.
+/// Returns `None` for editors without a batch mode, or when there are more
+/// commands than Vim accepts (one `-c` slot is reserved for `wq`).
+pub fn build_headless_args(kind: EditorKind, file: &str, commands: &[&str]) -> Option<Vec<String>> {
+    if !kind.supports_headless() || commands.len() >= MAX_VIM_COMMANDS {
+        return None;
+    }
+
+    let mut args = vec!["-es".to_string()];
+    for command in commands.iter().chain(&["wq"]) {
+        args.push("-c".to_string());
+        args.push((*command).to_string());
+    }
+    args.push(file.to_string());
+    Some(args)
+}
+
+/// Returns the extra arguments and file target for opening a remote file.
+///
+/// - Vim family: netrw URL (`vim scp://user@host//path`)
+/// - VS Code family: `code --remote ssh-remote+user@host --goto /path`
+///
+/// Other editors can't open remote files; callers check
+/// [`EditorKind::supports_remote()`] first.
+fn build_remote_args(kind: EditorKind, remote: &RemoteFile) -> (Vec<String>, String) {
+    match kind {
+        EditorKind::VsCode
+        | EditorKind::VsCodeInsiders
+        | EditorKind::VSCodium
+        | EditorKind::Cursor
+        | EditorKind::Windsurf => (
+            vec![
+                "--remote".to_string(),
+                format!("ssh-remote+{}", remote.authority()),
+            ],
+            remote.path.clone(),
+        ),
+        _ => (Vec::new(), remote.to_scp_url()),
+    }
+}
+
+/// Builds the file and positioning arguments for an editor.
+pub fn build_position_args(
+    kind: EditorKind,
+    file: &str,
+    line: Option<u32>,
+    column: Option<u32>,
+    wait: bool,
+) -> Vec<String> {
+    match kind {
+        // VS Code family: code -g file:line:column [--wait]
+        EditorKind::VsCode
+        | EditorKind::VsCodeInsiders
+        | EditorKind::VSCodium
+        | EditorKind::Cursor
+        | EditorKind::Windsurf => build_vscode_args(file, line, column, wait),
+
+        // Vim family: vim +call\ cursor(line,col) file
+        EditorKind::Vim | EditorKind::NeoVim | EditorKind::Vi | EditorKind::GVim => {
+            build_vim_args(file, line, column)
+        }
+
+        // Neovide and nvim-qt: neovide --no-fork file -- +call\ cursor(line,col)
+        EditorKind::Neovide | EditorKind::NvimQt => {
+            build_nvim_frontend_args(kind, file, line, column)
+        }
+
+        // VimR: vimr [--wait] --nvim +call\ cursor(line,col) file
+        EditorKind::VimR => build_vimr_args(file, line, column, wait),
+
+        // Emacs: emacs +line:col file [--wait]
+        EditorKind::Emacs => build_emacs_args(file, line, column, wait),
+
+        // Emacs client: emacsclient [-n] +line:col file
+        EditorKind::EmacsClient => build_emacsclient_args(file, line, column, wait),
+
+        // Sublime Text: subl file:line:column [--wait]
+        EditorKind::Sublime => build_sublime_args(file, line, column, wait),
+
+        // Zed: zed file:line:column [--wait]
+        EditorKind::Zed => build_zed_args(file, line, column, wait),
+
+        // Helix: hx file:line:column
+        EditorKind::Helix => build_helix_args(file, line, column),
+
+        // Nano: nano +line,col file
+        EditorKind::Nano => build_nano_args(file, line, column),
+
+        // Ex: ex +line file
+        EditorKind::Ex => build_ex_args(file, line),
+
+        // Kakoune: kak +line:col file
+        EditorKind::Kakoune => build_kakoune_args(file, line, column),
+
+        // Ed: no startup line option, just pass the file
+        EditorKind::Ed => {
+            vec![file.to_string()]
+        }
+
+        // TextMate: mate --line line file [--wait]
+        EditorKind::TextMate => build_textmate_args(file, line, wait),
+
+        // Notepad++: notepad++ -nLINE -cCOL file
+        EditorKind::NotepadPlusPlus => build_notepadpp_args(file, line, column),
+
+        // JetBrains IDEs: idea --line LINE --column COL file [--wait]
+        EditorKind::IntelliJ
+        | EditorKind::WebStorm
+        | EditorKind::PhpStorm
+        | EditorKind::PyCharm
+        | EditorKind::RubyMine
+        | EditorKind::GoLand
+        | EditorKind::CLion
+        | EditorKind::Rider
+        | EditorKind::DataGrip
+        | EditorKind::AndroidStudio => build_jetbrains_args(file, line, column, wait),
+
+        // Xcode: xed --line LINE file
+        EditorKind::Xcode => build_xcode_args(file, line, wait),
+
+        // Kate: kate --line LINE --column COL file
+        EditorKind::Kate => build_kate_args(file, line, column),
+
+        // Atom (deprecated but still used): atom file:line:column [--wait]
+        EditorKind::Atom => build_atom_args(file, line, column, wait),
+
+        // Notepad (Windows): no line/column support
+        EditorKind::Notepad => {
+            vec![file.to_string()]
+        }
+
+        // Unknown editor: just pass the file
+        EditorKind::Unknown => {
+            vec![file.to_string()]
+        }
+    }
+}
+
+/// VS Code family: `code -g file:line:column [--wait]`
+fn build_vscode_args(
+    file: &str,
+    line: Option<u32>,
+    column: Option<u32>,
+    wait: bool,
+) -> Vec<String> {
+    let mut args = Vec::new();
+
+    // Use --goto flag for line:column positioning
+    args.push("--goto".to_string());
+
+    let position = colon_position(file, line, column);
+    args.push(position);
+
+    if wait {
+        args.push("--wait".to_string());
+    }
+
+    args
+}
+
+/// Appends a `:line:column` suffix to `file`, as in `file:42:7`.
+///
+/// A Windows path starting with a drive letter (`C:\src\main.rs`) already
+/// contains a colon, so `C:\src\main.rs:42` has two: editors that split the
+/// position off from the right take the drive colon for the line separator.
+/// For such paths the column is always written (`1` if unset), so the last
+/// two colons are always the position's.
+fn colon_position(file: &str, line: Option<u32>, column: Option<u32>) -> String {
+    match (line, column) {
+        (Some(l), Some(c)) => format!("{file}:{l}:{c}"),
+        (Some(l), None) if has_drive_letter(file) => format!("{file}:{l}:1"),
+        (Some(l), None) => format!("{file}:{l}"),
+        _ => file.to_string(),
+    }
+}
+
+/// Returns `true` if `file` is a Windows path with a drive letter, like
+/// `C:\src` or `c:/src`.
+fn has_drive_letter(file: &str) -> bool {
+    matches!(file.as_bytes(), [drive, b':', b'\\' | b'/', ..] if drive.is_ascii_alphabetic())
+}
+
+/// Vim family: `vim +call\ cursor(line,col) file` or `vim +LINE file`
+fn build_vim_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
+    match (line, column) {
+        (Some(l), Some(c)) => {
+            vec![format!("+call cursor({l},{c})"), file.to_string()]
+        }
+        (Some(l), None) => {
+            vec![format!("+{l}"), file.to_string()]
+        }
+        _ => vec![file.to_string()],
+    }
+}
+
+/// Neovide and nvim-qt: `neovide --no-fork file -- +call\ cursor(line,col)`
+///
+/// Both fork into the background by default; the no-fork flag keeps them
+/// in the foreground like GVim with `-f`. Arguments after `--` go to the
+/// embedded nvim, so positioning is the same as NeoVim's.
+fn build_nvim_frontend_args(
+    kind: EditorKind,
+    file: &str,
+    line: Option<u32>,
+    column: Option<u32>,
+) -> Vec<String> {
+    let no_fork = if kind == EditorKind::NvimQt {
+        "--nofork"
+    } else {
+        "--no-fork"
+    };
+    let mut args = vec![no_fork.to_string(), file.to_string()];
+    if let [command, _] = build_vim_args(file, line, column).as_slice() {
+        args.push("--".to_string());
+        args.push(command.clone());
+    }
+    args
+}
+
+/// VimR: `vimr [--wait] --nvim +call\ cursor(line,col) file`
+///
+/// Everything after `--nvim` goes to nvim, so `--wait` comes first.
+fn build_vimr_args(file: &str, line: Option<u32>, column: Option<u32>, wait: bool) -> Vec<String> {
+    let mut args = Vec::new();
+    if wait {
+        args.push("--wait".to_string());
+    }
+    let nvim_args = build_vim_args(file, line, column);
+    if nvim_args.len() > 1 {
+        args.push("--nvim".to_string());
+    }
+    args.extend(nvim_args);
+    args
+}
+
+/// Vim family, `normal!` style: `vim +LINE -c "normal! COL|" file`
+fn build_vim_normal_args(file: &str, line: u32, column: Option<u32>) -> Vec<String> {
+    match column {
+        Some(c) => vec![
+            format!("+{line}"),
+            "-c".to_string(),
+            format!("normal! {c}|"),
+            file.to_string(),
+        ],
+        None => vec![format!("+{line}"), file.to_string()],
+    }
+}
+
+/// Emacs: `emacs +line:col file`
+fn build_emacs_args(file: &str, line: Option<u32>, column: Option<u32>, wait: bool) -> Vec<String> {
+    let mut args = Vec::new();
+
+    match (line, column) {
+        (Some(l), Some(c)) => args.push(format!("+{l}:{c}")),
+        (Some(l), None) => args.push(format!("+{l}")),
+        _ => {}
+    }
+
+    args.push(file.to_string());
+
+    if wait {
+        args.push("--eval".to_string());
+        args.push("(while (get-buffer-window) (sit-for 1))".to_string());
+    }
+
+    args
+}
+
+/// Emacs client: `emacsclient [-n] +line:col file`
+///
+/// `emacsclient` blocks until the buffer is done by default, so `-n` is
+/// passed when not waiting.
+fn build_emacsclient_args(
+    file: &str,
+    line: Option<u32>,
+    column: Option<u32>,
+    wait: bool,
+) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if !wait {
+        args.push("-n".to_string());
+    }
+
+    match (line, column) {
+        (Some(l), Some(c)) => args.push(format!("+{l}:{c}")),
+        (Some(l), None) => args.push(format!("+{l}")),
+        _ => {}
+    }
+
+    args.push(file.to_string());
+    args
+}
+
+/// Sublime Text: `subl file:line:column [--wait]`
+fn build_sublime_args(
+    file: &str,
+    line: Option<u32>,
+    column: Option<u32>,
+    wait: bool,
+) -> Vec<String> {
+    let mut args = Vec::new();
+
+    let position = colon_position(file, line, column);
+    args.push(position);
+
+    if wait {
+        args.push("--wait".to_string());
+    }
+
+    args
+}
+
+/// Zed: `zed file:line:column [--wait]`
+fn build_zed_args(file: &str, line: Option<u32>, column: Option<u32>, wait: bool) -> Vec<String> {
+    let mut args = Vec::new();
+
+    let position = colon_position(file, line, column);
+    args.push(position);
+
+    if wait {
+        args.push("--wait".to_string());
+    }
+
+    args
+}
+
+/// Helix: `hx file:line:column`
+fn build_helix_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
+    let position = colon_position(file, line, column);
+    vec![position]
+}
+
+/// Nano: `nano +line,col file`
+fn build_nano_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
+    match (line, column) {
+        (Some(l), Some(c)) => {
+            vec![format!("+{l},{c}"), file.to_string()]
+        }
+        (Some(l), None) => {
+            vec![format!("+{l}"), file.to_string()]
+        }
+        _ => vec![file.to_string()],
+    }
+}
+
+/// Kakoune: `kak +line:col file`
+fn build_kakoune_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
+    match (line, column) {
+        (Some(l), Some(c)) => {
+            vec![format!("+{l}:{c}"), file.to_string()]
+        }
+        (Some(l), None) => {
+            vec![format!("+{l}"), file.to_string()]
+        }
+        _ => vec![file.to_string()],
+    }
+}
+
+/// Ex: `ex +line file`
+fn build_ex_args(file: &str, line: Option<u32>) -> Vec<String> {
+    match line {
+        Some(l) => vec![format!("+{l}"), file.to_string()],
+        None => vec![file.to_string()],
+    }
+}
+
+/// TextMate: `mate --line line file [--wait]`
+fn build_textmate_args(file: &str, line: Option<u32>, wait: bool) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if let Some(l) = line {
+        args.push("--line".to_string());
+        args.push(l.to_string());
+    }
+
+    args.push(file.to_string());
+
+    if wait {
+        args.push("--wait".to_string());
+    }
+
+    args
+}
+
+/// Notepad++: `notepad++ -nLINE -cCOL file`
+fn build_notepadpp_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if let Some(l) = line {
+        args.push(format!("-n{l}"));
+    }
+
+    if let Some(c) = column {
+        args.push(format!("-c{c}"));
+    }
+
+    args.push(file.to_string());
+    args
+}
+
+/// JetBrains IDEs: `idea --line LINE --column COL file [--wait]`
+///
+/// Without a column, the older `idea file:line` form is used, except for
+/// Windows paths with a drive letter, which get `--line LINE file` instead
+/// of a second colon.
+fn build_jetbrains_args(
+    file: &str,
+    line: Option<u32>,
+    column: Option<u32>,
+    wait: bool,
+) -> Vec<String> {
+    let mut args = Vec::new();
+
+    match (line, column) {
+        (Some(l), None) if has_drive_letter(file) => {
+            args.push("--line".to_string());
+            args.push(l.to_string());
+            args.push(file.to_string());
+        }
+        (Some(l), Some(c)) => {
+            args.push("--line".to_string());
+            args.push(l.to_string());
+            args.push("--column".to_string());
+            args.push(c.to_string());
+            args.push(file.to_string());
+        }
+        (Some(l), None) => args.push(format!("{file}:{l}")),
+        (None, _) => args.push(file.to_string()),
+    }
+
+    if wait {
+        args.push("--wait".to_string());
+    }
+
+    args
+}
+
+/// Xcode: `xed --line LINE file`
+fn build_xcode_args(file: &str, line: Option<u32>, wait: bool) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if let Some(l) = line {
+        args.push("--line".to_string());
+        args.push(l.to_string());
+    }
+
+    args.push(file.to_string());
+
+    if wait {
+        args.push("--wait".to_string());
+    }
+
+    args
+}
+
+/// Kate: `kate --line LINE --column COL file`
+fn build_kate_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if let Some(l) = line {
+        args.push("--line".to_string());
+        args.push(l.to_string());
+    }
+
+    if let Some(c) = column {
+        args.push("--column".to_string());
+        args.push(c.to_string());
+    }
+
+    args.push(file.to_string());
+    args
+}
+
+/// Atom: `atom file:line:column [--wait]`
+fn build_atom_args(file: &str, line: Option<u32>, column: Option<u32>, wait: bool) -> Vec<String> {
+    let mut args = Vec::new();
+
+    let position = colon_position(file, line, column);
+    args.push(position);
+
+    if wait {
+        args.push("--wait".to_string());
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expands `Kind => [args...]` pairs into a `(kind, expected args)` table.
+    macro_rules! snapshots {
+        ($($($kind:ident)|+ => $args:tt),* $(,)?) => {
+            vec![$($((EditorKind::$kind, vec!$args),)+)*]
+        };
+    }
+
+    /// Expected arguments for `src/main.rs` at line 42, column 7, with wait.
+    fn position_snapshots() -> Vec<(EditorKind, Vec<&'static str>)> {
+        snapshots! {
+            VsCode | VsCodeInsiders | VSCodium | Cursor | Windsurf => [
+                "--goto",
+                "src/main.rs:42:7",
+                "--wait",
+            ],
+            Vim | NeoVim | Vi | GVim => ["+call cursor(42,7)", "src/main.rs"],
+            Neovide => ["--no-fork", "src/main.rs", "--", "+call cursor(42,7)"],
+            NvimQt => ["--nofork", "src/main.rs", "--", "+call cursor(42,7)"],
+            VimR => ["--wait", "--nvim", "+call cursor(42,7)", "src/main.rs"],
+            Emacs => ["+42:7", "src/main.rs", "--eval", "(while (get-buffer-window) (sit-for 1))"],
+            EmacsClient => ["+42:7", "src/main.rs"],
+            Sublime | Zed | Atom => ["src/main.rs:42:7", "--wait"],
+            Helix => ["src/main.rs:42:7"],
+            Kate => ["--line", "42", "--column", "7", "src/main.rs"],
+            Nano => ["+42,7", "src/main.rs"],
+            Ex => ["+42", "src/main.rs"],
+            Kakoune => ["+42:7", "src/main.rs"],
+            TextMate | Xcode => ["--line", "42", "src/main.rs", "--wait"],
+            NotepadPlusPlus => ["-n42", "-c7", "src/main.rs"],
+            IntelliJ | WebStorm | PhpStorm | PyCharm | RubyMine | GoLand | CLion | Rider | DataGrip
+                | AndroidStudio => ["--line", "42", "--column", "7", "src/main.rs", "--wait"],
+            Ed | Notepad | Unknown => ["src/main.rs"],
+        }
+    }
+
+    #[test]
+    fn test_position_snapshots_cover_every_editor() {
+        let snapshots = position_snapshots();
+        for kind in EditorKind::all() {
+            let count = snapshots
+                .iter()
+                .filter(|(snapshot_kind, _)| snapshot_kind == kind)
+                .count();
+            assert_eq!(count, 1, "{kind:?} needs exactly one snapshot");
+        }
+        assert_eq!(snapshots.len(), EditorKind::all().len());
+    }
+
+    #[test]
+    fn test_position_snapshots() {
+        for (kind, expected) in position_snapshots() {
+            let args = build_args(
+                kind,
+                "src/main.rs",
+                &ArgOptions {
+                    line: Some(42),
+                    column: Some(7),
+                    wait: true,
+                    ..ArgOptions::default()
+                },
+            );
+            assert_eq!(args, expected, "{kind:?}");
+            assert_eq!(
+                kind.position_args("src/main.rs", Some(42), Some(7), true),
+                expected,
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_emacsclient_wait_and_no_wait() {
+        let options = |wait| ArgOptions {
+            line: Some(42),
+            column: Some(10),
+            wait,
+            ..ArgOptions::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::EmacsClient, "a.rs", &options(true)),
+            vec!["+42:10", "a.rs"]
+        );
+        assert_eq!(
+            build_args(EditorKind::EmacsClient, "a.rs", &options(false)),
+            vec!["-n", "+42:10", "a.rs"]
+        );
+    }
+
+    #[test]
+    fn test_emacsclient_starts_daemon() {
+        let options = ArgOptions {
+            line: Some(42),
+            start_emacs_daemon: true,
+            ..ArgOptions::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::EmacsClient, "a.rs", &options),
+            vec!["-a", "", "-n", "+42", "a.rs"]
+        );
+        assert_eq!(
+            build_args(EditorKind::Emacs, "a.rs", &options),
+            vec!["+42", "a.rs"]
+        );
+    }
+
+    #[test]
+    fn test_template_substitution() {
+        let template: Vec<String> = ["--open", "{file}", "--goto={line}:{column}"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            build_template_args(&template, "a.rs", Some(42), Some(7)),
+            vec!["--open", "a.rs", "--goto=42:7"]
+        );
+
+        // No `{file}`: the file goes last
+        let template: Vec<String> = ["-l", "{line}"].map(String::from).to_vec();
+        assert_eq!(
+            build_template_args(&template, "a.rs", Some(3), None),
+            vec!["-l", "3", "a.rs"]
+        );
+    }
+
+    #[test]
+    fn test_template_drops_missing_values() {
+        let template: Vec<String> = [
+            "--open",
+            "{file}",
+            "--goto-line",
+            "{line}",
+            "--col",
+            "{column}",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(
+            build_template_args(&template, "a.rs", None, None),
+            vec!["--open", "a.rs"]
+        );
+        assert_eq!(
+            build_template_args(&template, "a.rs", Some(42), None),
+            vec!["--open", "a.rs", "--goto-line", "42"]
+        );
+
+        // A self-contained argument goes alone; non-option arguments stay
+        let template: Vec<String> = ["{file}", "--line={line}", "+{line}"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            build_template_args(&template, "a.rs", None, None),
+            vec!["a.rs"]
+        );
+    }
+
+    #[test]
+    fn test_template_overrides_builtin_args() {
+        let options = ArgOptions {
+            line: Some(42),
+            wait: true,
+            read_only: true,
+            template: Some(vec!["{file}@{line}".to_string()]),
+            ..ArgOptions::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::VsCode, "a.rs", &options),
+            vec!["a.rs@42"]
+        );
+    }
+
+    #[test]
+    fn test_vim_scroll_commands() {
+        let options = |scroll| ArgOptions {
+            line: Some(42),
+            scroll: Some(scroll),
+            ..ArgOptions::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::Vim, "a.rs", &options(ScrollPosition::Center)),
+            vec!["+42", "a.rs", "-c", "normal! zz"]
+        );
+        assert_eq!(
+            build_args(EditorKind::NeoVim, "a.rs", &options(ScrollPosition::Top)),
+            vec!["+42", "a.rs", "-c", "normal! zt"]
+        );
+        assert_eq!(
+            build_args(EditorKind::GVim, "a.rs", &options(ScrollPosition::Bottom)),
+            vec!["+42", "a.rs", "-c", "normal! zb"]
+        );
+    }
+
+    #[test]
+    fn test_vim_selection_commands() {
+        let options = ArgOptions {
+            line: Some(42),
+            select_to: Some(50),
+            scroll: Some(ScrollPosition::Center),
+            ..ArgOptions::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::Vim, "a.rs", &options),
+            vec!["+42", "a.rs", "-c", "normal! V50Go", "-c", "normal! zz"]
+        );
+        assert_eq!(
+            build_args(EditorKind::VsCode, "a.rs", &options),
+            vec!["--goto", "a.rs:42"]
+        );
+    }
+
+    #[test]
+    fn test_scroll_ignored_without_line_or_support() {
+        let options = ArgOptions {
+            scroll: Some(ScrollPosition::Center),
+            ..ArgOptions::default()
+        };
+        assert_eq!(build_args(EditorKind::Vim, "a.rs", &options), vec!["a.rs"]);
+
+        let options = ArgOptions {
+            line: Some(42),
+            scroll: Some(ScrollPosition::Center),
+            ..ArgOptions::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::VsCode, "a.rs", &options),
+            vec!["--goto", "a.rs:42"]
+        );
+    }
+
+    #[test]
+    fn test_extra_files_follow_the_primary_file() {
+        let options = ArgOptions {
+            line: Some(42),
+            column: Some(7),
+            wait: true,
+            extra_files: vec!["b.rs".to_string(), "c.rs".to_string()],
+            ..ArgOptions::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::Vim, "a.rs", &options),
+            vec!["+call cursor(42,7)", "a.rs", "b.rs", "c.rs"]
+        );
+        assert_eq!(
+            build_args(EditorKind::VsCode, "a.rs", &options),
+            vec!["--goto", "a.rs:42:7", "b.rs", "c.rs", "--wait"]
+        );
+        assert_eq!(
+            build_args(EditorKind::Nano, "a.rs", &options),
+            vec!["+42,7", "a.rs", "b.rs", "c.rs"]
+        );
+    }
+
+    #[test]
+    fn test_every_editor_receives_the_file() {
+        for kind in EditorKind::all() {
+            let args = kind.position_args("src/main.rs", None, None, false);
+            assert!(
+                args.iter().any(|arg| arg == "src/main.rs"),
+                "{kind:?}: {args:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_vscode_args() {
+        let args = build_vscode_args("test.rs", Some(42), Some(10), false);
+        assert_eq!(args, vec!["--goto", "test.rs:42:10"]);
+
+        let args = build_vscode_args("test.rs", Some(42), None, false);
+        assert_eq!(args, vec!["--goto", "test.rs:42"]);
+
+        let args = build_vscode_args("test.rs", None, None, true);
+        assert_eq!(args, vec!["--goto", "test.rs", "--wait"]);
+    }
+
+    #[test]
+    fn test_vim_args() {
+        let args = build_vim_args("test.rs", Some(42), Some(10));
+        assert_eq!(args, vec!["+call cursor(42,10)", "test.rs"]);
+
+        let args = build_vim_args("test.rs", Some(42), None);
+        assert_eq!(args, vec!["+42", "test.rs"]);
+
+        let args = build_vim_args("test.rs", None, None);
+        assert_eq!(args, vec!["test.rs"]);
+    }
+
+    #[test]
+    fn test_nano_args() {
+        let args = build_nano_args("test.rs", Some(42), Some(10));
+        assert_eq!(args, vec!["+42,10", "test.rs"]);
+
+        let args = build_nano_args("test.rs", Some(42), None);
+        assert_eq!(args, vec!["+42", "test.rs"]);
+    }
+
+    #[test]
+    fn test_ex_args() {
+        let args = build_ex_args("test.rs", Some(42));
+        assert_eq!(args, vec!["+42", "test.rs"]);
+
+        let args = build_ex_args("test.rs", None);
+        assert_eq!(args, vec!["test.rs"]);
+    }
+
+    #[test]
+    fn test_ed_ignores_position() {
+        let args = EditorKind::Ed.position_args("test.rs", Some(42), Some(10), false);
+        assert_eq!(args, vec!["test.rs"]);
+    }
+
+    #[test]
+    fn test_emacs_args() {
+        let args = build_emacs_args("test.rs", Some(42), Some(10), false);
+        assert_eq!(args, vec!["+42:10", "test.rs"]);
+    }
+
+    #[test]
+    fn test_notepadpp_args() {
+        let args = build_notepadpp_args("test.rs", Some(42), Some(10));
+        assert_eq!(args, vec!["-n42", "-c10", "test.rs"]);
+    }
+
+    #[test]
+    fn test_jetbrains_args() {
+        let args = build_jetbrains_args("test.rs", Some(42), None, false);
+        assert_eq!(args, vec!["test.rs:42"]);
+
+        let args = build_jetbrains_args("test.rs", Some(42), None, true);
+        assert_eq!(args, vec!["test.rs:42", "--wait"]);
+
+        let args = build_jetbrains_args("test.rs", None, None, false);
+        assert_eq!(args, vec!["test.rs"]);
+    }
+
+    #[test]
+    fn test_jetbrains_args_with_column() {
+        let args = build_jetbrains_args("test.rs", Some(42), Some(10), false);
+        assert_eq!(args, vec!["--line", "42", "--column", "10", "test.rs"]);
+
+        for kind in [
+            EditorKind::IntelliJ,
+            EditorKind::PyCharm,
+            EditorKind::GoLand,
+            EditorKind::Rider,
+        ] {
+            let options = ArgOptions {
+                line: Some(42),
+                column: Some(10),
+                wait: true,
+                ..Default::default()
+            };
+            assert_eq!(
+                build_args(kind, "test.rs", &options),
+                vec!["--line", "42", "--column", "10", "test.rs", "--wait"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_windows_drive_paths_always_get_column() {
+        let file = r"C:\src\main.rs";
+        assert_eq!(
+            build_vscode_args(file, Some(42), None, false),
+            vec!["--goto", r"C:\src\main.rs:42:1"]
+        );
+        assert_eq!(
+            build_sublime_args(file, Some(42), None, false),
+            vec![r"C:\src\main.rs:42:1"]
+        );
+        assert_eq!(
+            build_zed_args(file, Some(42), None, true),
+            vec![r"C:\src\main.rs:42:1", "--wait"]
+        );
+        assert_eq!(
+            build_helix_args("d:/src/main.rs", Some(42), None),
+            vec!["d:/src/main.rs:42:1"]
+        );
+        assert_eq!(
+            build_atom_args(file, Some(42), None, false),
+            vec![r"C:\src\main.rs:42:1"]
+        );
+        assert_eq!(
+            build_jetbrains_args(file, Some(42), None, false),
+            vec!["--line", "42", r"C:\src\main.rs"]
+        );
+
+        // An explicit column, or no position at all, is unchanged
+        assert_eq!(
+            build_sublime_args(file, Some(42), Some(10), false),
+            vec![r"C:\src\main.rs:42:10"]
+        );
+        assert_eq!(
+            build_zed_args(file, None, None, false),
+            vec![r"C:\src\main.rs"]
+        );
+    }
+
+    #[test]
+    fn test_drive_letter_detection() {
+        assert!(has_drive_letter(r"C:\src\main.rs"));
+        assert!(has_drive_letter("c:/src/main.rs"));
+        assert!(!has_drive_letter("src/main.rs"));
+        assert!(!has_drive_letter("/home/me/c:/main.rs"));
+        assert!(!has_drive_letter("C:main.rs"));
+        assert!(!has_drive_letter(r"\\server\share\main.rs"));
+        assert!(!has_drive_letter("1:/main.rs"));
+    }
+
+    #[test]
+    fn test_nvim_frontend_args() {
+        assert_eq!(
+            build_nvim_frontend_args(EditorKind::Neovide, "test.rs", Some(42), Some(10)),
+            vec!["--no-fork", "test.rs", "--", "+call cursor(42,10)"]
+        );
+        assert_eq!(
+            build_nvim_frontend_args(EditorKind::NvimQt, "test.rs", Some(42), None),
+            vec!["--nofork", "test.rs", "--", "+42"]
+        );
+        assert_eq!(
+            build_nvim_frontend_args(EditorKind::Neovide, "test.rs", None, None),
+            vec!["--no-fork", "test.rs"]
+        );
+    }
+
+    #[test]
+    fn test_vimr_args() {
+        assert_eq!(
+            build_vimr_args("test.rs", Some(42), Some(10), false),
+            vec!["--nvim", "+call cursor(42,10)", "test.rs"]
+        );
+        assert_eq!(
+            build_vimr_args("test.rs", Some(42), None, true),
+            vec!["--wait", "--nvim", "+42", "test.rs"]
+        );
+        assert_eq!(
+            build_vimr_args("test.rs", None, None, true),
+            vec!["--wait", "test.rs"]
+        );
+    }
+
+    #[test]
+    fn test_kakoune_args() {
+        assert_eq!(
+            build_kakoune_args("test.rs", Some(42), Some(10)),
+            vec!["+42:10", "test.rs"]
+        );
+        assert_eq!(
+            build_kakoune_args("test.rs", Some(42), None),
+            vec!["+42", "test.rs"]
+        );
+        assert_eq!(build_kakoune_args("test.rs", None, None), vec!["test.rs"]);
+        // A column alone can't be placed
+        assert_eq!(
+            build_kakoune_args("test.rs", None, Some(10)),
+            vec!["test.rs"]
+        );
+    }
+
+    #[test]
+    fn test_helix_args() {
+        let args = build_helix_args("test.rs", Some(42), Some(10));
+        assert_eq!(args, vec!["test.rs:42:10"]);
+    }
+
+    #[test]
+    fn test_split_args() {
+        let options = ArgOptions {
+            line: Some(42),
+            split: Some(Split::Right),
+            extra_files: vec!["b.rs".to_string()],
+            ..Default::default()
+        };
+        let args = build_args(EditorKind::Vim, "test.rs", &options);
+        assert_eq!(args, vec!["-O", "+42", "test.rs", "b.rs"]);
+
+        let options = ArgOptions {
+            split: Some(Split::Below),
+            ..options
+        };
+        let args = build_args(EditorKind::Helix, "test.rs", &options);
+        assert_eq!(args, vec!["--hsplit", "test.rs:42", "b.rs"]);
+
+        // Editors without a split flag ignore the option
+        let args = build_args(EditorKind::VsCode, "test.rs", &options);
+        assert_eq!(args, vec!["--goto", "test.rs:42", "b.rs"]);
+        let args = build_args(EditorKind::Vi, "test.rs", &options);
+        assert_eq!(args, vec!["+42", "test.rs", "b.rs"]);
+    }
+
+    #[test]
+    fn test_split_needs_several_files() {
+        // `-O` and `--vsplit` only arrange the files given; one file isn't split
+        let options = ArgOptions {
+            line: Some(42),
+            split: Some(Split::Right),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::Vim, "test.rs", &options),
+            vec!["+42", "test.rs"]
+        );
+        assert_eq!(
+            build_args(EditorKind::Helix, "test.rs", &options),
+            vec!["test.rs:42"]
+        );
+    }
+
+    #[test]
+    fn test_folder_args() {
+        let options = ArgOptions {
+            line: Some(42),
+            column: Some(7),
+            folder: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::VsCode, "proj", &options),
+            vec!["proj"]
+        );
+        assert_eq!(build_args(EditorKind::Zed, "proj", &options), vec!["proj"]);
+        assert_eq!(
+            build_args(EditorKind::NeoVim, "proj", &options),
+            vec!["proj"]
+        );
+        assert_eq!(
+            build_args(EditorKind::IntelliJ, "proj", &options),
+            vec!["proj"]
+        );
+
+        let options = ArgOptions {
+            folder: true,
+            wait: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_args(EditorKind::VsCode, "proj", &options),
+            vec!["proj", "--wait"]
+        );
+        assert_eq!(
+            build_args(EditorKind::Zed, "proj", &options),
+            vec!["proj", "--wait"]
+        );
+    }
+
+    #[test]
+    fn test_background_args() {
+        let options = |background| ArgOptions {
+            line: Some(42),
+            background,
+            ..Default::default()
+        };
+
+        let args = build_args(EditorKind::Sublime, "test.rs", &options(true));
+        assert_eq!(args, vec!["--background", "test.rs:42"]);
+        let args = build_args(EditorKind::Sublime, "test.rs", &options(false));
+        assert_eq!(args, vec!["test.rs:42"]);
+
+        // Editors without a background flag ignore the option
+        for kind in EditorKind::all()
+            .iter()
+            .filter(|kind| !kind.supports_background())
+        {
+            assert_eq!(
+                build_args(*kind, "test.rs", &options(true)),
+                build_args(*kind, "test.rs", &options(false)),
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_window_mode_args() {
+        let options = |window_mode| ArgOptions {
+            line: Some(42),
+            window_mode,
+            ..Default::default()
+        };
+
+        let args = build_args(EditorKind::VsCode, "test.rs", &options(WindowMode::Reuse));
+        assert_eq!(args, vec!["--reuse-window", "--goto", "test.rs:42"]);
+        let args = build_args(EditorKind::Cursor, "test.rs", &options(WindowMode::New));
+        assert_eq!(args, vec!["--new-window", "--goto", "test.rs:42"]);
+        let args = build_args(EditorKind::VsCode, "test.rs", &options(WindowMode::Default));
+        assert_eq!(args, vec!["--goto", "test.rs:42"]);
+
+        let args = build_args(EditorKind::Sublime, "test.rs", &options(WindowMode::New));
+        assert_eq!(args, vec!["--new-window", "test.rs:42"]);
+        let args = build_args(EditorKind::Sublime, "test.rs", &options(WindowMode::Reuse));
+        assert_eq!(args, vec!["test.rs:42"]);
+
+        // Editors without window flags ignore the option
+        for kind in EditorKind::all()
+            .iter()
+            .filter(|kind| !kind.supports_window_mode())
+        {
+            assert_eq!(
+                build_args(*kind, "test.rs", &options(WindowMode::New)),
+                build_args(*kind, "test.rs", &options(WindowMode::Default)),
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_multiple_positions_args() {
+        let options = ArgOptions {
+            line: Some(1),
+            column: Some(2),
+            extra_positions: vec![(10, 4), (20, 8)],
+            ..Default::default()
+        };
+        let args = build_args(EditorKind::VsCode, "test.rs", &options);
+        assert_eq!(
+            args,
+            vec![
+                "--goto",
+                "test.rs:1:2",
+                "--goto",
+                "test.rs:10:4",
+                "--goto",
+                "test.rs:20:8"
+            ]
+        );
+
+        // Editors that take one target only get the first position
+        let args = build_args(EditorKind::Vim, "test.rs", &options);
+        assert_eq!(args, vec!["+call cursor(1,2)", "test.rs"]);
+    }
+
+    #[test]
+    fn test_remote_args() {
+        let options = ArgOptions {
+            line: Some(42),
+            column: Some(10),
+            remote: RemoteFile::parse("alice@devbox:/srv/app/main.rs"),
+            ..Default::default()
+        };
+        let file = "alice@devbox:/srv/app/main.rs";
+
+        let args = build_args(EditorKind::Vim, file, &options);
+        assert_eq!(
+            args,
+            vec!["+call cursor(42,10)", "scp://alice@devbox//srv/app/main.rs"]
+        );
+
+        let args = build_args(EditorKind::VsCode, file, &options);
+        assert_eq!(
+            args,
+            vec![
+                "--remote",
+                "ssh-remote+alice@devbox",
+                "--goto",
+                "/srv/app/main.rs:42:10"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_only_args() {
+        let options = ArgOptions {
+            line: Some(42),
+            read_only: true,
+            ..Default::default()
+        };
+        let file = "test.rs";
+
+        assert_eq!(
+            build_args(EditorKind::Vim, file, &options),
+            vec!["-R", "+42", "test.rs"]
+        );
+        assert_eq!(
+            build_args(EditorKind::Nano, file, &options),
+            vec!["-v", "+42", "test.rs"]
+        );
+
+        // VS Code has no read-only flag, so nothing is added
+        assert_eq!(
+            build_args(EditorKind::VsCode, file, &options),
+            vec!["--goto", "test.rs:42"]
+        );
+    }
+
+    #[test]
+    fn test_vim_position_styles() {
+        let options = |vim_position_style| ArgOptions {
+            line: Some(42),
+            column: Some(7),
+            vim_position_style,
+            ..Default::default()
+        };
+        let file = "test.rs";
+
+        assert_eq!(
+            build_args(
+                EditorKind::Vim,
+                file,
+                &options(VimPositionStyle::CursorCall)
+            ),
+            vec!["+call cursor(42,7)", "test.rs"]
+        );
+        assert_eq!(
+            build_args(
+                EditorKind::NeoVim,
+                file,
+                &options(VimPositionStyle::GotoLineNormal)
+            ),
+            vec!["+42", "-c", "normal! 7|", "test.rs"]
+        );
+        assert_eq!(
+            build_args(
+                EditorKind::Vim,
+                file,
+                &ArgOptions {
+                    column: None,
+                    ..options(VimPositionStyle::GotoLineNormal)
+                }
+            ),
+            vec!["+42", "test.rs"]
+        );
+
+        // Other editors ignore the style
+        assert_eq!(
+            build_args(
+                EditorKind::Nano,
+                file,
+                &options(VimPositionStyle::GotoLineNormal)
+            ),
+            vec!["+42,7", "test.rs"]
+        );
+    }
+
+    #[test]
+    fn test_encoding_args() {
+        let options = ArgOptions {
+            line: Some(42),
+            column: Some(7),
+            encoding: Some("latin1".to_string()),
+            ..Default::default()
+        };
+        let file = "legacy.txt";
+
+        assert_eq!(
+            build_args(EditorKind::VsCode, file, &options),
+            vec!["--encoding", "latin1", "--goto", "legacy.txt:42:7"]
+        );
+        assert_eq!(
+            build_args(EditorKind::Vim, file, &options),
+            vec!["-c", "e ++enc=latin1", "+call cursor(42,7)", "legacy.txt"]
+        );
+        assert_eq!(
+            build_args(EditorKind::Nano, file, &options),
+            vec!["+42,7", "legacy.txt"]
+        );
+    }
+
+    #[test]
+    fn test_isolated_args() {
+        let options = ArgOptions {
+            line: Some(42),
+            isolated: Some("/tmp/opensesame-isolated".to_string()),
+            ..Default::default()
+        };
+        let file = "test.rs";
+
+        assert_eq!(
+            build_args(EditorKind::Vim, file, &options),
+            vec!["-u", "NONE", "-U", "NONE", "+42", "test.rs"]
+        );
+        assert_eq!(
+            build_args(EditorKind::NeoVim, file, &options),
+            vec!["--clean", "+42", "test.rs"]
+        );
+        assert_eq!(
+            build_args(EditorKind::VsCode, file, &options),
+            vec![
+                "--user-data-dir",
+                "/tmp/opensesame-isolated",
+                "--goto",
+                "test.rs:42"
+            ]
+        );
+
+        // Kate has no isolation flag, so nothing is added
+        assert_eq!(
+            build_args(EditorKind::Kate, file, &options),
+            vec!["--line", "42", "test.rs"]
+        );
+    }
+
+    #[test]
+    fn test_plus_line_fallback_for_unknown() {
+        let file = "test.rs";
+        let mut options = ArgOptions {
+            line: Some(42),
+            column: Some(7),
+            plus_line_fallback: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            build_args(EditorKind::Unknown, file, &options),
+            vec!["+42", "test.rs"]
+        );
+        // Known editors keep their own syntax
+        assert_eq!(
+            build_args(EditorKind::Nano, file, &options),
+            vec!["+42,7", "test.rs"]
+        );
+
+        options.plus_line_fallback = false;
+        assert_eq!(
+            build_args(EditorKind::Unknown, file, &options),
+            vec!["test.rs"]
+        );
+
+        options.plus_line_fallback = true;
+        options.line = None;
+        assert_eq!(
+            build_args(EditorKind::Unknown, file, &options),
+            vec!["test.rs"]
+        );
+    }
+
+    #[test]
+    fn test_headless_args() {
+        let file = "test.rs";
+
+        let args = build_headless_args(EditorKind::Vim, file, &["%s/foo/bar/g"]).unwrap();
+        assert_eq!(
+            args,
+            vec!["-es", "-c", "%s/foo/bar/g", "-c", "wq", "test.rs"]
+        );
+
+        let args = build_headless_args(EditorKind::NeoVim, file, &["g/^$/d", "retab"]).unwrap();
+        assert_eq!(
+            args,
+            vec!["-es", "-c", "g/^$/d", "-c", "retab", "-c", "wq", "test.rs"]
+        );
+
+        let args = build_headless_args(EditorKind::Vim, file, &[]).unwrap();
+        assert_eq!(args, vec!["-es", "-c", "wq", "test.rs"]);
+    }
+
+    #[test]
+    fn test_headless_args_rejected() {
+        let file = "test.rs";
+        assert_eq!(build_headless_args(EditorKind::VsCode, file, &["wq"]), None);
+        assert_eq!(build_headless_args(EditorKind::Nano, file, &[]), None);
+
+        let commands = ["retab"; 10];
+        assert_eq!(build_headless_args(EditorKind::Vim, file, &commands), None);
+        assert!(build_headless_args(EditorKind::Vim, file, &commands[..9]).is_some());
+    }
+
+    #[test]
+    fn test_kate_args() {
+        let args = build_kate_args("test.rs", Some(42), Some(10));
+        assert_eq!(args, vec!["--line", "42", "--column", "10", "test.rs"]);
+    }
+
+    #[test]
+    fn test_build_args_into_reused_buffer() {
+        use std::ffi::OsString;
+
+        let mut buffer: Vec<OsString> = Vec::new();
+        for (kind, expected) in position_snapshots() {
+            let options = ArgOptions {
+                line: Some(42),
+                column: Some(7),
+                wait: true,
+                ..Default::default()
+            };
+            buffer.clear();
+            build_args_into(&mut buffer, kind, "src/main.rs", &options);
+            assert_eq!(buffer, expected, "{kind}");
+        }
+    }
+
+    #[test]
+    fn test_build_args_into_appends() {
+        let mut args = vec!["--wait".to_string()];
+        build_args_into(
+            &mut args,
+            EditorKind::Vim,
+            "test.rs",
+            &ArgOptions::default(),
+        );
+        assert_eq!(args, vec!["--wait", "test.rs"]);
+    }
+
+    #[test]
+    fn test_known_flags_cover_emitted_flags() {
+        let positioned = ArgOptions {
+            line: Some(42),
+            column: Some(7),
+            ..Default::default()
+        };
+        let variants = [
+            ArgOptions {
+                column: None,
+                ..positioned.clone()
+            },
+            ArgOptions {
+                wait: true,
+                extra_positions: vec![(50, 1)],
+                ..positioned.clone()
+            },
+            ArgOptions {
+                vim_position_style: VimPositionStyle::GotoLineNormal,
+                ..positioned.clone()
+            },
+            ArgOptions {
+                split: Some(Split::Right),
+                extra_files: vec!["b.rs".to_string()],
+                window_mode: WindowMode::Reuse,
+                background: true,
+                read_only: true,
+                isolated: Some("/tmp/data".to_string()),
+                encoding: Some("latin1".to_string()),
+                start_emacs_daemon: true,
+                scroll: Some(ScrollPosition::Center),
+                ..positioned.clone()
+            },
+            ArgOptions {
+                split: Some(Split::Below),
+                extra_files: vec!["b.rs".to_string()],
+                window_mode: WindowMode::New,
+                scroll: Some(ScrollPosition::Top),
+                ..positioned.clone()
+            },
+            ArgOptions {
+                scroll: Some(ScrollPosition::Bottom),
+                select_to: Some(50),
+                ..positioned.clone()
+            },
+            ArgOptions {
+                folder: true,
+                wait: true,
+                ..Default::default()
+            },
+            ArgOptions {
+                remote: RemoteFile::parse("me@host:/src/main.rs"),
+                ..positioned.clone()
+            },
+        ];
+
+        for &kind in EditorKind::all() {
+            // Every word of every documented form, with the values filled in
+            let documented: Vec<String> = kind
+                .known_flags()
+                .iter()
+                .flat_map(|(_, form)| form.split_whitespace())
+                .map(|word| {
+                    word.replace("{line}", "42")
+                        .replace("{column}", "7")
+                        .replace("{end}", "50")
+                })
+                .collect();
+            for options in &variants {
+                if options.remote.is_some() && !kind.supports_remote() {
+                    continue;
+                }
+                for arg in build_args(kind, "src/main.rs", options) {
+                    if arg.starts_with('-') {
+                        assert!(
+                            documented.contains(&arg),
+                            "{kind:?} emits {arg} without documenting it"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
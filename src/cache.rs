@@ -0,0 +1,394 @@
+//! On-disk editor resolution cache.
+//!
+//! Short-lived CLIs resolve the editor on every start, which means a full
+//! `PATH` scan when `$VISUAL` and `$EDITOR` are unset. [`EditorCache`]
+//! stores the resolved editor in a small text file, keyed by a hash of
+//! `PATH`, `$VISUAL`, and `$EDITOR`, and reuses it until the entry expires
+//! or any of those variables change.
+//!
+//! Requires the `cache` feature.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::detect::{DetectedEditor, EditorSource, detect_editor};
+use crate::env::{Environment, SystemEnvironment};
+use crate::error::Result;
+use crate::kind::EditorKind;
+
+/// First line of every cache file; bump when the format changes.
+const HEADER: &str = "opensesame-cache 2";
+
+/// How long a cached entry stays valid by default (one day).
+const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Environment variables whose values key the cache.
+const KEY_VARS: &[&str] = &["PATH", "VISUAL", "EDITOR"];
+
+/// A file-backed cache of the detected editor.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use opensesame::EditorCache;
+///
+/// let cache = EditorCache::new("/tmp/my-tool/editor-cache").ttl(Duration::from_secs(3600));
+/// let editor = cache.detect()?;
+/// println!("using {}", editor.binary);
+/// # Ok::<(), opensesame::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorCache {
+    path: PathBuf,
+    ttl: Duration,
+}
+
+impl EditorCache {
+    /// Creates a cache stored at `path`, with a one-day TTL.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    /// Creates a cache in the platform's user cache directory.
+    ///
+    /// Uses `$XDG_CACHE_HOME` or `~/.cache` on Unix, `~/Library/Caches` on
+    /// macOS, and `%LOCALAPPDATA%` on Windows. Returns `None` if none of
+    /// those can be determined.
+    pub fn at_default_location() -> Option<Self> {
+        default_location(&SystemEnvironment).map(Self::new)
+    }
+
+    /// Sets how long a cached entry stays valid.
+    #[must_use]
+    pub const fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Returns the path of the cache file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Removes the cache file. A missing file is not an error.
+    pub fn clear(&self) -> std::io::Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Detects the default editor, consulting the cache first.
+    ///
+    /// On a miss the editor is detected as in [`Editor::detect()`](crate::Editor::detect)
+    /// and written back. Failing to write the cache is not an error.
+    pub fn detect(&self) -> Result<DetectedEditor> {
+        self.detect_in(&SystemEnvironment, SystemTime::now())
+    }
+
+    /// Detects the default editor in the given environment.
+    pub(crate) fn detect_in(
+        &self,
+        env: &dyn Environment,
+        now: SystemTime,
+    ) -> Result<DetectedEditor> {
+        if let Some(editor) = self.load(env, now) {
+            return Ok(editor);
+        }
+        let editor = detect_editor(env)?;
+        let _ = self.store(env, &editor, now);
+        Ok(editor)
+    }
+
+    /// Returns the cached editor if the entry is fresh, matches the current
+    /// environment, and its binary still resolves.
+    fn load(&self, env: &dyn Environment, now: SystemTime) -> Option<DetectedEditor> {
+        let contents = std::fs::read_to_string(&self.path).ok()?;
+        let mut lines = contents.lines();
+        if lines.next()? != HEADER {
+            return None;
+        }
+
+        let key = lines.next()?.strip_prefix("key ")?;
+        if key != format!("{:016x}", cache_key(env)) {
+            return None;
+        }
+
+        let stored: u64 = lines.next()?.strip_prefix("stored ")?.parse().ok()?;
+        let age = now
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .checked_sub(Duration::from_secs(stored))?;
+        if age > self.ttl {
+            return None;
+        }
+
+        let source = match lines.next()?.strip_prefix("source ")? {
+            "path" => EditorSource::PathSearch,
+            var => EditorSource::Environment(var.strip_prefix("env ")?.to_string()),
+        };
+        // The kind can't be recomputed from the binary: a `vi` symlinked to
+        // `nvim` or a kind declared in config would come back wrong
+        let kind = match lines.next()?.strip_prefix("kind ")? {
+            "Unknown" => EditorKind::Unknown,
+            name => EditorKind::from_name(name)?,
+        };
+        let binary = lines.next()?.strip_prefix("binary ")?.to_string();
+        let extra_args = lines
+            .map(|line| line.strip_prefix("arg ").map(str::to_string))
+            .collect::<Option<Vec<_>>>()?;
+
+        env.which(&binary)?;
+        Some(DetectedEditor {
+            binary,
+            kind,
+            extra_args,
+            source,
+        })
+    }
+
+    /// Writes an editor to the cache file, creating parent directories.
+    fn store(
+        &self,
+        env: &dyn Environment,
+        editor: &DetectedEditor,
+        now: SystemTime,
+    ) -> std::io::Result<()> {
+        let source = match editor.source {
+            EditorSource::PathSearch => "path".to_string(),
+            EditorSource::Environment(ref var) => format!("env {var}"),
+            _ => return Ok(()),
+        };
+        let fields = std::iter::once(&editor.binary).chain(&editor.extra_args);
+        if fields.into_iter().any(|field| field.contains(['\n', '\r'])) {
+            return Ok(());
+        }
+
+        let stored = now
+            .duration_since(UNIX_EPOCH)
+            .map_err(std::io::Error::other)?
+            .as_secs();
+        let mut contents = format!(
+            "{HEADER}\nkey {:016x}\nstored {stored}\nsource {source}\nkind {}\nbinary {}\n",
+            cache_key(env),
+            editor.kind.as_str(),
+            editor.binary
+        );
+        for arg in &editor.extra_args {
+            contents.push_str("arg ");
+            contents.push_str(arg);
+            contents.push('\n');
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, contents)
+    }
+}
+
+/// Hashes the environment variables that affect detection (64-bit FNV-1a).
+///
+/// A fixed hash function keeps keys stable across Rust versions, unlike
+/// `DefaultHasher`.
+fn cache_key(env: &dyn Environment) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for var in KEY_VARS {
+        let value = env.var(var);
+        // Distinguish unset from empty, and separate each variable
+        let bytes = value.as_deref().map_or(&[0xff][..], str::as_bytes);
+        for &byte in bytes.iter().chain(&[0]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Returns the default cache file path for the platform.
+fn default_location(env: &dyn Environment) -> Option<PathBuf> {
+    let dir = if cfg!(windows) {
+        PathBuf::from(env.var("LOCALAPPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(env.var("HOME")?)
+            .join("Library")
+            .join("Caches")
+    } else {
+        match env.var("XDG_CACHE_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env.var("HOME")?).join(".cache"),
+        }
+    };
+    Some(dir.join("opensesame").join("editor"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::FakeEnvironment;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn cache() -> (tempfile::TempDir, EditorCache) {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = EditorCache::new(dir.path().join("nested").join("editor"));
+        (dir, cache)
+    }
+
+    #[test]
+    fn test_cache_miss_detects_and_stores() {
+        let (_dir, cache) = cache();
+        let env = FakeEnvironment::new()
+            .with_var("PATH", "/usr/bin")
+            .with_binary("nvim");
+
+        assert!(cache.load(&env, at(1000)).is_none());
+        let editor = cache.detect_in(&env, at(1000)).unwrap();
+        assert_eq!(editor.binary, "nvim");
+        assert!(cache.path().exists());
+    }
+
+    #[test]
+    fn test_cache_hit_skips_detection() {
+        let (_dir, cache) = cache();
+        let env = FakeEnvironment::new()
+            .with_var("PATH", "/usr/bin")
+            .with_var("EDITOR", "code --wait")
+            .with_binary("code");
+        cache.detect_in(&env, at(1000)).unwrap();
+
+        let editor = cache.load(&env, at(2000)).unwrap();
+        assert_eq!(editor.binary, "code");
+        assert_eq!(editor.kind, EditorKind::VsCode);
+        assert_eq!(editor.extra_args, vec!["--wait"]);
+        assert_eq!(
+            editor.source,
+            EditorSource::Environment("EDITOR".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cache_keeps_resolved_kind() {
+        let (_dir, cache) = cache();
+        let env = FakeEnvironment::new()
+            .with_var("PATH", "/usr/bin")
+            .with_binary("vi");
+        let editor = DetectedEditor {
+            binary: "vi".to_string(),
+            kind: EditorKind::NeoVim,
+            extra_args: Vec::new(),
+            source: EditorSource::PathSearch,
+        };
+        cache.store(&env, &editor, at(1000)).unwrap();
+        assert_eq!(cache.load(&env, at(1000)).unwrap(), editor);
+
+        let unknown = DetectedEditor {
+            binary: "vi".to_string(),
+            kind: EditorKind::Unknown,
+            ..editor
+        };
+        cache.store(&env, &unknown, at(1000)).unwrap();
+        assert_eq!(
+            cache.load(&env, at(1000)).unwrap().kind,
+            EditorKind::Unknown
+        );
+    }
+
+    #[test]
+    fn test_cache_ignores_old_format() {
+        let (_dir, cache) = cache();
+        let env = FakeEnvironment::new()
+            .with_var("PATH", "/usr/bin")
+            .with_binary("vi");
+        let contents = format!(
+            "opensesame-cache 1\nkey {:016x}\nstored 1000\nsource path\nbinary vi\n",
+            cache_key(&env)
+        );
+        std::fs::create_dir_all(cache.path().parent().unwrap()).unwrap();
+        std::fs::write(cache.path(), contents).unwrap();
+        assert!(cache.load(&env, at(1000)).is_none());
+    }
+
+    #[test]
+    fn test_cache_invalidated_by_path_change() {
+        let (_dir, cache) = cache();
+        let env = FakeEnvironment::new()
+            .with_var("PATH", "/usr/bin")
+            .with_binary("nvim");
+        cache.detect_in(&env, at(1000)).unwrap();
+
+        let changed = FakeEnvironment::new()
+            .with_var("PATH", "/opt/bin:/usr/bin")
+            .with_binary("nvim")
+            .with_binary("code");
+        assert!(cache.load(&changed, at(1000)).is_none());
+        assert_eq!(cache.detect_in(&changed, at(1000)).unwrap().binary, "code");
+    }
+
+    #[test]
+    fn test_cache_expires_after_ttl() {
+        let (_dir, cache) = cache();
+        let cache = cache.ttl(Duration::from_secs(60));
+        let env = FakeEnvironment::new()
+            .with_var("PATH", "/usr/bin")
+            .with_binary("nvim");
+        cache.detect_in(&env, at(1000)).unwrap();
+
+        assert!(cache.load(&env, at(1060)).is_some());
+        assert!(cache.load(&env, at(1061)).is_none());
+    }
+
+    #[test]
+    fn test_cache_ignores_uninstalled_binary() {
+        let (_dir, cache) = cache();
+        let env = FakeEnvironment::new()
+            .with_var("PATH", "/usr/bin")
+            .with_binary("nvim");
+        cache.detect_in(&env, at(1000)).unwrap();
+
+        let uninstalled = FakeEnvironment::new().with_var("PATH", "/usr/bin");
+        assert!(cache.load(&uninstalled, at(1000)).is_none());
+    }
+
+    #[test]
+    fn test_cache_clear() {
+        let (_dir, cache) = cache();
+        cache.clear().unwrap();
+
+        let env = FakeEnvironment::new()
+            .with_var("PATH", "/usr/bin")
+            .with_binary("nvim");
+        cache.detect_in(&env, at(1000)).unwrap();
+        cache.clear().unwrap();
+        assert!(!cache.path().exists());
+    }
+
+    #[test]
+    fn test_cache_key_distinguishes_unset_and_empty() {
+        let unset = FakeEnvironment::new();
+        let empty = FakeEnvironment::new().with_var("EDITOR", "");
+        assert_ne!(cache_key(&unset), cache_key(&empty));
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn test_default_location() {
+        let env = FakeEnvironment::new().with_var("HOME", "/home/alice");
+        assert_eq!(
+            default_location(&env),
+            Some(PathBuf::from("/home/alice/.cache/opensesame/editor"))
+        );
+
+        let env = env.with_var("XDG_CACHE_HOME", "/var/cache/alice");
+        assert_eq!(
+            default_location(&env),
+            Some(PathBuf::from("/var/cache/alice/opensesame/editor"))
+        );
+    }
+}
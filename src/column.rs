@@ -0,0 +1,183 @@
+//! Column conversion helpers.
+//!
+//! Tools disagree on what a "column" is. Compilers such as rustc report byte
+//! offsets, most editors count characters, and some count display cells with
+//! tabs expanded. These helpers convert between the conventions for a single
+//! line of text. All columns are 1-indexed, and the column one past the last
+//! character (end of line) is valid.
+
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+/// Converts a 1-indexed character column to a 1-indexed byte column.
+///
+/// Returns `None` if `char_col` is 0 or past the end of the line.
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::char_column_to_byte_column;
+///
+/// // 'é' is two bytes in UTF-8, so the 'x' after it starts at byte 3
+/// assert_eq!(char_column_to_byte_column("éx", 2), Some(3));
+/// ```
+pub fn char_column_to_byte_column(line_text: &str, char_col: u32) -> Option<u32> {
+    let index = usize::try_from(char_col.checked_sub(1)?).ok()?;
+    let byte_offset = line_text
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(core::iter::once(line_text.len()))
+        .nth(index)?;
+    u32::try_from(byte_offset + 1).ok()
+}
+
+/// Converts a 1-indexed byte column to a 1-indexed character column.
+///
+/// Returns `None` if `byte_col` is 0, past the end of the line, or points
+/// into the middle of a multi-byte character.
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::byte_column_to_char_column;
+///
+/// // rustc reports the 'x' in "éx" at byte column 3; editors call it column 2
+/// assert_eq!(byte_column_to_char_column("éx", 3), Some(2));
+/// ```
+pub fn byte_column_to_char_column(line_text: &str, byte_col: u32) -> Option<u32> {
+    let byte_offset = usize::try_from(byte_col.checked_sub(1)?).ok()?;
+    if !line_text.is_char_boundary(byte_offset) {
+        return None;
+    }
+    let char_index = line_text[..byte_offset].chars().count();
+    u32::try_from(char_index + 1).ok()
+}
+
+/// Converts a 1-indexed character column to a 1-indexed display column.
+///
+/// Tabs advance to the next multiple of `tab_width`; every other character
+/// occupies one cell. Returns `None` if `char_col` is 0 or past the end of
+/// the line. A `tab_width` of 0 is treated as 1.
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::char_column_to_display_column;
+///
+/// // With 4-wide tabs, the 'x' after one tab is drawn in cell 5
+/// assert_eq!(char_column_to_display_column("\tx", 2, 4), Some(5));
+/// ```
+pub fn char_column_to_display_column(
+    line_text: &str,
+    char_col: u32,
+    tab_width: u32,
+) -> Option<u32> {
+    let index = usize::try_from(char_col.checked_sub(1)?).ok()?;
+    if index > line_text.chars().count() {
+        return None;
+    }
+
+    let tab_width = tab_width.max(1);
+    let mut cells: u32 = 0;
+    for ch in line_text.chars().take(index) {
+        cells = if ch == '\t' {
+            (cells / tab_width + 1).checked_mul(tab_width)?
+        } else {
+            cells.checked_add(1)?
+        };
+    }
+    cells.checked_add(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_columns_are_identical() {
+        let line = "fn main() {}";
+        for col in 1..=13 {
+            assert_eq!(char_column_to_byte_column(line, col), Some(col));
+            assert_eq!(byte_column_to_char_column(line, col), Some(col));
+        }
+    }
+
+    #[test]
+    fn test_tabs_count_as_one_column() {
+        let line = "\t\tlet x = 1;";
+        assert_eq!(char_column_to_byte_column(line, 3), Some(3));
+        assert_eq!(byte_column_to_char_column(line, 3), Some(3));
+    }
+
+    #[test]
+    fn test_multibyte_characters() {
+        // 'λ' is 2 bytes, '🦀' is 4 bytes
+        let line = "λ = \"🦀\";";
+        assert_eq!(char_column_to_byte_column(line, 1), Some(1));
+        assert_eq!(char_column_to_byte_column(line, 2), Some(3));
+        assert_eq!(char_column_to_byte_column(line, 6), Some(7));
+        assert_eq!(char_column_to_byte_column(line, 7), Some(11));
+
+        assert_eq!(byte_column_to_char_column(line, 3), Some(2));
+        assert_eq!(byte_column_to_char_column(line, 7), Some(6));
+        assert_eq!(byte_column_to_char_column(line, 11), Some(7));
+    }
+
+    #[test]
+    fn test_tabs_and_multibyte_roundtrip() {
+        let line = "\tcafé\t= \"naïve\";";
+        let char_len = u32::try_from(line.chars().count()).unwrap();
+        for col in 1..=char_len + 1 {
+            let byte_col = char_column_to_byte_column(line, col).unwrap();
+            assert_eq!(byte_column_to_char_column(line, byte_col), Some(col));
+        }
+    }
+
+    #[test]
+    fn test_end_of_line_is_valid() {
+        assert_eq!(char_column_to_byte_column("é", 2), Some(3));
+        assert_eq!(byte_column_to_char_column("é", 3), Some(2));
+        assert_eq!(char_column_to_byte_column("", 1), Some(1));
+        assert_eq!(byte_column_to_char_column("", 1), Some(1));
+    }
+
+    #[test]
+    fn test_out_of_range_columns() {
+        assert_eq!(char_column_to_byte_column("abc", 0), None);
+        assert_eq!(char_column_to_byte_column("abc", 5), None);
+        assert_eq!(byte_column_to_char_column("abc", 0), None);
+        assert_eq!(byte_column_to_char_column("abc", 5), None);
+    }
+
+    #[test]
+    fn test_display_column_expands_tabs() {
+        let line = "\tif x {\t// note";
+        // The 'i' after one tab
+        assert_eq!(char_column_to_display_column(line, 2, 8), Some(9));
+        assert_eq!(char_column_to_display_column(line, 2, 4), Some(5));
+        assert_eq!(char_column_to_display_column(line, 2, 2), Some(3));
+
+        // The second tab starts at cell 15 (width 8) and ends at the next stop
+        assert_eq!(char_column_to_display_column(line, 8, 8), Some(15));
+        assert_eq!(char_column_to_display_column(line, 9, 8), Some(17));
+        assert_eq!(char_column_to_display_column(line, 9, 4), Some(13));
+    }
+
+    #[test]
+    fn test_display_column_without_tabs() {
+        assert_eq!(char_column_to_display_column("let é = 1;", 7, 8), Some(7));
+        assert_eq!(char_column_to_display_column("abc", 4, 8), Some(4));
+        assert_eq!(char_column_to_display_column("abc", 5, 8), None);
+        assert_eq!(char_column_to_display_column("abc", 0, 8), None);
+    }
+
+    #[test]
+    fn test_display_column_zero_tab_width() {
+        assert_eq!(char_column_to_display_column("\t\tx", 3, 0), Some(3));
+    }
+
+    #[test]
+    fn test_byte_column_inside_character() {
+        // Byte column 2 is the second byte of 'é'
+        assert_eq!(byte_column_to_char_column("éx", 2), None);
+    }
+}
@@ -3,431 +3,889 @@
 //! This module constructs editor-specific command-line arguments for opening
 //! files at specific line and column positions.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::args::{ArgOptions, build_args_into};
 use crate::detect::DetectedEditor;
-use crate::editor::EditorKind;
+use crate::env::Environment;
+use crate::kind::EditorKind;
+use crate::kind::takes_plus_line;
+use crate::options::TerminalLauncher;
+
+/// Options that shape how the editor process is launched.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    /// How terminal editors are hosted.
+    pub terminal_launcher: TerminalLauncher,
+    /// Treat the editor as a terminal editor even if its kind is not one.
+    pub assume_terminal: bool,
+    /// Program name to present as `argv[0]`; ignored outside Unix.
+    #[cfg_attr(not(unix), allow(dead_code))]
+    pub argv0: Option<String>,
+    /// Shell to run the editor through as `<shell> -lc 'exec <command>'`, so
+    /// the user's login profile sets up `PATH`. `argv0` is ignored then.
+    pub login_shell: Option<String>,
+    /// Working directory of the editor, if not the current directory.
+    ///
+    /// Launchers that start the editor somewhere else (tmux, Terminal.app)
+    /// are told to change to it, so relative file paths still resolve.
+    pub current_dir: Option<PathBuf>,
+}
+
+/// Returns the arguments to `open` that bring an editor's window to the front
+/// on macOS (`open -a <App>`).
+///
+/// Returns `None` for editors without a known application bundle name,
+/// including terminal editors.
+pub fn activate_args(kind: EditorKind) -> Option<Vec<String>> {
+    let app = match kind {
+        EditorKind::VsCode => "Visual Studio Code",
+        EditorKind::VsCodeInsiders => "Visual Studio Code - Insiders",
+        EditorKind::VSCodium => "VSCodium",
+        EditorKind::Cursor => "Cursor",
+        EditorKind::Windsurf => "Windsurf",
+        EditorKind::GVim => "MacVim",
+        EditorKind::Neovide => "Neovide",
+        EditorKind::VimR => "VimR",
+        EditorKind::Sublime => "Sublime Text",
+        EditorKind::Zed => "Zed",
+        EditorKind::Atom => "Atom",
+        EditorKind::TextMate => "TextMate",
+        EditorKind::Xcode => "Xcode",
+        EditorKind::IntelliJ => "IntelliJ IDEA",
+        EditorKind::WebStorm => "WebStorm",
+        EditorKind::PhpStorm => "PhpStorm",
+        EditorKind::PyCharm => "PyCharm",
+        EditorKind::RubyMine => "RubyMine",
+        EditorKind::GoLand => "GoLand",
+        EditorKind::CLion => "CLion",
+        EditorKind::Rider => "Rider",
+        EditorKind::DataGrip => "DataGrip",
+        EditorKind::AndroidStudio => "Android Studio",
+        _ => return None,
+    };
+    Some(vec!["-a".to_string(), app.to_string()])
+}
+
+/// Returns `true` if the editor's extra arguments already include its wait flag.
+fn has_wait_flag(editor: &DetectedEditor) -> bool {
+    editor
+        .kind
+        .wait_flag()
+        .is_some_and(|flag| editor.extra_args.iter().any(|arg| arg == flag))
+}
+
+/// Returns `true` for an `emacsclient` that opens a frame in the terminal
+/// (`-t`, `-nw`, or `--tty` in its extra arguments).
+pub fn is_tty_emacsclient(editor: &DetectedEditor) -> bool {
+    editor.kind == EditorKind::EmacsClient
+        && editor
+            .extra_args
+            .iter()
+            .any(|arg| matches!(arg.as_str(), "-t" | "-nw" | "--tty"))
+}
 
 /// Builds the command to open a file in an editor.
 pub fn build_command(
     editor: &DetectedEditor,
     file: &Path,
-    line: Option<u32>,
-    column: Option<u32>,
-    wait: bool,
+    options: &ArgOptions,
+    launch: &LaunchOptions,
 ) -> Command {
-    let mut cmd = Command::new(&editor.binary);
-
     // Add any extra args from environment (e.g., "--wait" from "$EDITOR=code --wait")
-    for arg in &editor.extra_args {
-        cmd.arg(arg);
-    }
+    let mut args = editor.extra_args.clone();
+
+    // Don't repeat a wait flag the extra args already carry
+    let without_wait;
+    let options = if options.wait && has_wait_flag(editor) {
+        without_wait = ArgOptions {
+            wait: false,
+            ..options.clone()
+        };
+        &without_wait
+    } else {
+        options
+    };
 
     // Build editor-specific arguments
-    let args = build_args(editor.kind, file, line, column, wait);
-    for arg in args {
-        cmd.arg(arg);
-    }
-
-    // Terminal editors need to inherit stdio
-    if editor.is_terminal_editor() {
-        cmd.stdin(std::process::Stdio::inherit())
-            .stdout(std::process::Stdio::inherit())
-            .stderr(std::process::Stdio::inherit());
-    }
-
-    cmd
-}
-
-/// Builds the argument list for an editor.
-fn build_args(
-    kind: EditorKind,
-    file: &Path,
-    line: Option<u32>,
-    column: Option<u32>,
-    wait: bool,
-) -> Vec<String> {
-    let file_str = file.display().to_string();
-
-    match kind {
-        // VS Code family: code -g file:line:column [--wait]
-        EditorKind::VsCode
-        | EditorKind::VsCodeInsiders
-        | EditorKind::VSCodium
-        | EditorKind::Cursor
-        | EditorKind::Windsurf => {
-            build_vscode_args(&file_str, line, column, wait)
-        }
-
-        // Vim family: vim +call\ cursor(line,col) file
-        EditorKind::Vim | EditorKind::NeoVim | EditorKind::Vi | EditorKind::GVim => {
-            build_vim_args(&file_str, line, column)
-        }
-
-        // Emacs: emacs +line:col file [--wait]
-        EditorKind::Emacs | EditorKind::EmacsClient => {
-            build_emacs_args(&file_str, line, column, wait)
-        }
-
-        // Sublime Text: subl file:line:column [--wait]
-        EditorKind::Sublime => {
-            build_sublime_args(&file_str, line, column, wait)
-        }
-
-        // Zed: zed file:line:column [--wait]
-        EditorKind::Zed => {
-            build_zed_args(&file_str, line, column, wait)
-        }
-
-        // Helix: hx file:line:column
-        EditorKind::Helix => {
-            build_helix_args(&file_str, line, column)
-        }
-
-        // Nano: nano +line,col file
-        EditorKind::Nano => {
-            build_nano_args(&file_str, line, column)
-        }
-
-        // TextMate: mate --line line file [--wait]
-        EditorKind::TextMate => {
-            build_textmate_args(&file_str, line, wait)
-        }
+    build_args_into(&mut args, editor.kind, &file.to_string_lossy(), options);
+
+    let terminal = editor.kind.is_terminal_editor_hinted(Some(&editor.binary))
+        || launch.assume_terminal
+        || is_tty_emacsclient(editor);
+
+    // Run through a login shell so its profile can extend PATH
+    let (binary, args) = match launch.login_shell {
+        Some(ref shell) => (
+            shell.as_str(),
+            vec!["-lc".to_string(), login_shell_script(&editor.binary, &args)],
+        ),
+        None => (editor.binary.as_str(), args),
+    };
 
-        // Notepad++: notepad++ -nLINE -cCOL file
-        EditorKind::NotepadPlusPlus => {
-            build_notepadpp_args(&file_str, line, column)
-        }
+    // Terminal editors may be hosted in a new multiplexer window
+    let wrapped = if terminal {
+        let dir = launch
+            .current_dir
+            .clone()
+            .or_else(|| std::env::current_dir().ok());
+        wrap_terminal_command(launch.terminal_launcher, dir.as_deref(), binary, &args)
+    } else {
+        None
+    };
 
-        // JetBrains IDEs: idea file:line [--wait]
-        EditorKind::IntelliJ
-        | EditorKind::WebStorm
-        | EditorKind::PhpStorm
-        | EditorKind::PyCharm
-        | EditorKind::RubyMine
-        | EditorKind::GoLand
-        | EditorKind::CLion
-        | EditorKind::Rider
-        | EditorKind::DataGrip
-        | EditorKind::AndroidStudio => {
-            build_jetbrains_args(&file_str, line, wait)
-        }
+    let Some((program, wrapped_args)) = wrapped else {
+        let mut cmd = Command::new(binary);
+        cmd.args(args);
 
-        // Xcode: xed --line LINE file
-        EditorKind::Xcode => {
-            build_xcode_args(&file_str, line, wait)
+        #[cfg(unix)]
+        if let (Some(argv0), None) = (&launch.argv0, &launch.login_shell) {
+            std::os::unix::process::CommandExt::arg0(&mut cmd, argv0);
         }
 
-        // Kate: kate --line LINE --column COL file
-        EditorKind::Kate => {
-            build_kate_args(&file_str, line, column)
+        // Terminal editors need to inherit stdio
+        if terminal {
+            cmd.stdin(std::process::Stdio::inherit())
+                .stdout(std::process::Stdio::inherit())
+                .stderr(std::process::Stdio::inherit());
         }
 
-        // Atom (deprecated but still used): atom file:line:column [--wait]
-        EditorKind::Atom => {
-            build_atom_args(&file_str, line, column, wait)
-        }
+        return cmd;
+    };
 
-        // Notepad (Windows): no line/column support
-        EditorKind::Notepad => {
-            vec![file_str]
-        }
+    let mut cmd = Command::new(program);
+    cmd.args(wrapped_args);
+    cmd
+}
 
-        // Unknown editor: just pass the file
-        EditorKind::Unknown => {
-            vec![file_str]
-        }
+/// Returns the script a login shell runs to start the editor: `exec` followed
+/// by the binary and its arguments, each quoted for a POSIX shell.
+///
+/// `exec` replaces the shell, so exit status and waiting behave as if the
+/// editor had been spawned directly.
+pub fn login_shell_script(binary: &str, args: &[String]) -> String {
+    let mut script = String::from("exec");
+    for word in core::iter::once(binary).chain(args.iter().map(String::as_str)) {
+        script.push(' ');
+        script.push_str(&shell_quote(word));
     }
+    script
 }
 
-/// VS Code family: `code -g file:line:column [--wait]`
-fn build_vscode_args(file: &str, line: Option<u32>, column: Option<u32>, wait: bool) -> Vec<String> {
-    let mut args = Vec::new();
-
-    // Use --goto flag for line:column positioning
-    args.push("--goto".to_string());
-
-    let position = match (line, column) {
-        (Some(l), Some(c)) => format!("{file}:{l}:{c}"),
-        (Some(l), None) => format!("{file}:{l}"),
-        _ => file.to_string(),
+/// Shells that run [`login_shell_script()`] as written.
+const POSIX_SHELLS: &[&str] = &[
+    "sh", "bash", "zsh", "dash", "ksh", "ksh93", "mksh", "oksh", "yash", "ash",
+];
+
+/// Returns the login shell to run [`login_shell_script()`] with.
+///
+/// `shell` (usually `$SHELL`) is used if it is a POSIX shell. fish, tcsh,
+/// nushell, and other shells quote differently, or don't accept `-lc`, so
+/// they fall back to `/bin/sh`, as does a missing or empty `shell`.
+pub fn posix_login_shell(shell: Option<&str>) -> String {
+    let is_posix = |shell: &&str| {
+        Path::new(shell)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| POSIX_SHELLS.contains(&name))
     };
-    args.push(position);
-
-    if wait {
-        args.push("--wait".to_string());
-    }
-
-    args
+    shell.filter(is_posix).unwrap_or("/bin/sh").to_string()
 }
 
-/// Vim family: `vim +call\ cursor(line,col) file` or `vim +LINE file`
-fn build_vim_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
-    match (line, column) {
-        (Some(l), Some(c)) => {
-            vec![format!("+call cursor({l},{c})"), file.to_string()]
-        }
-        (Some(l), None) => {
-            vec![format!("+{l}"), file.to_string()]
-        }
-        _ => vec![file.to_string()],
+/// Quotes `word` for a POSIX shell.
+///
+/// Words made only of characters that are never special are left as is;
+/// anything else is wrapped in single quotes, with embedded single quotes
+/// written as `'\''`.
+fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"%+,-./:=@_".contains(&b));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
     }
 }
 
-/// Emacs: `emacs +line:col file`
-fn build_emacs_args(file: &str, line: Option<u32>, column: Option<u32>, wait: bool) -> Vec<String> {
-    let mut args = Vec::new();
-
-    match (line, column) {
-        (Some(l), Some(c)) => args.push(format!("+{l}:{c}")),
-        (Some(l), None) => args.push(format!("+{l}")),
-        _ => {}
-    }
-
-    args.push(file.to_string());
+/// Wraps a terminal editor invocation for a non-inline launcher.
+///
+/// Returns the launcher program and its full argument list, or `None` for
+/// [`TerminalLauncher::Inline`]. `dir` is the directory the editor should
+/// start in; tmux gets it as `-c`, since new panes otherwise start in the
+/// session's directory.
+///
+/// The editor argv is passed as separate arguments. Terminal emulators run
+/// it directly, but a shell may still see it: tmux before 3.0 joins the
+/// words into one command for its `default-shell`, and Terminal.app only
+/// takes a shell command (see [`terminal_app_args()`]).
+/// [`EditorBuilder::sanitize()`](crate::EditorBuilder::sanitize) rejects
+/// the line breaks that would split such a command.
+///
+/// The Windows console is opened with `conhost.exe` rather than
+/// `cmd /c start`: cmd parses the command line again, so `&`, `|`, or `>`
+/// in a file name would start another command.
+pub fn wrap_terminal_command(
+    launcher: TerminalLauncher,
+    dir: Option<&Path>,
+    binary: &str,
+    args: &[String],
+) -> Option<(String, Vec<String>)> {
+    let prefix: &[&str] = match launcher {
+        TerminalLauncher::Inline => return None,
+        TerminalLauncher::TmuxWindow => &["tmux", "new-window"],
+        TerminalLauncher::TmuxSplit => &["tmux", "split-window", "-h"],
+        TerminalLauncher::Kitty => &["kitty"],
+        TerminalLauncher::WezTerm => &["wezterm", "start", "--"],
+        TerminalLauncher::Alacritty => &["alacritty", "-e"],
+        TerminalLauncher::GnomeTerminal => &["gnome-terminal", "--"],
+        TerminalLauncher::XTerminalEmulator => &["x-terminal-emulator", "-e"],
+        TerminalLauncher::WindowsConsole => &["conhost.exe"],
+        TerminalLauncher::TerminalApp => {
+            return Some((
+                "osascript".to_string(),
+                terminal_app_args(dir, binary, args),
+            ));
+        }
+    };
 
-    if wait {
-        args.push("--eval".to_string());
-        args.push("(while (get-buffer-window) (sit-for 1))".to_string());
+    let (program, launcher_args) = prefix.split_first()?;
+    let mut wrapped: Vec<String> = launcher_args.iter().map(|s| (*s).to_string()).collect();
+    if let (TerminalLauncher::TmuxWindow | TerminalLauncher::TmuxSplit, Some(dir)) = (launcher, dir)
+    {
+        wrapped.push("-c".to_string());
+        wrapped.push(dir.to_string_lossy().into_owned());
     }
-
-    args
+    wrapped.push(binary.to_string());
+    wrapped.extend(args.iter().cloned());
+    Some(((*program).to_string(), wrapped))
 }
 
-/// Sublime Text: `subl file:line:column [--wait]`
-fn build_sublime_args(file: &str, line: Option<u32>, column: Option<u32>, wait: bool) -> Vec<String> {
-    let mut args = Vec::new();
-
-    let position = match (line, column) {
-        (Some(l), Some(c)) => format!("{file}:{l}:{c}"),
-        (Some(l), None) => format!("{file}:{l}"),
-        _ => file.to_string(),
-    };
-    args.push(position);
-
-    if wait {
-        args.push("--wait".to_string());
+/// Returns the `osascript` arguments that run the editor in a new
+/// Terminal.app window.
+///
+/// Terminal.app runs a shell command line, so the editor argv is quoted
+/// for a POSIX shell (as for a login shell) and prefixed with a `cd` to
+/// `dir`, since new windows start in the home directory.
+fn terminal_app_args(dir: Option<&Path>, binary: &str, args: &[String]) -> Vec<String> {
+    let mut script = login_shell_script(binary, args);
+    if let Some(dir) = dir {
+        script = format!("cd {} && {script}", shell_quote(&dir.to_string_lossy()));
     }
-
-    args
+    let script = script.replace('\\', "\\\\").replace('"', "\\\"");
+    vec![
+        "-e".to_string(),
+        format!("tell application \"Terminal\" to do script \"{script}\""),
+        "-e".to_string(),
+        "tell application \"Terminal\" to activate".to_string(),
+    ]
 }
 
-/// Zed: `zed file:line:column [--wait]`
-fn build_zed_args(file: &str, line: Option<u32>, column: Option<u32>, wait: bool) -> Vec<String> {
-    let mut args = Vec::new();
-
-    let position = match (line, column) {
-        (Some(l), Some(c)) => format!("{file}:{l}:{c}"),
-        (Some(l), None) => format!("{file}:{l}"),
-        _ => file.to_string(),
-    };
-    args.push(position);
-
-    if wait {
-        args.push("--wait".to_string());
+// `Path`-taking conveniences live here so `kind` stays free of `std`
+impl EditorKind {
+    /// Returns the arguments that open `file` at a position in this editor.
+    ///
+    /// This is the file and positioning part of the command line, without
+    /// the binary itself or any split, read-only, or remote flags. `line`
+    /// and `column` are 1-indexed; editors without column support ignore
+    /// `column`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::EditorKind;
+    ///
+    /// let args = EditorKind::Nano.position_args("src/main.rs", Some(42), Some(7), false);
+    /// assert_eq!(args, vec!["+42,7", "src/main.rs"]);
+    /// ```
+    pub fn position_args(
+        &self,
+        file: impl AsRef<Path>,
+        line: Option<u32>,
+        column: Option<u32>,
+        wait: bool,
+    ) -> Vec<String> {
+        let options = crate::args::ArgOptions {
+            line,
+            column,
+            wait,
+            ..Default::default()
+        };
+        crate::args::build_args(*self, &file.as_ref().to_string_lossy(), &options)
     }
 
-    args
+    /// Returns the position arguments for `file`, using `binary` to fill in
+    /// for [`Unknown`](Self::Unknown).
+    ///
+    /// Known kinds ignore the hint and behave like
+    /// [`position_args()`](Self::position_args). An unknown editor whose
+    /// binary is a common terminal editor that accepts `+LINE file` (joe,
+    /// micro, mg, ...) is positioned that way; any other unknown editor only
+    /// gets the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::EditorKind;
+    ///
+    /// let kind = EditorKind::from_binary("micro");
+    /// let args = kind.format_position(Some("micro"), "src/main.rs", Some(42), None, false);
+    /// assert_eq!(args, vec!["+42", "src/main.rs"]);
+    /// ```
+    pub fn format_position(
+        &self,
+        binary: Option<&str>,
+        file: impl AsRef<Path>,
+        line: Option<u32>,
+        column: Option<u32>,
+        wait: bool,
+    ) -> Vec<String> {
+        let options = ArgOptions {
+            line,
+            column,
+            wait,
+            plus_line_fallback: takes_plus_line(*self, binary),
+            ..Default::default()
+        };
+        crate::args::build_args(*self, &file.as_ref().to_string_lossy(), &options)
+    }
 }
 
-/// Helix: `hx file:line:column`
-fn build_helix_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
-    let position = match (line, column) {
-        (Some(l), Some(c)) => format!("{file}:{l}:{c}"),
-        (Some(l), None) => format!("{file}:{l}"),
-        _ => file.to_string(),
-    };
-    vec![position]
-}
+// Detection reads the environment, so it lives here rather than in `options`
+impl TerminalLauncher {
+    /// Detects a launcher from the terminal the current process runs in.
+    ///
+    /// Returns `TmuxWindow` inside tmux, `Kitty` inside Kitty, `WezTerm`
+    /// inside WezTerm, and `Inline` in other terminals. Without a terminal
+    /// (stdin is not a TTY, e.g. in a GUI application), returns a launcher
+    /// that opens a new terminal window: `TerminalApp` on macOS,
+    /// `WindowsConsole` on Windows, and elsewhere the first installed of
+    /// `x-terminal-emulator`, `gnome-terminal`, `alacritty`, `wezterm`, and
+    /// `kitty`, or `Inline` if none is.
+    pub fn detect() -> Self {
+        use std::io::IsTerminal;
+
+        Self::detect_in(
+            &crate::env::SystemEnvironment,
+            std::io::stdin().is_terminal(),
+        )
+    }
 
-/// Nano: `nano +line,col file`
-fn build_nano_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
-    match (line, column) {
-        (Some(l), Some(c)) => {
-            vec![format!("+{l},{c}"), file.to_string()]
+    /// Detects a launcher from the given environment.
+    pub(crate) fn detect_in(env: &dyn Environment, has_tty: bool) -> Self {
+        if env.var("TMUX").is_some() {
+            Self::TmuxWindow
+        } else if env.var("KITTY_WINDOW_ID").is_some() {
+            Self::Kitty
+        } else if env.var("WEZTERM_PANE").is_some()
+            || env.var("TERM_PROGRAM").as_deref() == Some("WezTerm")
+        {
+            Self::WezTerm
+        } else if has_tty {
+            Self::Inline
+        } else {
+            Self::new_window_in(env)
         }
-        (Some(l), None) => {
-            vec![format!("+{l}"), file.to_string()]
-        }
-        _ => vec![file.to_string()],
     }
-}
 
-/// TextMate: `mate --line line file [--wait]`
-fn build_textmate_args(file: &str, line: Option<u32>, wait: bool) -> Vec<String> {
-    let mut args = Vec::new();
-
-    if let Some(l) = line {
-        args.push("--line".to_string());
-        args.push(l.to_string());
+    /// Returns a launcher that opens a new terminal window on this platform.
+    fn new_window_in(env: &dyn Environment) -> Self {
+        if cfg!(target_os = "macos") {
+            return Self::TerminalApp;
+        }
+        if cfg!(windows) {
+            return Self::WindowsConsole;
+        }
+        [
+            ("x-terminal-emulator", Self::XTerminalEmulator),
+            ("gnome-terminal", Self::GnomeTerminal),
+            ("alacritty", Self::Alacritty),
+            ("wezterm", Self::WezTerm),
+            ("kitty", Self::Kitty),
+        ]
+        .into_iter()
+        .find(|(binary, _)| env.which(binary).is_some())
+        .map_or(Self::Inline, |(_, launcher)| launcher)
     }
 
-    args.push(file.to_string());
-
-    if wait {
-        args.push("--wait".to_string());
+    /// Returns the launcher to actually use in the given environment.
+    pub(crate) fn effective(self, env: &dyn Environment) -> Self {
+        match self {
+            Self::TmuxWindow | Self::TmuxSplit if env.var("TMUX").is_none() => Self::Inline,
+            launcher => launcher,
+        }
     }
-
-    args
 }
 
-/// Notepad++: `notepad++ -nLINE -cCOL file`
-fn build_notepadpp_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
-    let mut args = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if let Some(l) = line {
-        args.push(format!("-n{l}"));
+    #[test]
+    fn test_terminal_launcher_requires_tmux() {
+        use crate::env::FakeEnvironment;
+
+        let outside = FakeEnvironment::new();
+        assert_eq!(
+            TerminalLauncher::TmuxWindow.effective(&outside),
+            TerminalLauncher::Inline
+        );
+        assert_eq!(
+            TerminalLauncher::TmuxSplit.effective(&outside),
+            TerminalLauncher::Inline
+        );
+
+        let inside = FakeEnvironment::new().with_var("TMUX", "/tmp/tmux-1000/default,1234,0");
+        assert_eq!(
+            TerminalLauncher::TmuxWindow.effective(&inside),
+            TerminalLauncher::TmuxWindow
+        );
+        assert_eq!(
+            TerminalLauncher::Inline.effective(&inside),
+            TerminalLauncher::Inline
+        );
     }
 
-    if let Some(c) = column {
-        args.push(format!("-c{c}"));
+    #[test]
+    fn test_terminal_launcher_detect() {
+        use crate::env::FakeEnvironment;
+
+        let detect = |env: FakeEnvironment| TerminalLauncher::detect_in(&env, true);
+        assert_eq!(detect(FakeEnvironment::new()), TerminalLauncher::Inline);
+        assert_eq!(
+            detect(FakeEnvironment::new().with_var("TMUX", "/tmp/tmux")),
+            TerminalLauncher::TmuxWindow
+        );
+        assert_eq!(
+            detect(FakeEnvironment::new().with_var("KITTY_WINDOW_ID", "1")),
+            TerminalLauncher::Kitty
+        );
+        assert_eq!(
+            detect(FakeEnvironment::new().with_var("TERM_PROGRAM", "WezTerm")),
+            TerminalLauncher::WezTerm
+        );
     }
 
-    args.push(file.to_string());
-    args
-}
-
-/// JetBrains IDEs: `idea file:line [--wait]`
-fn build_jetbrains_args(file: &str, line: Option<u32>, wait: bool) -> Vec<String> {
-    let mut args = Vec::new();
-
-    // JetBrains doesn't support column positioning
-    let position = match line {
-        Some(l) => format!("{file}:{l}"),
-        None => file.to_string(),
-    };
-    args.push(position);
-
-    if wait {
-        args.push("--wait".to_string());
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn test_terminal_launcher_detect_without_tty() {
+        use crate::env::FakeEnvironment;
+
+        let detect = |env: FakeEnvironment| TerminalLauncher::detect_in(&env, false);
+        assert_eq!(detect(FakeEnvironment::new()), TerminalLauncher::Inline);
+        assert_eq!(
+            detect(
+                FakeEnvironment::new()
+                    .with_binary("alacritty")
+                    .with_binary("x-terminal-emulator")
+            ),
+            TerminalLauncher::XTerminalEmulator
+        );
+        assert_eq!(
+            detect(
+                FakeEnvironment::new()
+                    .with_binary("alacritty")
+                    .with_binary("kitty")
+            ),
+            TerminalLauncher::Alacritty
+        );
+
+        // A multiplexer or terminal in the environment still wins
+        assert_eq!(
+            detect(
+                FakeEnvironment::new()
+                    .with_var("TMUX", "/tmp/tmux")
+                    .with_binary("gnome-terminal")
+            ),
+            TerminalLauncher::TmuxWindow
+        );
     }
 
-    args
-}
-
-/// Xcode: `xed --line LINE file`
-fn build_xcode_args(file: &str, line: Option<u32>, wait: bool) -> Vec<String> {
-    let mut args = Vec::new();
-
-    if let Some(l) = line {
-        args.push("--line".to_string());
-        args.push(l.to_string());
+    #[test]
+    fn test_wrap_terminal_command_tmux() {
+        let editor_args = vec!["+42".to_string(), "test.rs".to_string()];
+
+        assert_eq!(
+            wrap_terminal_command(TerminalLauncher::Inline, None, "vim", &editor_args),
+            None
+        );
+
+        let (program, wrapped) =
+            wrap_terminal_command(TerminalLauncher::TmuxWindow, None, "vim", &editor_args).unwrap();
+        assert_eq!(program, "tmux");
+        assert_eq!(wrapped, vec!["new-window", "vim", "+42", "test.rs"]);
+
+        let (program, wrapped) =
+            wrap_terminal_command(TerminalLauncher::TmuxSplit, None, "vim", &editor_args).unwrap();
+        assert_eq!(program, "tmux");
+        assert_eq!(wrapped, vec!["split-window", "-h", "vim", "+42", "test.rs"]);
     }
 
-    args.push(file.to_string());
-
-    if wait {
-        args.push("--wait".to_string());
+    #[test]
+    fn test_wrap_terminal_command_tmux_starts_in_dir() {
+        let editor_args = vec!["src/main.rs".to_string()];
+        let dir = Some(Path::new("/home/me/proj"));
+
+        let (_, wrapped) =
+            wrap_terminal_command(TerminalLauncher::TmuxWindow, dir, "vim", &editor_args).unwrap();
+        assert_eq!(
+            wrapped,
+            vec!["new-window", "-c", "/home/me/proj", "vim", "src/main.rs"]
+        );
+        let (_, wrapped) =
+            wrap_terminal_command(TerminalLauncher::TmuxSplit, dir, "vim", &editor_args).unwrap();
+        assert_eq!(
+            wrapped,
+            vec![
+                "split-window",
+                "-h",
+                "-c",
+                "/home/me/proj",
+                "vim",
+                "src/main.rs"
+            ]
+        );
+
+        // Other launchers start in the directory they're spawned in
+        let (_, wrapped) =
+            wrap_terminal_command(TerminalLauncher::Kitty, dir, "vim", &editor_args).unwrap();
+        assert_eq!(wrapped, vec!["vim", "src/main.rs"]);
     }
 
-    args
-}
-
-/// Kate: `kate --line LINE --column COL file`
-fn build_kate_args(file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
-    let mut args = Vec::new();
-
-    if let Some(l) = line {
-        args.push("--line".to_string());
-        args.push(l.to_string());
+    #[test]
+    fn test_wrap_terminal_command_gui_terminals() {
+        let editor_args = vec!["+call cursor(42,10)".to_string(), "test.rs".to_string()];
+
+        let (program, wrapped) =
+            wrap_terminal_command(TerminalLauncher::Kitty, None, "vim", &editor_args).unwrap();
+        assert_eq!(program, "kitty");
+        assert_eq!(wrapped, vec!["vim", "+call cursor(42,10)", "test.rs"]);
+
+        let (program, wrapped) =
+            wrap_terminal_command(TerminalLauncher::WezTerm, None, "vim", &editor_args).unwrap();
+        assert_eq!(program, "wezterm");
+        assert_eq!(
+            wrapped,
+            vec!["start", "--", "vim", "+call cursor(42,10)", "test.rs"]
+        );
     }
 
-    if let Some(c) = column {
-        args.push("--column".to_string());
-        args.push(c.to_string());
+    #[test]
+    fn test_wrap_terminal_command_new_windows() {
+        let editor_args = vec!["+42".to_string(), "my file.rs".to_string()];
+        let wrap = |launcher| wrap_terminal_command(launcher, None, "vim", &editor_args).unwrap();
+
+        let (program, wrapped) = wrap(TerminalLauncher::XTerminalEmulator);
+        assert_eq!(program, "x-terminal-emulator");
+        assert_eq!(wrapped, vec!["-e", "vim", "+42", "my file.rs"]);
+        let (program, wrapped) = wrap(TerminalLauncher::Alacritty);
+        assert_eq!(program, "alacritty");
+        assert_eq!(wrapped, vec!["-e", "vim", "+42", "my file.rs"]);
+        let (program, wrapped) = wrap(TerminalLauncher::GnomeTerminal);
+        assert_eq!(program, "gnome-terminal");
+        assert_eq!(wrapped, vec!["--", "vim", "+42", "my file.rs"]);
+        let (program, wrapped) = wrap(TerminalLauncher::WindowsConsole);
+        assert_eq!(program, "conhost.exe");
+        assert_eq!(wrapped, vec!["vim", "+42", "my file.rs"]);
+        assert_eq!(wrap(TerminalLauncher::TerminalApp).0, "osascript");
     }
 
-    args.push(file.to_string());
-    args
-}
-
-/// Atom: `atom file:line:column [--wait]`
-fn build_atom_args(file: &str, line: Option<u32>, column: Option<u32>, wait: bool) -> Vec<String> {
-    let mut args = Vec::new();
-
-    let position = match (line, column) {
-        (Some(l), Some(c)) => format!("{file}:{l}:{c}"),
-        (Some(l), None) => format!("{file}:{l}"),
-        _ => file.to_string(),
-    };
-    args.push(position);
-
-    if wait {
-        args.push("--wait".to_string());
+    #[test]
+    fn test_wrap_terminal_command_windows_console_skips_cmd() {
+        // Through `cmd /c`, `&calc` would run calc
+        let editor_args = vec!["+42".to_string(), "a&calc.rs".to_string()];
+        let (program, wrapped) =
+            wrap_terminal_command(TerminalLauncher::WindowsConsole, None, "vim", &editor_args)
+                .unwrap();
+        assert_eq!(program, "conhost.exe");
+        assert_eq!(wrapped, vec!["vim", "+42", "a&calc.rs"]);
+        assert!(
+            !wrapped
+                .iter()
+                .any(|arg| arg.eq_ignore_ascii_case("cmd") || arg == "/c")
+        );
     }
 
-    args
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
     #[test]
-    fn test_vscode_args() {
-        let args = build_vscode_args("test.rs", Some(42), Some(10), false);
-        assert_eq!(args, vec!["--goto", "test.rs:42:10"]);
-
-        let args = build_vscode_args("test.rs", Some(42), None, false);
-        assert_eq!(args, vec!["--goto", "test.rs:42"]);
-
-        let args = build_vscode_args("test.rs", None, None, true);
-        assert_eq!(args, vec!["--goto", "test.rs", "--wait"]);
+    fn test_build_command_terminal_app_uses_current_dir() {
+        let launch = LaunchOptions {
+            terminal_launcher: TerminalLauncher::TerminalApp,
+            current_dir: Some(PathBuf::from("/Users/me/proj")),
+            ..Default::default()
+        };
+        let vim = DetectedEditor {
+            binary: "vim".to_string(),
+            kind: EditorKind::Vim,
+            extra_args: Vec::new(),
+            source: crate::detect::EditorSource::Explicit,
+        };
+
+        let cmd = build_command(&vim, Path::new("a.rs"), &ArgOptions::default(), &launch);
+        assert_eq!(cmd.get_program(), "osascript");
+        let script = cmd.get_args().nth(1).unwrap().to_str().unwrap();
+        assert!(
+            script.contains("cd /Users/me/proj && exec vim a.rs"),
+            "{script}"
+        );
     }
 
     #[test]
-    fn test_vim_args() {
-        let args = build_vim_args("test.rs", Some(42), Some(10));
-        assert_eq!(args, vec!["+call cursor(42,10)", "test.rs"]);
+    fn test_terminal_app_args() {
+        let args = terminal_app_args(
+            Some(Path::new("/Users/me/my \"proj\"")),
+            "vim",
+            &["+42".to_string(), "a b.rs".to_string()],
+        );
+        assert_eq!(
+            args,
+            vec![
+                "-e",
+                concat!(
+                    r#"tell application "Terminal" to do script "#,
+                    r#""cd '/Users/me/my \"proj\"' && exec vim +42 'a b.rs'""#,
+                ),
+                "-e",
+                r#"tell application "Terminal" to activate"#,
+            ]
+        );
+    }
 
-        let args = build_vim_args("test.rs", Some(42), None);
-        assert_eq!(args, vec!["+42", "test.rs"]);
+    #[test]
+    fn test_build_command_wraps_in_x_terminal_emulator() {
+        let launch = LaunchOptions {
+            terminal_launcher: TerminalLauncher::XTerminalEmulator,
+            ..Default::default()
+        };
+        let options = ArgOptions {
+            line: Some(42),
+            ..Default::default()
+        };
+        let nano = DetectedEditor {
+            binary: "nano".to_string(),
+            kind: EditorKind::Nano,
+            extra_args: Vec::new(),
+            source: crate::detect::EditorSource::Explicit,
+        };
+
+        let cmd = build_command(&nano, Path::new("test.rs"), &options, &launch);
+        assert_eq!(cmd.get_program(), "x-terminal-emulator");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, vec!["-e", "nano", "+42", "test.rs"]);
+    }
 
-        let args = build_vim_args("test.rs", None, None);
-        assert_eq!(args, vec!["test.rs"]);
+    #[test]
+    fn test_build_command_dedupes_wait_flag() {
+        let editor = DetectedEditor {
+            binary: "code".to_string(),
+            kind: EditorKind::VsCode,
+            extra_args: vec!["--wait".to_string()],
+            source: crate::detect::EditorSource::Environment("EDITOR".to_string()),
+        };
+        let options = ArgOptions {
+            line: Some(42),
+            wait: true,
+            ..Default::default()
+        };
+
+        let cmd = build_command(
+            &editor,
+            Path::new("test.rs"),
+            &options,
+            &LaunchOptions::default(),
+        );
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["--wait", "--goto", "test.rs:42"]);
     }
 
     #[test]
-    fn test_nano_args() {
-        let args = build_nano_args("test.rs", Some(42), Some(10));
-        assert_eq!(args, vec!["+42,10", "test.rs"]);
+    fn test_build_command_wraps_only_tty_emacsclient() {
+        let launch = LaunchOptions {
+            terminal_launcher: TerminalLauncher::TmuxWindow,
+            ..Default::default()
+        };
+        let emacsclient = |extra_args: &[&str]| DetectedEditor {
+            binary: "emacsclient".to_string(),
+            kind: EditorKind::EmacsClient,
+            extra_args: extra_args.iter().map(ToString::to_string).collect(),
+            source: crate::detect::EditorSource::Explicit,
+        };
+
+        let cmd = build_command(
+            &emacsclient(&[]),
+            Path::new("test.rs"),
+            &ArgOptions::default(),
+            &launch,
+        );
+        assert_eq!(cmd.get_program(), "emacsclient");
+
+        let cmd = build_command(
+            &emacsclient(&["-nw"]),
+            Path::new("test.rs"),
+            &ArgOptions::default(),
+            &launch,
+        );
+        assert_eq!(cmd.get_program(), "tmux");
+        assert!(is_tty_emacsclient(&emacsclient(&["--tty"])));
+    }
 
-        let args = build_nano_args("test.rs", Some(42), None);
-        assert_eq!(args, vec!["+42", "test.rs"]);
+    #[test]
+    fn test_build_command_wraps_only_terminal_editors() {
+        let launch = LaunchOptions {
+            terminal_launcher: TerminalLauncher::TmuxWindow,
+            current_dir: Some(PathBuf::from("/proj")),
+            ..Default::default()
+        };
+        let options = ArgOptions {
+            line: Some(42),
+            ..Default::default()
+        };
+        let editor = |binary: &str| DetectedEditor {
+            binary: binary.to_string(),
+            kind: EditorKind::from_binary(binary),
+            extra_args: Vec::new(),
+            source: crate::detect::EditorSource::Explicit,
+        };
+
+        let cmd = build_command(&editor("nvim"), Path::new("test.rs"), &options, &launch);
+        assert_eq!(cmd.get_program(), "tmux");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(
+            args,
+            vec!["new-window", "-c", "/proj", "nvim", "+42", "test.rs"]
+        );
+
+        let cmd = build_command(&editor("code"), Path::new("test.rs"), &options, &launch);
+        assert_eq!(cmd.get_program(), "code");
+
+        // Unknown kind, but the binary is a known terminal editor
+        let cmd = build_command(&editor("micro"), Path::new("test.rs"), &options, &launch);
+        assert_eq!(cmd.get_program(), "tmux");
     }
 
     #[test]
-    fn test_emacs_args() {
-        let args = build_emacs_args("test.rs", Some(42), Some(10), false);
-        assert_eq!(args, vec!["+42:10", "test.rs"]);
+    fn test_build_command_wraps_assumed_terminal_editor() {
+        let launch = LaunchOptions {
+            terminal_launcher: TerminalLauncher::TmuxWindow,
+            assume_terminal: true,
+            current_dir: Some(PathBuf::from("/proj")),
+            ..Default::default()
+        };
+        let micro = DetectedEditor {
+            binary: "micro".to_string(),
+            kind: EditorKind::Unknown,
+            extra_args: Vec::new(),
+            source: crate::detect::EditorSource::Environment("EDITOR".to_string()),
+        };
+
+        let cmd = build_command(
+            &micro,
+            Path::new("test.rs"),
+            &ArgOptions::default(),
+            &launch,
+        );
+        assert_eq!(cmd.get_program(), "tmux");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, vec!["new-window", "-c", "/proj", "micro", "test.rs"]);
     }
 
     #[test]
-    fn test_notepadpp_args() {
-        let args = build_notepadpp_args("test.rs", Some(42), Some(10));
-        assert_eq!(args, vec!["-n42", "-c10", "test.rs"]);
+    #[cfg(unix)]
+    fn test_build_command_sets_argv0() {
+        let launch = LaunchOptions {
+            argv0: Some("view".to_string()),
+            ..Default::default()
+        };
+        let vim = DetectedEditor {
+            binary: "vim".to_string(),
+            kind: EditorKind::Vim,
+            extra_args: Vec::new(),
+            source: crate::detect::EditorSource::Explicit,
+        };
+
+        let cmd = build_command(&vim, Path::new("test.rs"), &ArgOptions::default(), &launch);
+        assert_eq!(cmd.get_program(), "vim");
+        // `Command`'s Debug output shows the program in brackets when argv[0] differs
+        assert!(
+            format!("{cmd:?}").starts_with(r#"["vim"] "view""#),
+            "{cmd:?}"
+        );
     }
 
     #[test]
-    fn test_jetbrains_args() {
-        let args = build_jetbrains_args("test.rs", Some(42), false);
-        assert_eq!(args, vec!["test.rs:42"]);
+    fn test_login_shell_script_quotes_words() {
+        let args = vec![
+            "--goto".to_string(),
+            "my file.rs:42".to_string(),
+            "it's".to_string(),
+            "$(rm -rf ~)".to_string(),
+            String::new(),
+        ];
+        assert_eq!(
+            login_shell_script("code", &args),
+            r"exec code --goto 'my file.rs:42' 'it'\''s' '$(rm -rf ~)' ''"
+        );
+        assert_eq!(
+            login_shell_script("/usr/local/bin/nvim", &[]),
+            "exec /usr/local/bin/nvim"
+        );
+    }
 
-        // JetBrains doesn't support column
-        let args = build_jetbrains_args("test.rs", Some(42), true);
-        assert_eq!(args, vec!["test.rs:42", "--wait"]);
+    #[test]
+    fn test_posix_login_shell() {
+        assert_eq!(posix_login_shell(Some("/bin/zsh")), "/bin/zsh");
+        assert_eq!(
+            posix_login_shell(Some("/usr/local/bin/bash")),
+            "/usr/local/bin/bash"
+        );
+        assert_eq!(posix_login_shell(Some("/usr/bin/fish")), "/bin/sh");
+        assert_eq!(posix_login_shell(Some("/bin/tcsh")), "/bin/sh");
+        assert_eq!(posix_login_shell(Some("nu")), "/bin/sh");
+        assert_eq!(posix_login_shell(Some("")), "/bin/sh");
+        assert_eq!(posix_login_shell(None), "/bin/sh");
     }
 
     #[test]
-    fn test_helix_args() {
-        let args = build_helix_args("test.rs", Some(42), Some(10));
-        assert_eq!(args, vec!["test.rs:42:10"]);
+    fn test_build_command_via_login_shell() {
+        let launch = LaunchOptions {
+            login_shell: Some("/bin/zsh".to_string()),
+            argv0: Some("view".to_string()),
+            ..Default::default()
+        };
+        let options = ArgOptions {
+            line: Some(42),
+            column: Some(7),
+            ..Default::default()
+        };
+        let code = DetectedEditor {
+            binary: "code".to_string(),
+            kind: EditorKind::VsCode,
+            extra_args: vec!["--new-window".to_string()],
+            source: crate::detect::EditorSource::Explicit,
+        };
+
+        let cmd = build_command(&code, Path::new("src/my file.rs"), &options, &launch);
+        assert_eq!(cmd.get_program(), "/bin/zsh");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(
+            args,
+            vec!["-lc", "exec code --new-window --goto 'src/my file.rs:42:7'"]
+        );
+        assert!(!format!("{cmd:?}").contains("view"), "{cmd:?}");
     }
 
     #[test]
-    fn test_kate_args() {
-        let args = build_kate_args("test.rs", Some(42), Some(10));
-        assert_eq!(args, vec!["--line", "42", "--column", "10", "test.rs"]);
+    fn test_activate_args() {
+        assert_eq!(
+            activate_args(EditorKind::VsCode),
+            Some(vec!["-a".to_string(), "Visual Studio Code".to_string()])
+        );
+        assert_eq!(
+            activate_args(EditorKind::Sublime),
+            Some(vec!["-a".to_string(), "Sublime Text".to_string()])
+        );
+        assert_eq!(activate_args(EditorKind::Vim), None);
+        assert_eq!(activate_args(EditorKind::Unknown), None);
     }
 }
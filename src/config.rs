@@ -42,7 +42,11 @@
 //!   args: ["--noplugin"]
 //! ```
 
-use crate::editor::EditorKind;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::kind::{EditorKind, KIND_NAMES};
 
 /// Sources from which an editor can be resolved.
 ///
@@ -72,6 +76,64 @@ pub enum ResolveFrom {
     Editor,
     /// Search PATH for known editors.
     PathSearch,
+    /// Check `$GIT_EDITOR` environment variable.
+    ///
+    /// Git sets this while running hooks and when invoking the commit
+    /// message editor, so tools acting as a git editor should prefer it.
+    GitEnvEditor,
+    /// Check `$GIT_MERGE_TOOL` environment variable.
+    ///
+    /// Useful for tools launched during `git mergetool`. Not part of any
+    /// predefined order.
+    GitMergeTool,
+    /// Check the system default editor set with `update-alternatives`
+    /// (`/etc/alternatives/editor`).
+    ///
+    /// Debian and Ubuntu only; skipped where the link doesn't exist. Not
+    /// part of any predefined order.
+    SystemAlternatives,
+    /// Check an arbitrary environment variable, such as `$MYAPP_EDITOR`.
+    ///
+    /// Parsed like `$EDITOR`. The name is `&'static str` so that
+    /// `ResolveFrom` stays `Copy`; a name only known at runtime has to be
+    /// leaked (e.g. with [`String::leak()`]) to be used here. Not part of
+    /// any predefined order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::{Editor, ResolveFrom};
+    ///
+    /// Editor::builder()
+    ///     .file("src/main.rs")
+    ///     .resolve_order(&[
+    ///         ResolveFrom::EnvVar("MYAPP_EDITOR"),
+    ///         ResolveFrom::Editor,
+    ///         ResolveFrom::PathSearch,
+    ///     ])
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    EnvVar(&'static str),
+}
+
+impl ResolveFrom {
+    /// Returns the environment variable this source reads, if it reads one.
+    ///
+    /// [`Visual`](Self::Visual), [`Editor`](Self::Editor),
+    /// [`GitEnvEditor`](Self::GitEnvEditor), and
+    /// [`GitMergeTool`](Self::GitMergeTool) are shorthands for
+    /// [`EnvVar`](Self::EnvVar) with their variable's name.
+    pub const fn env_var(&self) -> Option<&'static str> {
+        match self {
+            Self::Visual => Some("VISUAL"),
+            Self::Editor => Some("EDITOR"),
+            Self::GitEnvEditor => Some("GIT_EDITOR"),
+            Self::GitMergeTool => Some("GIT_MERGE_TOOL"),
+            Self::EnvVar(var) => Some(var),
+            Self::Config | Self::PathSearch | Self::SystemAlternatives => None,
+        }
+    }
 }
 
 /// Default resolution order when configs are provided.
@@ -93,6 +155,20 @@ pub const ENV_ONLY_RESOLVE_ORDER: &[ResolveFrom] = &[
     ResolveFrom::PathSearch,
 ];
 
+/// Resolution order for tools invoked by git (e.g., as a commit message editor).
+///
+/// Order: GitEnvEditor, Config, Visual, Editor, PathSearch
+///
+/// This mirrors git's own precedence, where `$GIT_EDITOR` overrides both the
+/// configured editor and the generic environment variables.
+pub const GIT_RESOLVE_ORDER: &[ResolveFrom] = &[
+    ResolveFrom::GitEnvEditor,
+    ResolveFrom::Config,
+    ResolveFrom::Visual,
+    ResolveFrom::Editor,
+    ResolveFrom::PathSearch,
+];
+
 /// Configuration for editor selection.
 ///
 /// This struct is typically loaded from an application's config file and
@@ -106,6 +182,8 @@ pub const ENV_ONLY_RESOLVE_ORDER: &[ResolveFrom] = &[
 /// - `editor`: Binary name or path (e.g., "nvim", "/usr/local/bin/code")
 /// - `editor_kind`: Alternative to `editor`, uses [`EditorKind`] string names
 /// - `args`: Extra arguments to pass to the editor
+/// - `args_template`: Argument template replacing opensesame's own arguments
+/// - `extension_map`: Editors to use for specific file extensions
 ///
 /// # Example
 ///
@@ -126,7 +204,8 @@ pub struct EditorConfig {
     ///
     /// This can be a simple binary name like "nvim" or a full path like
     /// "/usr/local/bin/code". The binary must exist in PATH (or at the
-    /// specified path) for resolution to succeed.
+    /// specified path) for resolution to succeed. A leading `~` and `$VAR`
+    /// or `${VAR}` references are expanded, as in "~/bin/code".
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub editor: Option<String>,
 
@@ -140,8 +219,44 @@ pub struct EditorConfig {
     /// Extra arguments to pass to the editor.
     ///
     /// These are appended to the command after opensesame's positioning arguments.
-    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    /// `~` and environment variables are expanded like in [`editor`](Self::editor).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
     pub args: Vec<String>,
+
+    /// Argument template that replaces opensesame's positioning arguments.
+    ///
+    /// Lets editors opensesame doesn't know receive a position. Each
+    /// argument may contain `{file}`, `{line}`, and `{column}`, which are
+    /// substituted when the command is built; `args` still come first.
+    /// When the line or column isn't set, an argument that references it is
+    /// dropped, along with the option right before it (an argument starting
+    /// with `-` and containing no tokens), so
+    /// `["--open", "{file}", "--goto-line", "{line}"]` becomes
+    /// `["--open", "main.rs"]`. The file is appended if no argument
+    /// references `{file}`. An empty template uses the built-in arguments.
+    ///
+    /// Applies to the config's `editor` or `editor_kind`, not to editors
+    /// from `extension_map`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub args_template: Vec<String>,
+
+    /// Editors to use for specific file extensions.
+    ///
+    /// Keys are extensions without the leading dot (`"md"`, `"rs"`),
+    /// matched case-insensitively. When the opened file's extension is
+    /// mapped and that editor is installed, it takes precedence over
+    /// `editor` and `editor_kind`; `args` are not passed to it.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub extension_map: BTreeMap<String, EditorKindConfig>,
 }
 
 impl EditorConfig {
@@ -151,6 +266,8 @@ impl EditorConfig {
             editor: None,
             editor_kind: None,
             args: Vec::new(),
+            args_template: Vec::new(),
+            extension_map: BTreeMap::new(),
         }
     }
 
@@ -160,6 +277,8 @@ impl EditorConfig {
             editor: Some(editor.into()),
             editor_kind: None,
             args: Vec::new(),
+            args_template: Vec::new(),
+            extension_map: BTreeMap::new(),
         }
     }
 
@@ -169,15 +288,59 @@ impl EditorConfig {
             editor: None,
             editor_kind: Some(EditorKindConfig(kind)),
             args: Vec::new(),
+            args_template: Vec::new(),
+            extension_map: BTreeMap::new(),
         }
     }
 
+    /// Maps a file extension to an editor kind.
+    ///
+    /// A leading dot is ignored, so `"md"` and `".md"` are equivalent.
+    #[must_use]
+    pub fn with_extension(mut self, extension: &str, kind: EditorKind) -> Self {
+        let extension = extension.strip_prefix('.').unwrap_or(extension);
+        self.extension_map
+            .insert(extension.to_string(), EditorKindConfig(kind));
+        self
+    }
+
+    /// Returns the editor kind mapped to a file extension, if any.
+    pub fn editor_for_extension(&self, extension: &str) -> Option<EditorKind> {
+        let extension = extension.strip_prefix('.').unwrap_or(extension);
+        self.extension_map
+            .iter()
+            .find(|(key, _)| {
+                key.strip_prefix('.')
+                    .unwrap_or(key)
+                    .eq_ignore_ascii_case(extension)
+            })
+            .map(|(_, kind)| kind.0)
+    }
+
     /// Returns true if this config has no editor specified.
     pub const fn is_empty(&self) -> bool {
         self.editor.is_none() && self.editor_kind.is_none()
     }
 }
 
+impl From<EditorKind> for EditorConfig {
+    /// Creates a config with the given editor kind.
+    ///
+    /// Equivalent to [`EditorConfig::with_editor_kind()`].
+    fn from(kind: EditorKind) -> Self {
+        Self::with_editor_kind(kind)
+    }
+}
+
+impl From<&str> for EditorConfig {
+    /// Creates a config with the given editor binary.
+    ///
+    /// Equivalent to [`EditorConfig::with_editor()`].
+    fn from(editor: &str) -> Self {
+        Self::with_editor(editor)
+    }
+}
+
 /// Wrapper for [`EditorKind`] that supports serde string deserialization.
 ///
 /// This allows config files to specify editors by name:
@@ -185,9 +348,35 @@ impl EditorConfig {
 /// ```yaml
 /// editor_kind: NeoVim
 /// ```
+///
+/// With the `serde` feature, [`EditorKind`] itself (de)serializes the same
+/// way, so new config structs can use it directly.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EditorKindConfig(pub EditorKind);
 
+/// Parses a name with [`EditorKind::from_name()`].
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::EditorKind;
+///
+/// assert_eq!("nvim".parse::<EditorKind>().unwrap(), EditorKind::NeoVim);
+/// assert!("notepad2".parse::<EditorKind>().is_err());
+/// ```
+impl FromStr for EditorKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).ok_or_else(|| Error::InvalidConfig {
+            message: format!(
+                "unknown editor '{s}', expected one of: {}",
+                KIND_NAMES.join(", ")
+            ),
+        })
+    }
+}
+
 impl From<EditorKind> for EditorKindConfig {
     fn from(kind: EditorKind) -> Self {
         Self(kind)
@@ -206,47 +395,7 @@ impl<'de> serde::Deserialize<'de> for EditorKindConfig {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        EditorKind::from_name(&s)
-            .map(EditorKindConfig)
-            .ok_or_else(|| {
-                serde::de::Error::unknown_variant(
-                    &s,
-                    &[
-                        "VsCode",
-                        "VsCodeInsiders",
-                        "VSCodium",
-                        "Cursor",
-                        "Windsurf",
-                        "Vim",
-                        "NeoVim",
-                        "Vi",
-                        "GVim",
-                        "Emacs",
-                        "EmacsClient",
-                        "Sublime",
-                        "Zed",
-                        "Helix",
-                        "Atom",
-                        "Kate",
-                        "Nano",
-                        "TextMate",
-                        "Xcode",
-                        "NotepadPlusPlus",
-                        "Notepad",
-                        "IntelliJ",
-                        "WebStorm",
-                        "PhpStorm",
-                        "PyCharm",
-                        "RubyMine",
-                        "GoLand",
-                        "CLion",
-                        "Rider",
-                        "DataGrip",
-                        "AndroidStudio",
-                    ],
-                )
-            })
+        EditorKind::deserialize(deserializer).map(EditorKindConfig)
     }
 }
 
@@ -256,7 +405,7 @@ impl serde::Serialize for EditorKindConfig {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(self.0.as_str())
+        self.0.serialize(serializer)
     }
 }
 
@@ -264,6 +413,18 @@ impl serde::Serialize for EditorKindConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_resolve_from_env_var() {
+        assert_eq!(ResolveFrom::Visual.env_var(), Some("VISUAL"));
+        assert_eq!(ResolveFrom::GitEnvEditor.env_var(), Some("GIT_EDITOR"));
+        assert_eq!(
+            ResolveFrom::EnvVar("MYAPP_EDITOR").env_var(),
+            Some("MYAPP_EDITOR")
+        );
+        assert_eq!(ResolveFrom::PathSearch.env_var(), None);
+        assert_eq!(ResolveFrom::Config.env_var(), None);
+    }
+
     #[test]
     fn test_editor_config_default() {
         let config = EditorConfig::default();
@@ -289,6 +450,25 @@ mod tests {
         assert!(!config.is_empty());
     }
 
+    #[test]
+    fn test_editor_config_from_kind() {
+        let config: EditorConfig = EditorKind::NeoVim.into();
+        assert!(config.editor.is_none());
+        assert_eq!(
+            config.editor_kind,
+            Some(EditorKindConfig(EditorKind::NeoVim))
+        );
+        assert!(config.args.is_empty());
+    }
+
+    #[test]
+    fn test_editor_config_from_str() {
+        let config: EditorConfig = "nvim".into();
+        assert_eq!(config.editor.as_deref(), Some("nvim"));
+        assert!(config.editor_kind.is_none());
+        assert!(config.args.is_empty());
+    }
+
     #[test]
     fn test_editor_kind_config_conversion() {
         let kind = EditorKind::VsCode;
@@ -297,6 +477,18 @@ mod tests {
         assert_eq!(kind, back);
     }
 
+    #[test]
+    fn test_editor_config_extension_map() {
+        let config = EditorConfig::with_editor("code")
+            .with_extension("md", EditorKind::Zed)
+            .with_extension(".rs", EditorKind::NeoVim);
+
+        assert_eq!(config.editor_for_extension("md"), Some(EditorKind::Zed));
+        assert_eq!(config.editor_for_extension(".MD"), Some(EditorKind::Zed));
+        assert_eq!(config.editor_for_extension("rs"), Some(EditorKind::NeoVim));
+        assert_eq!(config.editor_for_extension("txt"), None);
+    }
+
     #[test]
     fn test_resolve_from_equality() {
         assert_eq!(ResolveFrom::Config, ResolveFrom::Config);
@@ -312,6 +504,13 @@ mod tests {
         assert_eq!(DEFAULT_RESOLVE_ORDER[3], ResolveFrom::PathSearch);
     }
 
+    #[test]
+    fn test_git_resolve_order_has_git_editor_first() {
+        assert_eq!(GIT_RESOLVE_ORDER[0], ResolveFrom::GitEnvEditor);
+        assert!(GIT_RESOLVE_ORDER.contains(&ResolveFrom::Config));
+        assert!(!GIT_RESOLVE_ORDER.contains(&ResolveFrom::GitMergeTool));
+    }
+
     #[test]
     fn test_env_only_resolve_order() {
         assert_eq!(ENV_ONLY_RESOLVE_ORDER.len(), 3);
@@ -339,6 +538,24 @@ mod serde_tests {
         assert_eq!(parsed.args, config.args);
     }
 
+    #[test]
+    fn test_editor_config_args_template_serde() {
+        let json = r#"{
+            "editor": "myedit",
+            "args_template": ["--open", "{file}", "--goto-line", "{line}"]
+        }"#;
+        let config: EditorConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.args_template,
+            ["--open", "{file}", "--goto-line", "{line}"]
+        );
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains("args_template"));
+        let json = serde_json::to_string(&EditorConfig::with_editor("myedit")).unwrap();
+        assert!(!json.contains("args_template"));
+    }
+
     #[test]
     fn test_editor_config_deserialize_minimal() {
         let json = r#"{"editor": "code"}"#;
@@ -384,6 +601,20 @@ mod serde_tests {
         assert!(json.contains("VsCode"));
     }
 
+    #[test]
+    fn test_editor_config_deserialize_extension_map() {
+        let json = r#"{"editor": "code", "extension_map": {"md": "zed", "rs": "NeoVim"}}"#;
+        let config: EditorConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.editor_for_extension("md"), Some(EditorKind::Zed));
+        assert_eq!(config.editor_for_extension("rs"), Some(EditorKind::NeoVim));
+        assert!(
+            serde_json::to_string(&config)
+                .unwrap()
+                .contains("extension_map")
+        );
+    }
+
     #[test]
     fn test_editor_config_skip_empty_fields() {
         let config = EditorConfig::default();
@@ -3,9 +3,13 @@
 //! This module handles finding the user's preferred editor through various
 //! mechanisms: configuration, environment variables, and PATH search.
 
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
 use crate::config::{EditorConfig, ResolveFrom};
-use crate::editor::EditorKind;
+use crate::env::{Environment, SystemEnvironment};
 use crate::error::{Error, Result};
+use crate::kind::EditorKind;
 
 /// Common editor binaries to search for, in order of preference.
 ///
@@ -14,17 +18,18 @@ use crate::error::{Error, Result};
 /// 2. Traditional terminal editors (nvim, vim, emacs)
 /// 3. Simple editors (nano)
 const FALLBACK_EDITORS: &[&str] = &[
-    "code",      // VS Code
-    "cursor",    // Cursor
-    "windsurf",  // Windsurf
-    "zed",       // Zed
-    "nvim",      // NeoVim
-    "vim",       // Vim
-    "hx",        // Helix
-    "emacs",     // Emacs
-    "subl",      // Sublime Text
-    "nano",      // Nano
-    "vi",        // Vi (last resort)
+    "code",     // VS Code
+    "cursor",   // Cursor
+    "windsurf", // Windsurf
+    "zed",      // Zed
+    "nvim",     // NeoVim
+    "vim",      // Vim
+    "hx",       // Helix
+    "kak",      // Kakoune
+    "emacs",    // Emacs
+    "subl",     // Sublime Text
+    "nano",     // Nano
+    "vi",       // Vi (last resort)
 ];
 
 /// Windows-specific fallback editors.
@@ -32,10 +37,27 @@ const FALLBACK_EDITORS: &[&str] = &[
 const WINDOWS_FALLBACK_EDITORS: &[&str] = &[
     "code.cmd",
     "cursor.cmd",
+    "neovide",
+    "nvim-qt",
     "notepad++",
     "notepad",
 ];
 
+/// macOS-specific fallback editors.
+#[cfg(target_os = "macos")]
+const MACOS_FALLBACK_EDITORS: &[&str] = &["neovide", "vimr"];
+
+/// Editors searched for in macOS application bundles when their CLI isn't
+/// in PATH, in order of preference.
+#[cfg(target_os = "macos")]
+const MACOS_APP_BUNDLE_EDITORS: &[EditorKind] = &[
+    EditorKind::VsCode,
+    EditorKind::Cursor,
+    EditorKind::Windsurf,
+    EditorKind::Zed,
+    EditorKind::Sublime,
+];
+
 /// Detects the user's preferred editor.
 ///
 /// Detection order:
@@ -43,22 +65,24 @@ const WINDOWS_FALLBACK_EDITORS: &[&str] = &[
 /// 2. `$EDITOR` environment variable (traditional editor variable)
 /// 3. Search PATH for known editors
 ///
+/// Environment variables naming a binary that can't be found are skipped.
+///
 /// # Errors
 ///
 /// Returns `Error::NoEditorFound` if no editor could be detected.
-pub fn detect_editor() -> Result<DetectedEditor> {
+pub fn detect_editor(env: &dyn Environment) -> Result<DetectedEditor> {
     // Try $VISUAL first (preferred for visual/GUI editors)
-    if let Some(editor) = try_env_var("VISUAL") {
+    if let Some(editor) = try_env_var(env, "VISUAL").filter(|editor| is_installed(env, editor)) {
         return Ok(editor);
     }
 
     // Try $EDITOR
-    if let Some(editor) = try_env_var("EDITOR") {
+    if let Some(editor) = try_env_var(env, "EDITOR").filter(|editor| is_installed(env, editor)) {
         return Ok(editor);
     }
 
     // Search PATH for known editors
-    if let Some(editor) = search_path_for_editor() {
+    if let Some(editor) = search_path_for_editor(env) {
         return Ok(editor);
     }
 
@@ -72,8 +96,14 @@ pub fn detect_editor() -> Result<DetectedEditor> {
 ///
 /// # Arguments
 ///
+/// * `env` - The environment to read variables and search `PATH` from
 /// * `order` - The order in which to check sources
-/// * `configs` - Configurations passed via [`EditorBuilder::with_config()`](crate::EditorBuilder::with_config)
+/// * `configs` - Configurations passed via
+///   [`EditorBuilder::with_config()`](crate::EditorBuilder::with_config)
+/// * `extension` - Extension of the file being opened, matched against each
+///   config's [`extension_map`](EditorConfig::extension_map)
+/// * `verify` - Skip environment variables naming a binary that can't be
+///   found, instead of returning it
 ///
 /// # Errors
 ///
@@ -86,54 +116,248 @@ pub fn detect_editor() -> Result<DetectedEditor> {
 ///
 /// let order = &[ResolveFrom::Config, ResolveFrom::PathSearch];
 /// let configs = vec![EditorConfig::with_editor("nvim")];
-/// let editor = resolve_editor_with_order(order, &configs)?;
+/// let editor = resolve_editor_with_order(&SystemEnvironment, order, &configs, Some("rs"), true)?;
 /// ```
 pub fn resolve_editor_with_order(
+    env: &dyn Environment,
     order: &[ResolveFrom],
     configs: &[EditorConfig],
+    extension: Option<&str>,
+    verify: bool,
 ) -> Result<DetectedEditor> {
-    for source in order {
-        match source {
-            ResolveFrom::Config => {
-                for (index, config) in configs.iter().enumerate() {
-                    if let Some(editor) = try_config(config, index) {
-                        return Ok(editor);
-                    }
-                }
-            }
-            ResolveFrom::Visual => {
-                if let Some(editor) = try_env_var("VISUAL") {
-                    return Ok(editor);
-                }
-            }
-            ResolveFrom::Editor => {
-                if let Some(editor) = try_env_var("EDITOR") {
-                    return Ok(editor);
-                }
-            }
-            ResolveFrom::PathSearch => {
-                if let Some(editor) = search_path_for_editor() {
-                    return Ok(editor);
-                }
-            }
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("resolve_editor", ?order, ?extension).entered();
+    let editor = resolve_from_table(SOURCE_RESOLVERS, env, order, configs, extension)
+        .find(|editor| {
+            let installed = !verify || is_installed(env, editor);
+            trace_event!(
+                debug,
+                binary = %editor.binary,
+                source = %editor.source,
+                installed,
+                "checked editor candidate"
+            );
+            installed
+        })
+        .ok_or(Error::NoEditorFound)?;
+    trace_event!(
+        debug,
+        binary = %editor.binary,
+        kind = %editor.kind,
+        source = %editor.source,
+        "resolved editor"
+    );
+    Ok(editor)
+}
+
+/// Resolves every available editor using the specified resolution order.
+///
+/// Unlike [`resolve_editor_with_order()`], which stops at the first match,
+/// this returns all candidates from every source, in order: each matching
+/// config, the editor named by each environment variable, and every known
+/// editor installed in PATH. Entries are deduplicated by binary, keeping the
+/// first (highest priority) source. The first entry is the editor
+/// `resolve_editor_with_order()` would pick, so callers can retry with the
+/// next one if it fails to start.
+pub fn resolve_all_with_order(
+    env: &dyn Environment,
+    order: &[ResolveFrom],
+    configs: &[EditorConfig],
+    extension: Option<&str>,
+    verify: bool,
+) -> Vec<DetectedEditor> {
+    let mut resolved: Vec<DetectedEditor> = Vec::new();
+    let editors = resolve_from_table(SOURCE_RESOLVERS, env, order, configs, extension)
+        .filter(|editor| !verify || is_installed(env, editor));
+    for editor in editors {
+        if !resolved.iter().any(|r| r.binary == editor.binary) {
+            resolved.push(editor);
         }
     }
+    resolved
+}
 
-    Err(Error::NoEditorFound)
+/// Describes why each source in `order` found no editor.
+///
+/// Meant for after [`resolve_editor_with_order()`] failed, when every source
+/// came up empty; the reasons say what was looked for.
+pub fn explain_failure(
+    env: &dyn Environment,
+    order: &[ResolveFrom],
+    configs: &[EditorConfig],
+    extension: Option<&str>,
+) -> Vec<(ResolveFrom, String)> {
+    order
+        .iter()
+        .map(|&source| (source, explain_source(env, source, configs, extension)))
+        .collect()
+}
+
+/// Describes why a single source found no editor.
+fn explain_source(
+    env: &dyn Environment,
+    source: ResolveFrom,
+    configs: &[EditorConfig],
+    extension: Option<&str>,
+) -> String {
+    if let Some(var) = source.env_var() {
+        return match try_env_var(env, var) {
+            Some(editor) => format!("${var} names '{}', which was not found", editor.binary),
+            None if env.var(var).is_some() => format!("${var} is empty"),
+            None => format!("${var} is not set"),
+        };
+    }
+    match source {
+        ResolveFrom::Config => explain_configs(configs, extension),
+        ResolveFrom::PathSearch => "no known editor found in PATH".to_string(),
+        ResolveFrom::SystemAlternatives => format!("{ALTERNATIVES_EDITOR} does not exist"),
+        ResolveFrom::Visual
+        | ResolveFrom::Editor
+        | ResolveFrom::GitEnvEditor
+        | ResolveFrom::GitMergeTool
+        | ResolveFrom::EnvVar(_) => unreachable!("{source:?} reads an environment variable"),
+    }
+}
+
+/// Describes why none of the configs named an available editor.
+fn explain_configs(configs: &[EditorConfig], extension: Option<&str>) -> String {
+    if configs.is_empty() {
+        return "no configs were provided".to_string();
+    }
+    let reasons: Vec<String> = configs
+        .iter()
+        .enumerate()
+        .map(|(index, config)| {
+            let binaries: Vec<String> = extension
+                .and_then(|extension| config.editor_for_extension(extension))
+                .map(|kind| kind.default_binary().to_string())
+                .into_iter()
+                .chain(config.editor.clone())
+                .chain(
+                    config
+                        .editor_kind
+                        .as_ref()
+                        .map(|kind| kind.0.default_binary().to_string()),
+                )
+                .map(|binary| format!("'{binary}'"))
+                .collect();
+            if binaries.is_empty() {
+                format!("config {index} names no editor")
+            } else {
+                format!("config {index}: {} not found in PATH", binaries.join(", "))
+            }
+        })
+        .collect();
+    reasons.join("; ")
+}
+
+/// Lazily yields the editors a source resolves to, in priority order.
+///
+/// Takes the environment, the configs, and the extension of the file being
+/// opened.
+type SourceResolver = for<'a> fn(
+    &'a dyn Environment,
+    &'a [EditorConfig],
+    Option<&'a str>,
+) -> Box<dyn Iterator<Item = DetectedEditor> + 'a>;
+
+/// How each [`ResolveFrom`] source is resolved.
+///
+/// Adding a source means adding a variant and one entry here. Sources that
+/// read an environment variable ([`ResolveFrom::env_var()`]) need no entry:
+/// they are all resolved the same way. Other sources without an entry are
+/// skipped.
+const SOURCE_RESOLVERS: &[(ResolveFrom, SourceResolver)] = &[
+    (ResolveFrom::Config, |env, configs, extension| {
+        Box::new(
+            configs
+                .iter()
+                .enumerate()
+                .filter_map(move |(index, config)| try_config(env, config, index, extension)),
+        )
+    }),
+    (ResolveFrom::PathSearch, |env, _, _| {
+        Box::new(installed_path_editors(env))
+    }),
+    (ResolveFrom::SystemAlternatives, |env, _, _| {
+        Box::new(detect_from_alternatives(env).into_iter())
+    }),
+];
+
+/// Yields the editors from each source in `order`, using `table` to look
+/// up how each source is resolved.
+fn resolve_from_table<'a>(
+    table: &'a [(ResolveFrom, SourceResolver)],
+    env: &'a dyn Environment,
+    order: &'a [ResolveFrom],
+    configs: &'a [EditorConfig],
+    extension: Option<&'a str>,
+) -> impl Iterator<Item = DetectedEditor> + 'a {
+    order.iter().flat_map(
+        move |source| -> Box<dyn Iterator<Item = DetectedEditor> + 'a> {
+            if let Some(var) = source.env_var() {
+                return Box::new(try_env_var(env, var).into_iter());
+            }
+            match table.iter().find(|(entry, _)| entry == source) {
+                Some((_, resolve)) => resolve(env, configs, extension),
+                None => Box::new(std::iter::empty()),
+            }
+        },
+    )
 }
 
 /// Attempts to create a `DetectedEditor` from an `EditorConfig`.
 ///
+/// An editor mapped to the file's `extension` takes precedence. The
+/// `editor` and `args` fields are expanded with [`expand_config_value()`].
 /// Returns `None` if the config doesn't specify an editor or the editor
 /// isn't available.
-fn try_config(config: &EditorConfig, index: usize) -> Option<DetectedEditor> {
+fn try_config(
+    env: &dyn Environment,
+    config: &EditorConfig,
+    index: usize,
+    extension: Option<&str>,
+) -> Option<DetectedEditor> {
+    // Try the editor mapped to the file's extension first
+    if let Some(kind) = extension.and_then(|extension| config.editor_for_extension(extension)) {
+        let binary = kind.default_binary();
+        let found = env.which(binary).is_some();
+        trace_event!(
+            debug,
+            config = index,
+            extension,
+            binary,
+            found,
+            "checked config editor for extension"
+        );
+        if found {
+            return Some(DetectedEditor {
+                binary: binary.to_string(),
+                kind,
+                extra_args: Vec::new(),
+                source: EditorSource::Config { index },
+            });
+        }
+    }
+
+    let args = || {
+        config
+            .args
+            .iter()
+            .map(|arg| expand_config_value(env, arg))
+            .collect()
+    };
+
     // Try editor binary first (more specific)
     if let Some(ref binary) = config.editor {
-        if which::which(binary).is_ok() {
+        let binary = expand_config_value(env, binary);
+        let found = env.which(&binary).is_some();
+        trace_event!(debug, config = index, %binary, found, "checked config editor");
+        if found {
             return Some(DetectedEditor {
-                binary: binary.clone(),
-                kind: EditorKind::from_binary(binary),
-                extra_args: config.args.clone(),
+                kind: EditorKind::from_binary(&binary),
+                binary,
+                extra_args: args(),
                 source: EditorSource::Config { index },
             });
         }
@@ -142,11 +366,19 @@ fn try_config(config: &EditorConfig, index: usize) -> Option<DetectedEditor> {
     // Try editor_kind (fallback to default binary)
     if let Some(ref kind_config) = config.editor_kind {
         let binary = kind_config.0.default_binary();
-        if which::which(binary).is_ok() {
+        let found = env.which(binary).is_some();
+        trace_event!(
+            debug,
+            config = index,
+            binary,
+            found,
+            "checked config editor kind"
+        );
+        if found {
             return Some(DetectedEditor {
                 binary: binary.to_string(),
                 kind: kind_config.0,
-                extra_args: config.args.clone(),
+                extra_args: args(),
                 source: EditorSource::Config { index },
             });
         }
@@ -155,9 +387,68 @@ fn try_config(config: &EditorConfig, index: usize) -> Option<DetectedEditor> {
     None
 }
 
+/// Expands a leading `~` and `$VAR`/`${VAR}` references in a config value.
+///
+/// `~` alone or followed by a path separator becomes the home directory
+/// (`$HOME`, or `%USERPROFILE%` on Windows). References to unset variables
+/// are kept as written, so a lookup fails visibly instead of using a
+/// truncated path. No other shell syntax is interpreted, and values
+/// without `~` or `$` are returned unchanged.
+fn expand_config_value(env: &dyn Environment, value: &str) -> String {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    if let Some(after) = rest.strip_prefix('~') {
+        if after.is_empty() || after.starts_with(std::path::is_separator) {
+            if let Some(home) = env.var("HOME").or_else(|| env.var("USERPROFILE")) {
+                expanded.push_str(&home);
+                rest = after;
+            }
+        }
+    }
+
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let (name, tail) = if let Some(braced) = after.strip_prefix('{') {
+            braced.split_once('}').unwrap_or(("", after))
+        } else {
+            let end = after
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(after.len());
+            after.split_at(end)
+        };
+        let is_name = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_');
+        if let Some(value) = env.var(name).filter(|_| is_name) {
+            expanded.push_str(&value);
+            rest = tail;
+        } else {
+            expanded.push('$');
+            rest = after;
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Returns whether an editor can be run.
+///
+/// Editors from environment variables are taken at their word, so their
+/// binary is looked up in `PATH` (or checked to exist, for a path). Other
+/// sources only yield editors that were already found.
+fn is_installed(env: &dyn Environment, editor: &DetectedEditor) -> bool {
+    !matches!(editor.source, EditorSource::Environment(_)) || env.which(&editor.binary).is_some()
+}
+
 /// Attempts to get an editor from an environment variable.
-fn try_env_var(var: &str) -> Option<DetectedEditor> {
-    let value = std::env::var(var).ok()?;
+///
+/// The binary isn't checked; see [`resolve_editor_with_order()`] for lookups
+/// that skip editors that aren't installed.
+pub fn try_env_var(env: &dyn Environment, var: &str) -> Option<DetectedEditor> {
+    let value = env.var(var);
+    trace_event!(debug, var, value = ?value, "read editor variable");
+    let value = value?;
     let value = value.trim();
 
     if value.is_empty() {
@@ -165,9 +456,16 @@ fn try_env_var(var: &str) -> Option<DetectedEditor> {
     }
 
     // Parse the editor command (may include arguments like "code --wait")
-    let parts: Vec<&str> = value.split_whitespace().collect();
-    let binary = (*parts.first()?).to_string();
-    let args: Vec<String> = parts[1..].iter().map(|s| (*s).to_string()).collect();
+    let mut parts = if cfg!(windows) {
+        split_windows_command(value)
+    } else if value.contains(['\'', '"', '\\']) {
+        split_posix_command(value)
+    } else {
+        value.split_whitespace().map(str::to_string).collect()
+    }
+    .into_iter();
+    let binary = parts.next()?;
+    let args: Vec<String> = parts.collect();
 
     // Extract just the binary name for kind detection
     let binary_name = std::path::Path::new(&binary)
@@ -184,47 +482,327 @@ fn try_env_var(var: &str) -> Option<DetectedEditor> {
     })
 }
 
-/// Searches PATH for known editor binaries.
-fn search_path_for_editor() -> Option<DetectedEditor> {
-    for &binary in FALLBACK_EDITORS {
-        if which::which(binary).is_ok() {
-            return Some(DetectedEditor {
-                binary: binary.to_string(),
-                kind: EditorKind::from_binary(binary),
-                extra_args: Vec::new(),
-                source: EditorSource::PathSearch,
-            });
+/// The `update-alternatives` link for the system default editor.
+const ALTERNATIVES_EDITOR: &str = "/etc/alternatives/editor";
+
+/// Detects the system default editor set with `update-alternatives`.
+///
+/// Follows the `/etc/alternatives/editor` symlink chain to the real binary,
+/// such as `/usr/bin/vim.basic`. Returns `None` if the link doesn't exist,
+/// as on systems other than Debian and Ubuntu.
+pub fn detect_from_alternatives(env: &dyn Environment) -> Option<DetectedEditor> {
+    alternatives_editor(env, Path::new(ALTERNATIVES_EDITOR))
+}
+
+/// Resolves an alternatives `link` to the editor it points at.
+fn alternatives_editor(env: &dyn Environment, link: &Path) -> Option<DetectedEditor> {
+    let target = env.resolve_link(link)?;
+    let kind = kind_from_file_name(target.file_name()?.to_str()?);
+
+    Some(DetectedEditor {
+        binary: target.to_str()?.to_string(),
+        kind,
+        extra_args: Vec::new(),
+        source: EditorSource::SystemAlternatives,
+    })
+}
+
+/// Detects the kind of an editor from the file name of its real binary.
+fn kind_from_file_name(name: &str) -> EditorKind {
+    // Debian suffixes variants of a package's binary: vim.basic, vim.tiny
+    match EditorKind::from_binary(name) {
+        EditorKind::Unknown => name
+            .split_once('.')
+            .map_or(EditorKind::Unknown, |(base, _)| {
+                EditorKind::from_binary(base)
+            }),
+        kind => kind,
+    }
+}
+
+/// Returns the kind of the editor that a `vi` or `editor` binary links to.
+///
+/// Many distributions install `vi` and `/usr/bin/editor` as symlinks to Vim
+/// or NeoVim, which position the cursor better than Vi. Other kinds are
+/// kept (a `gvim` linked to Vim must stay GVim), as is `kind` when the link
+/// can't be resolved or points at an unknown editor.
+fn resolve_linked_kind(path: &Path, kind: EditorKind) -> EditorKind {
+    if !matches!(kind, EditorKind::Vi | EditorKind::Unknown) {
+        return kind;
+    }
+    let Ok(target) = std::fs::canonicalize(path) else {
+        return kind;
+    };
+    match target
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(kind_from_file_name)
+    {
+        Some(EditorKind::Unknown) | None => kind,
+        Some(linked) => {
+            trace_event!(debug, link = ?path, ?target, %linked, "followed editor symlink");
+            linked
         }
     }
+}
+
+/// Splits a command line using POSIX shell quoting rules.
+///
+/// Single quotes keep everything literally; double quotes group words and
+/// let a backslash escape `"`, `\`, `$`, and `` ` ``; an unquoted backslash
+/// escapes the next character. Nothing is expanded. An unterminated quote
+/// runs to the end of the value.
+fn split_posix_command(value: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                arg.extend(chars.by_ref().take_while(|&ch| ch != '\''));
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                while let Some(ch) = chars.next() {
+                    match ch {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some(next @ ('"' | '\\' | '$' | '`')) => arg.push(next),
+                            Some(next) => {
+                                arg.push('\\');
+                                arg.push(next);
+                            }
+                            None => arg.push('\\'),
+                        },
+                        ch => arg.push(ch),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    current.get_or_insert_with(String::new).push(next);
+                }
+            }
+            ch if ch.is_whitespace() => parts.extend(current.take()),
+            ch => current.get_or_insert_with(String::new).push(ch),
+        }
+    }
+    parts.extend(current);
+    parts
+}
+
+/// Splits a command line using Windows quoting rules.
+///
+/// Double quotes group words, so `"C:\Program Files\Microsoft VS Code\bin\code.cmd" --wait`
+/// yields the path as one binary. In the program name backslashes are
+/// literal; in arguments, backslashes escape a following quote as described
+/// for `CommandLineToArgvW`.
+fn split_windows_command(value: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut chars = value.trim_start().chars().peekable();
+
+    // Program name: quotes toggle grouping, nothing is escaped
+    let mut program = String::new();
+    let mut quoted = false;
+    while let Some(ch) = chars.next_if(|&ch| quoted || !ch.is_whitespace()) {
+        if ch == '"' {
+            quoted = !quoted;
+        } else {
+            program.push(ch);
+        }
+    }
+    parts.push(program);
+
+    // Arguments: 2n backslashes + quote -> n backslashes and a toggle,
+    // 2n+1 backslashes + quote -> n backslashes and a literal quote
+    let mut current: Option<String> = None;
+    let mut quoted = false;
+    let mut backslashes = 0;
+    for ch in chars {
+        match ch {
+            '\\' => backslashes += 1,
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                arg.extend(std::iter::repeat_n('\\', backslashes / 2));
+                if backslashes % 2 == 1 {
+                    arg.push('"');
+                } else {
+                    quoted = !quoted;
+                }
+                backslashes = 0;
+            }
+            ch if ch.is_whitespace() && !quoted => {
+                if let Some(mut arg) = current.take() {
+                    arg.extend(std::iter::repeat_n('\\', backslashes));
+                    parts.push(arg);
+                }
+                backslashes = 0;
+            }
+            ch => {
+                let arg = current.get_or_insert_with(String::new);
+                arg.extend(std::iter::repeat_n('\\', backslashes));
+                arg.push(ch);
+                backslashes = 0;
+            }
+        }
+    }
+    if backslashes > 0 {
+        current
+            .get_or_insert_with(String::new)
+            .extend(std::iter::repeat_n('\\', backslashes));
+    }
+    parts.extend(current);
+
+    parts
+}
+
+/// Searches PATH for known editor binaries.
+pub fn search_path_for_editor(env: &dyn Environment) -> Option<DetectedEditor> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("search_path").entered();
+    installed_path_editors(env).next()
+}
+
+/// Returns every known editor binary found in PATH, in preference order.
+fn installed_path_editors(env: &dyn Environment) -> impl Iterator<Item = DetectedEditor> + '_ {
+    let binaries = FALLBACK_EDITORS.iter();
 
     // Windows-specific fallbacks
     #[cfg(windows)]
-    for &binary in WINDOWS_FALLBACK_EDITORS {
-        if which::which(binary).is_ok() {
-            return Some(DetectedEditor {
-                binary: binary.to_string(),
-                kind: EditorKind::from_binary(binary),
-                extra_args: Vec::new(),
-                source: EditorSource::PathSearch,
-            });
+    let binaries = binaries.chain(WINDOWS_FALLBACK_EDITORS.iter());
+    #[cfg(target_os = "macos")]
+    let binaries = binaries.chain(MACOS_FALLBACK_EDITORS.iter());
+
+    let editors = binaries
+        .filter(|binary| {
+            let found = env.which(binary).is_some();
+            trace_event!(debug, %binary, found, "searched PATH for editor");
+            found
+        })
+        .map(|&binary| DetectedEditor {
+            binary: binary.to_string(),
+            kind: EditorKind::from_binary(binary),
+            extra_args: Vec::new(),
+            source: EditorSource::PathSearch,
+        });
+
+    // Apps whose CLI was never linked into PATH
+    #[cfg(target_os = "macos")]
+    let editors = editors.chain(
+        MACOS_APP_BUNDLE_EDITORS
+            .iter()
+            .filter(move |kind| env.which(kind.default_binary()).is_none())
+            .filter_map(move |&kind| {
+                Some(DetectedEditor {
+                    binary: find_in_app_bundle(env, kind)?,
+                    kind,
+                    extra_args: Vec::new(),
+                    source: EditorSource::PathSearch,
+                })
+            }),
+    );
+
+    editors
+}
+
+/// Returns the paths of an editor's CLI inside its macOS application
+/// bundle, relative to an `Applications` folder.
+#[cfg(any(target_os = "macos", test))]
+const fn app_bundle_clis(kind: EditorKind) -> &'static [&'static str] {
+    match kind {
+        EditorKind::VsCode => &["Visual Studio Code.app/Contents/Resources/app/bin/code"],
+        EditorKind::VsCodeInsiders => {
+            &["Visual Studio Code - Insiders.app/Contents/Resources/app/bin/code-insiders"]
         }
+        EditorKind::VSCodium => &["VSCodium.app/Contents/Resources/app/bin/codium"],
+        EditorKind::Cursor => &["Cursor.app/Contents/Resources/app/bin/cursor"],
+        EditorKind::Windsurf => &["Windsurf.app/Contents/Resources/app/bin/windsurf"],
+        EditorKind::Zed => &["Zed.app/Contents/MacOS/cli"],
+        EditorKind::Sublime => &["Sublime Text.app/Contents/SharedSupport/bin/subl"],
+        EditorKind::GVim => &["MacVim.app/Contents/bin/mvim"],
+        EditorKind::Neovide => &["Neovide.app/Contents/MacOS/neovide"],
+        EditorKind::VimR => &["VimR.app/Contents/Resources/vimr"],
+        EditorKind::TextMate => &["TextMate.app/Contents/SharedSupport/Support/bin/mate"],
+        EditorKind::IntelliJ => &[
+            "IntelliJ IDEA.app/Contents/MacOS/idea",
+            "IntelliJ IDEA CE.app/Contents/MacOS/idea",
+        ],
+        EditorKind::WebStorm => &["WebStorm.app/Contents/MacOS/webstorm"],
+        EditorKind::PhpStorm => &["PhpStorm.app/Contents/MacOS/phpstorm"],
+        EditorKind::PyCharm => &[
+            "PyCharm.app/Contents/MacOS/pycharm",
+            "PyCharm CE.app/Contents/MacOS/pycharm",
+        ],
+        EditorKind::RubyMine => &["RubyMine.app/Contents/MacOS/rubymine"],
+        EditorKind::GoLand => &["GoLand.app/Contents/MacOS/goland"],
+        EditorKind::CLion => &["CLion.app/Contents/MacOS/clion"],
+        EditorKind::Rider => &["Rider.app/Contents/MacOS/rider"],
+        EditorKind::DataGrip => &["DataGrip.app/Contents/MacOS/datagrip"],
+        EditorKind::AndroidStudio => &["Android Studio.app/Contents/MacOS/studio"],
+        _ => &[],
     }
+}
 
-    None
+/// Returns where to look for an editor's CLI in macOS application bundles:
+/// `/Applications`, then `~/Applications`.
+#[cfg(any(target_os = "macos", test))]
+fn app_bundle_candidates(kind: EditorKind, home: Option<&str>) -> Vec<PathBuf> {
+    let roots = std::iter::once(PathBuf::from("/Applications"))
+        .chain(home.map(|home| Path::new(home).join("Applications")));
+    roots
+        .flat_map(|root| app_bundle_clis(kind).iter().map(move |cli| root.join(cli)))
+        .collect()
+}
+
+/// Finds an editor's CLI inside its macOS application bundle.
+#[cfg(any(target_os = "macos", test))]
+fn find_in_app_bundle(env: &dyn Environment, kind: EditorKind) -> Option<String> {
+    app_bundle_candidates(kind, env.var("HOME").as_deref())
+        .into_iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .find(|path| env.which(path).is_some())
+}
+
+/// Collects every editor the user could pick from.
+///
+/// Includes the editors named by `$VISUAL` and `$EDITOR` followed by each
+/// known editor found in PATH. Entries are deduplicated by binary, keeping
+/// the first (highest priority) source.
+pub fn candidates(env: &dyn Environment) -> Vec<DetectedEditor> {
+    resolve_all_with_order(env, crate::config::ENV_ONLY_RESOLVE_ORDER, &[], None, true)
+}
+
+/// Collects every known editor installed in PATH, in order of preference.
+///
+/// Unlike [`search_path_for_editor()`], this doesn't stop at the first
+/// editor found. Environment variables are ignored, and editors are
+/// deduplicated by kind, keeping the preferred binary.
+pub fn detect_all(env: &dyn Environment) -> Vec<DetectedEditor> {
+    let mut editors: Vec<DetectedEditor> = Vec::new();
+    for editor in installed_path_editors(env) {
+        if !editors.iter().any(|e| e.kind == editor.kind) {
+            editors.push(editor);
+        }
+    }
+    editors
 }
 
 /// Finds a specific editor binary.
 ///
+/// A `vi` or `editor` binary that is a symlink is detected as the editor it
+/// points at, such as NeoVim for a `vi` linked to `nvim`.
+///
 /// # Errors
 ///
 /// Returns `Error::EditorNotFound` if the binary is not in PATH.
-pub fn find_editor(binary: &str) -> Result<DetectedEditor> {
+pub fn find_editor(env: &dyn Environment, binary: &str) -> Result<DetectedEditor> {
     // Check if it's in PATH
-    if which::which(binary).is_err() {
+    let Some(path) = env.which(binary) else {
         return Err(Error::EditorNotFound {
             binary: binary.to_string(),
         });
-    }
+    };
 
     let binary_name = std::path::Path::new(binary)
         .file_name()
@@ -233,7 +811,7 @@ pub fn find_editor(binary: &str) -> Result<DetectedEditor> {
 
     Ok(DetectedEditor {
         binary: binary.to_string(),
-        kind: EditorKind::from_binary(binary_name),
+        kind: resolve_linked_kind(&path, EditorKind::from_binary(binary_name)),
         extra_args: Vec::new(),
         source: EditorSource::Explicit,
     })
@@ -241,28 +819,48 @@ pub fn find_editor(binary: &str) -> Result<DetectedEditor> {
 
 /// Creates a detected editor from an `EditorKind`.
 ///
+/// On macOS, an editor whose CLI isn't in PATH is also looked for inside
+/// its application bundle. [`EditorKind::Vi`] becomes the editor its
+/// binary links to, as in [`find_editor()`].
+///
 /// # Errors
 ///
 /// Returns `Error::EditorNotFound` if the editor binary is not in PATH.
-pub fn find_editor_by_kind(kind: EditorKind) -> Result<DetectedEditor> {
+pub fn find_editor_by_kind(env: &dyn Environment, kind: EditorKind) -> Result<DetectedEditor> {
     let binary = kind.default_binary();
 
     // Check if it's in PATH
-    if which::which(binary).is_err() {
+    let Some(path) = env.which(binary) else {
+        #[cfg(target_os = "macos")]
+        if let Some(binary) = find_in_app_bundle(env, kind) {
+            return Ok(DetectedEditor {
+                binary,
+                kind,
+                extra_args: Vec::new(),
+                source: EditorSource::Explicit,
+            });
+        }
         return Err(Error::EditorNotFound {
             binary: binary.to_string(),
         });
-    }
+    };
 
     Ok(DetectedEditor {
         binary: binary.to_string(),
-        kind,
+        kind: resolve_linked_kind(&path, kind),
         extra_args: Vec::new(),
         source: EditorSource::Explicit,
     })
 }
 
 /// A detected editor with its metadata.
+///
+/// Returned by [`Editor::candidates()`](crate::Editor::candidates) and
+/// describes a concrete editor binary along with how it was found.
+///
+/// Equality and hashing consider only `binary` and `kind`: the same editor
+/// found through `$EDITOR` and through `PATH` compares equal, so candidate
+/// lists can be deduplicated with a `HashSet`.
 #[derive(Debug, Clone)]
 pub struct DetectedEditor {
     /// The binary name or path.
@@ -272,18 +870,64 @@ pub struct DetectedEditor {
     /// Extra arguments from environment variable (e.g., "--wait" from "$EDITOR=code --wait").
     pub extra_args: Vec<String>,
     /// How the editor was detected (useful for debugging/introspection).
-    #[allow(dead_code)]
     pub source: EditorSource,
 }
 
+impl PartialEq for DetectedEditor {
+    fn eq(&self, other: &Self) -> bool {
+        self.binary == other.binary && self.kind == other.kind
+    }
+}
+
+impl Eq for DetectedEditor {}
+
+impl Hash for DetectedEditor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.binary.hash(state);
+        self.kind.hash(state);
+    }
+}
+
 impl DetectedEditor {
     /// Returns `true` if this is a terminal-based editor (requires TTY).
     pub const fn is_terminal_editor(&self) -> bool {
         self.kind.is_terminal_editor()
     }
+
+    /// Resolves the binary to the absolute path of the executable.
+    ///
+    /// `binary` is often a bare name like `code`; this searches `PATH` the
+    /// same way launching does. Returns `None` if the binary can't be found.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// for editor in Editor::candidates() {
+    ///     println!("{} -> {:?}", editor.binary, editor.resolved_path());
+    /// }
+    /// ```
+    pub fn resolved_path(&self) -> Option<PathBuf> {
+        self.resolved_path_in(&SystemEnvironment)
+    }
+
+    /// Resolves the binary to an absolute path in the given environment.
+    pub(crate) fn resolved_path_in(&self, env: &dyn Environment) -> Option<PathBuf> {
+        env.which(&self.binary)
+    }
 }
 
 /// How an editor was detected.
+///
+/// The `Display` form is suitable for user-facing messages:
+///
+/// ```rust
+/// use opensesame::EditorSource;
+///
+/// let source = EditorSource::Environment("VISUAL".to_string());
+/// assert_eq!(format!("editor chosen from {source}"), "editor chosen from $VISUAL");
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EditorSource {
     /// Detected from an environment variable.
@@ -297,12 +941,194 @@ pub enum EditorSource {
         /// Index of the config in the resolution chain (0 = highest priority).
         index: usize,
     },
+    /// The system default set with `update-alternatives`.
+    SystemAlternatives,
+}
+
+impl std::fmt::Display for EditorSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Environment(var) => write!(f, "${var}"),
+            Self::PathSearch => write!(f, "PATH"),
+            Self::Explicit => write!(f, "explicit"),
+            Self::Config { index } => write!(f, "config #{index}"),
+            Self::SystemAlternatives => write!(f, "alternatives"),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{DEFAULT_RESOLVE_ORDER, ENV_ONLY_RESOLVE_ORDER};
+    use crate::config::{DEFAULT_RESOLVE_ORDER, ENV_ONLY_RESOLVE_ORDER, GIT_RESOLVE_ORDER};
+    use crate::env::FakeEnvironment;
+
+    #[test]
+    fn test_source_resolvers_cover_every_source() {
+        let sources = [
+            ResolveFrom::Config,
+            ResolveFrom::Visual,
+            ResolveFrom::Editor,
+            ResolveFrom::PathSearch,
+            ResolveFrom::GitEnvEditor,
+            ResolveFrom::GitMergeTool,
+            ResolveFrom::SystemAlternatives,
+        ];
+        for source in sources {
+            // Environment sources are resolved through `env_var()` instead
+            let expected = usize::from(source.env_var().is_none());
+            let count = SOURCE_RESOLVERS
+                .iter()
+                .filter(|(entry, _)| *entry == source)
+                .count();
+            assert_eq!(count, expected, "{source:?}");
+        }
+        assert_eq!(SOURCE_RESOLVERS.len(), 3);
+    }
+
+    #[test]
+    fn test_every_env_source_resolves_its_variable() {
+        let env = FakeEnvironment::new()
+            .with_var("VISUAL", "code")
+            .with_var("EDITOR", "vim")
+            .with_var("GIT_EDITOR", "hx")
+            .with_var("GIT_MERGE_TOOL", "meld")
+            .with_var("MYAPP_EDITOR", "nano")
+            .with_binary("code")
+            .with_binary("vim")
+            .with_binary("hx")
+            .with_binary("meld")
+            .with_binary("nano");
+        let sources = [
+            ResolveFrom::Visual,
+            ResolveFrom::Editor,
+            ResolveFrom::GitEnvEditor,
+            ResolveFrom::GitMergeTool,
+            ResolveFrom::EnvVar("MYAPP_EDITOR"),
+        ];
+        for source in sources {
+            let var = source.env_var().unwrap();
+            let editor = resolve_editor_with_order(&env, &[source], &[], None, true).unwrap();
+            assert_eq!(Some(editor.binary), env.var(var), "{source:?}");
+            assert_eq!(editor.source, EditorSource::Environment(var.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_resolve_custom_env_var() {
+        let env = FakeEnvironment::new()
+            .with_var("MYAPP_EDITOR", "hx --vsplit")
+            .with_var("EDITOR", "vim")
+            .with_binary("hx")
+            .with_binary("vim");
+        let order = [ResolveFrom::EnvVar("MYAPP_EDITOR"), ResolveFrom::Editor];
+
+        let editor = resolve_editor_with_order(&env, &order, &[], None, true).unwrap();
+        assert_eq!(editor.binary, "hx");
+        assert_eq!(editor.kind, EditorKind::Helix);
+        assert_eq!(editor.extra_args, vec!["--vsplit"]);
+        assert_eq!(
+            editor.source,
+            EditorSource::Environment("MYAPP_EDITOR".to_string())
+        );
+
+        // Unset, so resolution moves on
+        let order = [ResolveFrom::EnvVar("OTHER_EDITOR"), ResolveFrom::Editor];
+        let editor = resolve_editor_with_order(&env, &order, &[], None, true).unwrap();
+        assert_eq!(editor.binary, "vim");
+        assert_eq!(
+            explain_source(&env, ResolveFrom::EnvVar("OTHER_EDITOR"), &[], None),
+            "$OTHER_EDITOR is not set"
+        );
+    }
+
+    #[test]
+    fn test_unhandled_source_is_skipped() {
+        let env = FakeEnvironment::new()
+            .with_var("EDITOR", "vim")
+            .with_binary("meld")
+            .with_binary("vim");
+        let table: Vec<_> = SOURCE_RESOLVERS
+            .iter()
+            .copied()
+            .filter(|(source, _)| *source != ResolveFrom::Config)
+            .collect();
+        let order = [ResolveFrom::Config, ResolveFrom::Editor];
+        let configs = [EditorConfig {
+            editor: Some("meld".to_string()),
+            ..Default::default()
+        }];
+
+        let editors: Vec<_> = resolve_from_table(&table, &env, &order, &configs, None).collect();
+        assert_eq!(editors.len(), 1);
+        assert_eq!(editors[0].binary, "vim");
+    }
+
+    #[test]
+    fn test_detected_editor_resolved_path() {
+        let env = FakeEnvironment::new().with_binary_at("code", "/opt/vscode/bin/code");
+        let editor = DetectedEditor {
+            binary: "code".to_string(),
+            kind: EditorKind::VsCode,
+            extra_args: Vec::new(),
+            source: EditorSource::PathSearch,
+        };
+        assert_eq!(
+            editor.resolved_path_in(&env),
+            Some(PathBuf::from("/opt/vscode/bin/code"))
+        );
+
+        let missing = DetectedEditor {
+            binary: "subl".to_string(),
+            kind: EditorKind::Sublime,
+            ..editor
+        };
+        assert_eq!(missing.resolved_path_in(&env), None);
+    }
+
+    #[test]
+    fn test_detected_editor_dedupes_across_sources() {
+        use std::collections::HashSet;
+
+        let detected = |source| DetectedEditor {
+            binary: "nvim".to_string(),
+            kind: EditorKind::NeoVim,
+            extra_args: Vec::new(),
+            source,
+        };
+        let from_env = DetectedEditor {
+            extra_args: vec!["-p".to_string()],
+            ..detected(EditorSource::Environment("EDITOR".to_string()))
+        };
+        let from_path = detected(EditorSource::PathSearch);
+        assert_eq!(from_env, from_path);
+
+        let editors: HashSet<_> = [from_env, from_path, detected(EditorSource::Explicit)]
+            .into_iter()
+            .collect();
+        assert_eq!(editors.len(), 1);
+
+        let vim = DetectedEditor {
+            binary: "vim".to_string(),
+            kind: EditorKind::Vim,
+            ..detected(EditorSource::PathSearch)
+        };
+        assert!(!editors.contains(&vim));
+    }
+    #[test]
+    fn test_editor_source_display() {
+        assert_eq!(
+            EditorSource::Environment("VISUAL".to_string()).to_string(),
+            "$VISUAL"
+        );
+        assert_eq!(
+            EditorSource::Environment("EDITOR".to_string()).to_string(),
+            "$EDITOR"
+        );
+        assert_eq!(EditorSource::PathSearch.to_string(), "PATH");
+        assert_eq!(EditorSource::Config { index: 2 }.to_string(), "config #2");
+        assert_eq!(EditorSource::Explicit.to_string(), "explicit");
+    }
 
     #[test]
     fn test_editor_source_equality() {
@@ -330,6 +1156,144 @@ mod tests {
         assert_ne!(EditorSource::Config { index: 0 }, EditorSource::PathSearch);
     }
 
+    #[test]
+    fn test_split_windows_command_quoted_program() {
+        let parts =
+            split_windows_command(r#""C:\Program Files\Microsoft VS Code\bin\code.cmd" --wait"#);
+        assert_eq!(
+            parts,
+            vec![r"C:\Program Files\Microsoft VS Code\bin\code.cmd", "--wait"]
+        );
+
+        let parts = split_windows_command(r"C:\Windows\notepad.exe");
+        assert_eq!(parts, vec![r"C:\Windows\notepad.exe"]);
+    }
+
+    #[test]
+    fn test_split_windows_command_arguments() {
+        let parts = split_windows_command(r#"code  --user-data-dir "C:\My Data\\" -n"#);
+        assert_eq!(parts, vec!["code", "--user-data-dir", r"C:\My Data\", "-n"]);
+
+        let parts = split_windows_command(r#"notepad++ a\"b "" c\d"#);
+        assert_eq!(parts, vec!["notepad++", "a\"b", "", r"c\d"]);
+    }
+
+    #[test]
+    fn test_split_posix_command_quoted_program() {
+        let parts = split_posix_command(r#""/opt/my editor/code" --wait"#);
+        assert_eq!(parts, vec!["/opt/my editor/code", "--wait"]);
+
+        let parts = split_posix_command(r"/opt/my\ editor/code --wait");
+        assert_eq!(parts, vec!["/opt/my editor/code", "--wait"]);
+    }
+
+    #[test]
+    fn test_split_posix_command_arguments() {
+        let parts = split_posix_command(r#"vim  -c 'set spell ft=text' '' a"b\"c"\d"#);
+        assert_eq!(
+            parts,
+            vec!["vim", "-c", "set spell ft=text", "", r#"ab"cd"#]
+        );
+
+        let parts = split_posix_command(r#"code "C:\Users" 'it'\''s' "open"#);
+        assert_eq!(parts, vec!["code", r"C:\Users", "it's", "open"]);
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_try_env_var_quoted_path_with_spaces() {
+        let env = FakeEnvironment::new().with_var("EDITOR", r#""/opt/my editor/code" --wait"#);
+        let editor = try_env_var(&env, "EDITOR").unwrap();
+        assert_eq!(editor.binary, "/opt/my editor/code");
+        assert_eq!(editor.kind, EditorKind::VsCode);
+        assert_eq!(editor.extra_args, vec!["--wait"]);
+
+        let env = FakeEnvironment::new().with_var("VISUAL", "nvim -c 'set spell'");
+        let editor = try_env_var(&env, "VISUAL").unwrap();
+        assert_eq!(editor.extra_args, vec!["-c", "set spell"]);
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_try_env_var_quoted_program_files_path() {
+        let env = FakeEnvironment::new().with_var(
+            "EDITOR",
+            r#""C:\Program Files\Microsoft VS Code\bin\code.cmd" --wait"#,
+        );
+        let editor = try_env_var(&env, "EDITOR").unwrap();
+        assert_eq!(
+            editor.binary,
+            r"C:\Program Files\Microsoft VS Code\bin\code.cmd"
+        );
+        assert_eq!(editor.kind, EditorKind::VsCode);
+        assert_eq!(editor.extra_args, vec!["--wait"]);
+    }
+
+    #[test]
+    fn test_app_bundle_candidates() {
+        assert_eq!(
+            app_bundle_candidates(EditorKind::VsCode, Some("/Users/me")),
+            [
+                PathBuf::from(
+                    "/Applications/Visual Studio Code.app/Contents/Resources/app/bin/code"
+                ),
+                PathBuf::from(
+                    "/Users/me/Applications/Visual Studio Code.app/Contents/Resources/app/bin/code"
+                ),
+            ]
+        );
+        assert_eq!(
+            app_bundle_candidates(EditorKind::Zed, None),
+            [PathBuf::from("/Applications/Zed.app/Contents/MacOS/cli")]
+        );
+        assert_eq!(
+            app_bundle_candidates(EditorKind::Sublime, None),
+            [PathBuf::from(
+                "/Applications/Sublime Text.app/Contents/SharedSupport/bin/subl"
+            )]
+        );
+        assert_eq!(
+            app_bundle_candidates(EditorKind::IntelliJ, Some("/Users/me")).len(),
+            4
+        );
+        assert!(app_bundle_candidates(EditorKind::Vim, Some("/Users/me")).is_empty());
+
+        // Every GUI editor with a bundle CLI puts it inside `<App>.app/Contents/`
+        for &kind in EditorKind::known() {
+            for cli in app_bundle_clis(kind) {
+                assert!(kind.is_gui_editor(), "{kind:?}");
+                assert!(cli.contains(".app/Contents/"), "{kind:?}: {cli}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_in_app_bundle() {
+        let cli = "/Users/me/Applications/Zed.app/Contents/MacOS/cli";
+        let env = FakeEnvironment::new()
+            .with_var("HOME", "/Users/me")
+            .with_binary_at(cli, cli);
+        assert_eq!(
+            find_in_app_bundle(&env, EditorKind::Zed).as_deref(),
+            Some(cli)
+        );
+        assert_eq!(find_in_app_bundle(&env, EditorKind::VsCode), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn test_find_editor_by_kind_in_app_bundle() {
+        let cli = "/Applications/Visual Studio Code.app/Contents/Resources/app/bin/code";
+        let env = FakeEnvironment::new().with_binary_at(cli, cli);
+        let editor = find_editor_by_kind(&env, EditorKind::VsCode).unwrap();
+        assert_eq!(editor.binary, cli);
+        assert_eq!(editor.kind, EditorKind::VsCode);
+
+        let editor = search_path_for_editor(&env).unwrap();
+        assert_eq!(editor.binary, cli);
+        assert_eq!(editor.source, EditorSource::PathSearch);
+    }
+
     #[test]
     fn test_fallback_order() {
         // Verify our fallback list has the expected order
@@ -340,21 +1304,420 @@ mod tests {
 
     #[test]
     fn test_resolve_with_empty_order_fails() {
-        let result = resolve_editor_with_order(&[], &[]);
+        let result = resolve_editor_with_order(&FakeEnvironment::new(), &[], &[], None, true);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_resolve_with_empty_configs_and_config_source() {
         // When Config is in order but no configs provided, should fall through
-        let result = resolve_editor_with_order(&[ResolveFrom::Config], &[]);
+        let result = resolve_editor_with_order(
+            &FakeEnvironment::new(),
+            &[ResolveFrom::Config],
+            &[],
+            None,
+            true,
+        );
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_resolve_git_order_prefers_git_editor() {
+        let env = FakeEnvironment::new()
+            .with_var("GIT_EDITOR", "vim -f")
+            .with_var("EDITOR", "nano")
+            .with_binary("vim")
+            .with_binary("nano");
+        let editor = resolve_editor_with_order(&env, GIT_RESOLVE_ORDER, &[], None, true).unwrap();
+
+        assert_eq!(editor.kind, EditorKind::Vim);
+        assert_eq!(editor.extra_args, vec!["-f"]);
+        assert_eq!(
+            editor.source,
+            EditorSource::Environment("GIT_EDITOR".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_git_order_falls_back_to_editor() {
+        let env = FakeEnvironment::new()
+            .with_var("EDITOR", "nano")
+            .with_binary("nano");
+        let editor = resolve_editor_with_order(&env, GIT_RESOLVE_ORDER, &[], None, true).unwrap();
+        assert_eq!(editor.kind, EditorKind::Nano);
+    }
+
+    #[test]
+    fn test_resolve_git_merge_tool() {
+        let env = FakeEnvironment::new()
+            .with_var("GIT_MERGE_TOOL", "nvim")
+            .with_binary("nvim");
+        let editor =
+            resolve_editor_with_order(&env, &[ResolveFrom::GitMergeTool], &[], None, true).unwrap();
+        assert_eq!(editor.kind, EditorKind::NeoVim);
+        assert_eq!(
+            editor.source,
+            EditorSource::Environment("GIT_MERGE_TOOL".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_alternatives_editor_follows_symlink_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("vim.basic");
+        std::fs::write(&binary, "").unwrap();
+        let vim = dir.path().join("vim");
+        std::os::unix::fs::symlink(&binary, &vim).unwrap();
+        let link = dir.path().join("editor");
+        std::os::unix::fs::symlink(&vim, &link).unwrap();
+
+        let editor = alternatives_editor(&SystemEnvironment, &link).unwrap();
+        let binary = std::fs::canonicalize(binary).unwrap();
+        assert_eq!(editor.binary, binary.to_str().unwrap());
+        assert_eq!(editor.kind, EditorKind::Vim);
+        assert_eq!(editor.source, EditorSource::SystemAlternatives);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_editor_follows_vi_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let nvim = dir.path().join("nvim");
+        std::fs::write(&nvim, "").unwrap();
+        let vi = dir.path().join("vi");
+        std::os::unix::fs::symlink(&nvim, &vi).unwrap();
+        let vim = dir.path().join("vim.basic");
+        std::fs::write(&vim, "").unwrap();
+        let editor = dir.path().join("editor");
+        std::os::unix::fs::symlink(&vim, &editor).unwrap();
+        let gvim = dir.path().join("gvim");
+        std::os::unix::fs::symlink(&vim, &gvim).unwrap();
+        let env = FakeEnvironment::new()
+            .with_binary_at("vi", vi.to_str().unwrap())
+            .with_binary_at("editor", editor.to_str().unwrap())
+            .with_binary_at("gvim", gvim.to_str().unwrap());
+
+        let found = find_editor(&env, "vi").unwrap();
+        assert_eq!(found.binary, "vi");
+        assert_eq!(found.kind, EditorKind::NeoVim);
+        assert_eq!(
+            find_editor_by_kind(&env, EditorKind::Vi).unwrap().kind,
+            EditorKind::NeoVim
+        );
+        assert_eq!(find_editor(&env, "editor").unwrap().kind, EditorKind::Vim);
+
+        // Specific kinds aren't replaced by the editor they link to
+        assert_eq!(find_editor(&env, "gvim").unwrap().kind, EditorKind::GVim);
+    }
+
+    #[test]
+    fn test_find_editor_keeps_kind_of_unresolvable_binary() {
+        let env = FakeEnvironment::new().with_binary_at("vi", "/nonexistent/bin/vi");
+        assert_eq!(find_editor(&env, "vi").unwrap().kind, EditorKind::Vi);
+        assert_eq!(
+            find_editor_by_kind(&env, EditorKind::Vi).unwrap().kind,
+            EditorKind::Vi
+        );
+    }
+
+    #[test]
+    fn test_alternatives_editor_missing_link() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(alternatives_editor(&SystemEnvironment, &dir.path().join("editor")).is_none());
+    }
+
+    #[test]
+    fn test_resolve_system_alternatives_from_environment() {
+        let env = FakeEnvironment::new().with_link(ALTERNATIVES_EDITOR, "/usr/bin/vim.basic");
+        let order = [ResolveFrom::SystemAlternatives];
+        let editor = resolve_editor_with_order(&env, &order, &[], None, true).unwrap();
+        assert_eq!(editor.binary, "/usr/bin/vim.basic");
+        assert_eq!(editor.kind, EditorKind::Vim);
+        assert_eq!(editor.source, EditorSource::SystemAlternatives);
+
+        // No link in the environment means no editor, whatever the host has
+        let env = FakeEnvironment::new();
+        assert!(resolve_editor_with_order(&env, &order, &[], None, true).is_err());
+    }
+
+    #[test]
+    fn test_explain_failure() {
+        let env = FakeEnvironment::new().with_var("EDITOR", "  ");
+        let configs = vec![
+            EditorConfig::with_editor("nvim").with_extension("md", EditorKind::Zed),
+            EditorConfig::default(),
+        ];
+        let order = [
+            ResolveFrom::Config,
+            ResolveFrom::Visual,
+            ResolveFrom::Editor,
+            ResolveFrom::PathSearch,
+        ];
+
+        assert!(resolve_editor_with_order(&env, &order, &configs, Some("md"), true).is_err());
+        assert_eq!(
+            explain_failure(&env, &order, &configs, Some("md")),
+            vec![
+                (
+                    ResolveFrom::Config,
+                    "config 0: 'zed', 'nvim' not found in PATH; config 1 names no editor"
+                        .to_string()
+                ),
+                (ResolveFrom::Visual, "$VISUAL is not set".to_string()),
+                (ResolveFrom::Editor, "$EDITOR is empty".to_string()),
+                (
+                    ResolveFrom::PathSearch,
+                    "no known editor found in PATH".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            explain_failure(&env, &[ResolveFrom::Config], &[], None),
+            vec![(ResolveFrom::Config, "no configs were provided".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_resolve_skips_missing_env_editor() {
+        let env = FakeEnvironment::new()
+            .with_var("VISUAL", "not-an-editor --flag")
+            .with_var("EDITOR", "/nonexistent/vim")
+            .with_binary("nano");
+
+        let editor =
+            resolve_editor_with_order(&env, ENV_ONLY_RESOLVE_ORDER, &[], None, true).unwrap();
+        assert_eq!(editor.binary, "nano");
+        assert_eq!(editor.source, EditorSource::PathSearch);
+        let binaries: Vec<_> =
+            resolve_all_with_order(&env, ENV_ONLY_RESOLVE_ORDER, &[], None, true)
+                .into_iter()
+                .map(|e| e.binary)
+                .collect();
+        assert_eq!(binaries, vec!["nano"]);
+        assert_eq!(detect_editor(&env).unwrap().binary, "nano");
+
+        // Without verification the variable is trusted
+        let editor =
+            resolve_editor_with_order(&env, ENV_ONLY_RESOLVE_ORDER, &[], None, false).unwrap();
+        assert_eq!(editor.binary, "not-an-editor");
+        assert_eq!(
+            editor.source,
+            EditorSource::Environment("VISUAL".to_string())
+        );
+    }
+
+    #[test]
+    fn test_explain_failure_names_missing_env_editor() {
+        let env = FakeEnvironment::new().with_var("EDITOR", "nvim -p");
+        assert!(resolve_editor_with_order(&env, &[ResolveFrom::Editor], &[], None, true).is_err());
+        assert_eq!(
+            explain_failure(&env, &[ResolveFrom::Editor], &[], None),
+            vec![(
+                ResolveFrom::Editor,
+                "$EDITOR names 'nvim', which was not found".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_resolve_all_returns_candidates_in_source_order() {
+        let env = FakeEnvironment::new()
+            .with_var("VISUAL", "zed")
+            .with_var("EDITOR", "nvim")
+            .with_binary("code")
+            .with_binary("hx")
+            .with_binary("nvim")
+            .with_binary("zed");
+        let configs = [
+            EditorConfig::with_editor("hx"),
+            EditorConfig::with_editor("missing"),
+        ];
+        let editors = resolve_all_with_order(&env, DEFAULT_RESOLVE_ORDER, &configs, None, true);
+
+        let binaries: Vec<_> = editors.iter().map(|e| e.binary.as_str()).collect();
+        assert_eq!(binaries, vec!["hx", "zed", "nvim", "code"]);
+        assert_eq!(editors[0].source, EditorSource::Config { index: 0 });
+        assert_eq!(
+            editors[1].source,
+            EditorSource::Environment("VISUAL".to_string())
+        );
+        assert_eq!(editors[3].source, EditorSource::PathSearch);
+
+        // The first candidate is what single resolution picks
+        let first =
+            resolve_editor_with_order(&env, DEFAULT_RESOLVE_ORDER, &configs, None, true).unwrap();
+        assert_eq!(first.binary, editors[0].binary);
+    }
+
+    #[test]
+    fn test_resolve_routes_by_extension() {
+        let env = FakeEnvironment::new()
+            .with_var("EDITOR", "vim")
+            .with_binary("code")
+            .with_binary("zed")
+            .with_binary("vim");
+        let configs = [EditorConfig::with_editor("code")
+            .with_extension("md", EditorKind::Zed)
+            .with_extension("py", EditorKind::PyCharm)];
+        let resolve = |extension| {
+            resolve_editor_with_order(&env, DEFAULT_RESOLVE_ORDER, &configs, extension, true)
+        };
+
+        let markdown = resolve(Some("md")).unwrap();
+        assert_eq!(markdown.kind, EditorKind::Zed);
+        assert_eq!(markdown.source, EditorSource::Config { index: 0 });
+
+        // Unmapped extensions, missing mapped editors, and no file use the config's editor
+        assert_eq!(resolve(Some("rs")).unwrap().kind, EditorKind::VsCode);
+        assert_eq!(resolve(Some("py")).unwrap().kind, EditorKind::VsCode);
+        assert_eq!(resolve(None).unwrap().kind, EditorKind::VsCode);
+    }
+
+    #[test]
+    fn test_resolve_all_respects_order_and_empty() {
+        let env = FakeEnvironment::new()
+            .with_var("EDITOR", "vim")
+            .with_binary("vim")
+            .with_binary("nano");
+
+        let editors = resolve_all_with_order(
+            &env,
+            &[ResolveFrom::PathSearch, ResolveFrom::Editor],
+            &[],
+            None,
+            true,
+        );
+        let binaries: Vec<_> = editors.iter().map(|e| e.binary.as_str()).collect();
+        assert_eq!(binaries, vec!["vim", "nano"]);
+        assert_eq!(editors[0].source, EditorSource::PathSearch);
+
+        assert!(resolve_all_with_order(&env, &[], &[], None, true).is_empty());
+    }
+
+    #[test]
+    fn test_candidates_from_env_and_path() {
+        let env = FakeEnvironment::new()
+            .with_var("VISUAL", "code --wait")
+            .with_var("EDITOR", "nvim")
+            .with_binary("code")
+            .with_binary("nvim")
+            .with_binary("vim")
+            .with_binary("nano");
+        let candidates = candidates(&env);
+
+        let summary: Vec<_> = candidates
+            .iter()
+            .map(|c| (c.binary.as_str(), c.source.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("code", EditorSource::Environment("VISUAL".to_string())),
+                ("nvim", EditorSource::Environment("EDITOR".to_string())),
+                ("vim", EditorSource::PathSearch),
+                ("nano", EditorSource::PathSearch),
+            ]
+        );
+        assert_eq!(candidates[0].extra_args, vec!["--wait"]);
+    }
+
+    #[test]
+    fn test_detect_all_in_preference_order() {
+        let env = FakeEnvironment::new()
+            .with_var("EDITOR", "micro")
+            .with_binary("micro")
+            .with_binary("nano")
+            .with_binary("vi")
+            .with_binary("hx")
+            .with_binary("zed")
+            .with_binary("code");
+
+        let editors = detect_all(&env);
+        let summary: Vec<_> = editors
+            .iter()
+            .map(|e| (e.binary.as_str(), e.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("code", EditorKind::VsCode),
+                ("zed", EditorKind::Zed),
+                ("hx", EditorKind::Helix),
+                ("nano", EditorKind::Nano),
+                ("vi", EditorKind::Vi),
+            ]
+        );
+        assert!(editors.iter().all(|e| e.source == EditorSource::PathSearch));
+        assert!(detect_all(&FakeEnvironment::new()).is_empty());
+    }
+
+    #[test]
+    fn test_candidates_empty_environment() {
+        assert!(candidates(&FakeEnvironment::new()).is_empty());
+    }
+
+    #[test]
+    fn test_expand_config_value() {
+        let env = FakeEnvironment::new()
+            .with_var("HOME", "/home/me")
+            .with_var("EDITOR_DIR", "/opt/editors");
+        let expand = |value| expand_config_value(&env, value);
+
+        assert_eq!(expand("~/bin/my-code"), "/home/me/bin/my-code");
+        assert_eq!(expand("~"), "/home/me");
+        assert_eq!(expand("$HOME/bin/code"), "/home/me/bin/code");
+        assert_eq!(
+            expand("${EDITOR_DIR}/code-${HOME}"),
+            "/opt/editors/code-/home/me"
+        );
+        assert_eq!(
+            expand("--user-data-dir=$HOME/.vscode-alt"),
+            "--user-data-dir=/home/me/.vscode-alt"
+        );
+
+        // Left alone
+        assert_eq!(expand("nvim"), "nvim");
+        assert_eq!(expand("~user/bin/code"), "~user/bin/code");
+        assert_eq!(expand("a~/b"), "a~/b");
+        assert_eq!(expand("$UNSET/code"), "$UNSET/code");
+        assert_eq!(expand("${HOME"), "${HOME");
+        assert_eq!(expand("$1 and $"), "$1 and $");
+        assert_eq!(
+            expand_config_value(&FakeEnvironment::new(), "~/bin/code"),
+            "~/bin/code"
+        );
+    }
+
+    #[test]
+    fn test_try_config_expands_editor_and_args() {
+        let env = FakeEnvironment::new()
+            .with_var("HOME", "/home/me")
+            .with_binary_at("/home/me/bin/my-code", "/home/me/bin/my-code");
+        let config = EditorConfig {
+            editor: Some("~/bin/my-code".to_string()),
+            args: vec!["--profile=$HOME/.profile".to_string()],
+            ..Default::default()
+        };
+
+        let editor = try_config(&env, &config, 0, None).unwrap();
+        assert_eq!(editor.binary, "/home/me/bin/my-code");
+        assert_eq!(editor.extra_args, vec!["--profile=/home/me/.profile"]);
+
+        let config = EditorConfig {
+            editor: Some("$HOME/bin/my-code".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            try_config(&env, &config, 0, None).unwrap().binary,
+            "/home/me/bin/my-code"
+        );
+    }
+
     #[test]
     fn test_try_config_with_empty_config() {
         let config = EditorConfig::default();
-        let result = try_config(&config, 0);
+        let result = try_config(&FakeEnvironment::new(), &config, 0, None);
         assert!(result.is_none());
     }
 
@@ -368,3 +1731,83 @@ mod tests {
         assert!(!ENV_ONLY_RESOLVE_ORDER.contains(&ResolveFrom::Config));
     }
 }
+
+#[cfg(all(test, feature = "tracing"))]
+mod tracing_tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use super::*;
+    use crate::env::FakeEnvironment;
+
+    /// Records each event as its fields, `name=value` separated by spaces.
+    #[derive(Default)]
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            use std::fmt::Write;
+
+            if !self.0.is_empty() {
+                self.0.push(' ');
+            }
+            write!(self.0, "{}={value:?}", field.name()).unwrap();
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(String::new());
+            event.record(&mut fields);
+            self.0.lock().unwrap().push(fields.0);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_path_resolution_emits_events() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = Capture(Arc::clone(&events));
+        let env = FakeEnvironment::new().with_binary("vim");
+
+        let editor = tracing::subscriber::with_default(subscriber, || {
+            resolve_editor_with_order(&env, &[ResolveFrom::PathSearch], &[], None, true).unwrap()
+        });
+        assert_eq!(editor.binary, "vim");
+
+        let events = events.lock().unwrap();
+        assert!(
+            events
+                .contains(&"message=searched PATH for editor binary=code found=false".to_string()),
+            "{events:?}"
+        );
+        assert!(
+            events.contains(&"message=searched PATH for editor binary=vim found=true".to_string()),
+            "{events:?}"
+        );
+        assert!(
+            events.contains(&"message=resolved editor binary=vim kind=Vim source=PATH".to_string()),
+            "{events:?}"
+        );
+    }
+}
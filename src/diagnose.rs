@@ -0,0 +1,226 @@
+//! Editor configuration diagnostics.
+//!
+//! This module inspects every detection source without spawning anything,
+//! so applications can implement a "doctor" command that explains which
+//! editor would be used and why.
+
+use std::fmt;
+
+use crate::detect::{search_path_for_editor, try_env_var};
+use crate::env::Environment;
+use crate::kind::EditorKind;
+
+/// What a single detection source resolves to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceReport {
+    /// The binary named by the source.
+    pub binary: String,
+    /// Arguments that follow the binary (e.g., `--wait` from `code --wait`).
+    pub args: Vec<String>,
+    /// The editor kind detected from the binary name.
+    pub kind: EditorKind,
+    /// Whether the binary could be found in `PATH` (or at the given path).
+    pub found: bool,
+}
+
+/// A read-only report of what each editor source resolves to.
+///
+/// Returned by [`Editor::diagnose()`](crate::Editor::diagnose).
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::Editor;
+///
+/// let diagnostics = Editor::diagnose();
+/// for warning in diagnostics.warnings() {
+///     eprintln!("warning: {warning}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorDiagnostics {
+    /// What `$VISUAL` resolves to, if set.
+    pub visual: Option<SourceReport>,
+    /// What `$EDITOR` resolves to, if set.
+    pub editor: Option<SourceReport>,
+    /// The first known editor found by searching `PATH`, if any.
+    pub path_search: Option<SourceReport>,
+}
+
+impl EditorDiagnostics {
+    /// Collects diagnostics from the given environment.
+    pub(crate) fn collect(env: &dyn Environment) -> Self {
+        let report_var = |var: &str| {
+            try_env_var(env, var).map(|detected| SourceReport {
+                found: env.which(&detected.binary).is_some(),
+                binary: detected.binary,
+                args: detected.extra_args,
+                kind: detected.kind,
+            })
+        };
+
+        Self {
+            visual: report_var("VISUAL"),
+            editor: report_var("EDITOR"),
+            path_search: search_path_for_editor(env).map(|detected| SourceReport {
+                binary: detected.binary,
+                args: detected.extra_args,
+                kind: detected.kind,
+                found: true,
+            }),
+        }
+    }
+
+    /// Returns `true` if `$VISUAL` and `$EDITOR` are both set but name
+    /// different binaries.
+    pub fn is_conflicting(&self) -> bool {
+        match (&self.visual, &self.editor) {
+            (Some(visual), Some(editor)) => visual.binary != editor.binary,
+            _ => false,
+        }
+    }
+
+    /// Returns the binaries named by environment variables that could not
+    /// be found.
+    pub fn missing_binaries(&self) -> Vec<&str> {
+        [&self.visual, &self.editor]
+            .into_iter()
+            .flatten()
+            .filter(|report| !report.found)
+            .map(|report| report.binary.as_str())
+            .collect()
+    }
+
+    /// Returns `true` if no source resolves to a usable editor.
+    pub fn is_empty(&self) -> bool {
+        [&self.visual, &self.editor, &self.path_search]
+            .into_iter()
+            .flatten()
+            .all(|report| !report.found)
+    }
+
+    /// Returns human-readable warnings about the current configuration.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        for (var, report) in [("VISUAL", &self.visual), ("EDITOR", &self.editor)] {
+            if let Some(report) = report {
+                if !report.found {
+                    warnings.push(format!(
+                        "${var} is set to '{}', which is not installed or not in PATH",
+                        report.binary
+                    ));
+                }
+            }
+        }
+
+        if let (true, Some(visual), Some(editor)) =
+            (self.is_conflicting(), &self.visual, &self.editor)
+        {
+            warnings.push(format!(
+                "$VISUAL ('{}') and $EDITOR ('{}') point at different editors; \
+                 $VISUAL takes precedence",
+                visual.binary, editor.binary
+            ));
+        }
+
+        if self.is_empty() {
+            warnings.push(
+                "no editor found: set $VISUAL or $EDITOR, or install a supported editor"
+                    .to_string(),
+            );
+        }
+
+        warnings
+    }
+}
+
+impl fmt::Display for EditorDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (label, report) in [
+            ("$VISUAL", &self.visual),
+            ("$EDITOR", &self.editor),
+            ("PATH", &self.path_search),
+        ] {
+            match report {
+                Some(report) => {
+                    let status = if report.found { "found" } else { "missing" };
+                    writeln!(f, "{label}: {} ({}, {status})", report.binary, report.kind)?;
+                }
+                None => writeln!(f, "{label}: not set")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::FakeEnvironment;
+
+    #[test]
+    fn test_diagnose_reports_env_vars() {
+        let env = FakeEnvironment::new()
+            .with_var("VISUAL", "code --wait")
+            .with_var("EDITOR", "nvim")
+            .with_binary("code")
+            .with_binary("nvim");
+        let diagnostics = EditorDiagnostics::collect(&env);
+
+        let visual = diagnostics.visual.as_ref().unwrap();
+        assert_eq!(visual.binary, "code");
+        assert_eq!(visual.args, vec!["--wait"]);
+        assert_eq!(visual.kind, EditorKind::VsCode);
+        assert!(visual.found);
+
+        let editor = diagnostics.editor.as_ref().unwrap();
+        assert_eq!(editor.kind, EditorKind::NeoVim);
+        assert!(editor.found);
+
+        assert_eq!(diagnostics.path_search.as_ref().unwrap().binary, "code");
+        assert!(diagnostics.is_conflicting());
+        assert!(diagnostics.missing_binaries().is_empty());
+    }
+
+    #[test]
+    fn test_diagnose_flags_missing_binary() {
+        let env = FakeEnvironment::new()
+            .with_var("EDITOR", "not-an-editor")
+            .with_binary("vim");
+        let diagnostics = EditorDiagnostics::collect(&env);
+
+        assert!(diagnostics.visual.is_none());
+        assert!(!diagnostics.is_conflicting());
+        assert_eq!(diagnostics.missing_binaries(), vec!["not-an-editor"]);
+        assert!(!diagnostics.is_empty());
+
+        let warnings = diagnostics.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("$EDITOR"));
+    }
+
+    #[test]
+    fn test_diagnose_same_editor_is_not_conflicting() {
+        let env = FakeEnvironment::new()
+            .with_var("VISUAL", "vim")
+            .with_var("EDITOR", "vim")
+            .with_binary("vim");
+        let diagnostics = EditorDiagnostics::collect(&env);
+
+        assert!(!diagnostics.is_conflicting());
+        assert!(diagnostics.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_diagnose_nothing_configured() {
+        let diagnostics = EditorDiagnostics::collect(&FakeEnvironment::new());
+
+        assert!(diagnostics.visual.is_none());
+        assert!(diagnostics.editor.is_none());
+        assert!(diagnostics.path_search.is_none());
+        assert!(diagnostics.is_empty());
+        assert!(diagnostics.warnings()[0].contains("no editor found"));
+        assert!(diagnostics.to_string().contains("$VISUAL: not set"));
+    }
+}
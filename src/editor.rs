@@ -3,431 +3,32 @@
 //! This module provides the main `Editor` type and `EditorBuilder` for
 //! opening files in text editors.
 
+use std::ffi::{OsStr, OsString};
+use std::io::{BufRead, IsTerminal};
 use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
 
-use crate::command::build_command;
-use crate::config::{EditorConfig, ResolveFrom, DEFAULT_RESOLVE_ORDER, ENV_ONLY_RESOLVE_ORDER};
+use tempfile::TempDir;
+
+use crate::args::{ArgOptions, build_headless_args};
+use crate::command::{
+    LaunchOptions, activate_args, build_command, is_tty_emacsclient, posix_login_shell,
+};
+use crate::config::{DEFAULT_RESOLVE_ORDER, ENV_ONLY_RESOLVE_ORDER, EditorConfig, ResolveFrom};
 use crate::detect::{
-    detect_editor, find_editor, find_editor_by_kind, resolve_editor_with_order, DetectedEditor,
+    DetectedEditor, EditorSource, candidates, detect_all, detect_editor, explain_failure,
+    find_editor, find_editor_by_kind, resolve_all_with_order, resolve_editor_with_order,
 };
-use crate::error::{Error, Result};
-
-/// Known text editor types.
-///
-/// This enum represents all the text editors that opensesame knows how to
-/// invoke with proper line:column positioning.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[non_exhaustive]
-pub enum EditorKind {
-    // VS Code family
-    /// Visual Studio Code
-    VsCode,
-    /// VS Code Insiders
-    VsCodeInsiders,
-    /// VSCodium (open source VS Code)
-    VSCodium,
-    /// Cursor (AI-powered VS Code fork)
-    Cursor,
-    /// Windsurf (Codeium's editor)
-    Windsurf,
-
-    // Vim family
-    /// Vim
-    Vim,
-    /// NeoVim
-    NeoVim,
-    /// Vi
-    Vi,
-    /// GVim (graphical Vim)
-    GVim,
-
-    // Emacs family
-    /// GNU Emacs
-    Emacs,
-    /// Emacs Client
-    EmacsClient,
-
-    // Modern GUI editors
-    /// Sublime Text
-    Sublime,
-    /// Zed
-    Zed,
-    /// Helix
-    Helix,
-    /// Atom (deprecated but still used)
-    Atom,
-    /// Kate (KDE)
-    Kate,
-
-    // Terminal editors
-    /// GNU Nano
-    Nano,
-
-    // macOS editors
-    /// TextMate
-    TextMate,
-    /// Xcode
-    Xcode,
-
-    // Windows editors
-    /// Notepad++ (Windows)
-    NotepadPlusPlus,
-    /// Notepad (Windows, no line support)
-    Notepad,
-
-    // JetBrains family
-    /// IntelliJ IDEA
-    IntelliJ,
-    /// WebStorm
-    WebStorm,
-    /// PhpStorm
-    PhpStorm,
-    /// PyCharm
-    PyCharm,
-    /// RubyMine
-    RubyMine,
-    /// GoLand
-    GoLand,
-    /// CLion
-    CLion,
-    /// Rider
-    Rider,
-    /// DataGrip
-    DataGrip,
-    /// Android Studio
-    AndroidStudio,
-
-    /// Unknown editor (will just pass file path)
-    Unknown,
-}
-
-impl EditorKind {
-    /// Parses an `EditorKind` from its string name.
-    ///
-    /// Accepts names like "VsCode", "NeoVim", "Vim", etc. The matching
-    /// is case-insensitive and supports common variations.
-    ///
-    /// Returns `None` for unrecognized names.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use opensesame::EditorKind;
-    ///
-    /// assert_eq!(EditorKind::from_name("NeoVim"), Some(EditorKind::NeoVim));
-    /// assert_eq!(EditorKind::from_name("vscode"), Some(EditorKind::VsCode));
-    /// assert_eq!(EditorKind::from_name("unknown"), None);
-    /// ```
-    pub fn from_name(name: &str) -> Option<Self> {
-        // Normalize: lowercase and remove common separators
-        let normalized = name.to_lowercase().replace(['-', '_'], "");
-
-        match normalized.as_str() {
-            // VS Code family
-            "vscode" | "visualstudiocode" | "code" => Some(Self::VsCode),
-            "vscodeinsiders" | "codeinsiders" => Some(Self::VsCodeInsiders),
-            "vscodium" | "codium" => Some(Self::VSCodium),
-            "cursor" => Some(Self::Cursor),
-            "windsurf" => Some(Self::Windsurf),
-
-            // Vim family
-            "vim" => Some(Self::Vim),
-            "neovim" | "nvim" => Some(Self::NeoVim),
-            "vi" => Some(Self::Vi),
-            "gvim" | "mvim" => Some(Self::GVim),
-
-            // Emacs family
-            "emacs" | "gnuemacs" | "xemacs" => Some(Self::Emacs),
-            "emacsclient" => Some(Self::EmacsClient),
-
-            // Modern GUI editors
-            "sublime" | "sublimetext" | "subl" => Some(Self::Sublime),
-            "zed" => Some(Self::Zed),
-            "helix" | "hx" => Some(Self::Helix),
-            "atom" => Some(Self::Atom),
-            "kate" => Some(Self::Kate),
-
-            // Terminal editors
-            "nano" => Some(Self::Nano),
-
-            // macOS editors
-            "textmate" | "mate" => Some(Self::TextMate),
-            "xcode" | "xed" => Some(Self::Xcode),
-
-            // Windows editors
-            "notepadplusplus" | "notepad++" | "npp" => Some(Self::NotepadPlusPlus),
-            "notepad" => Some(Self::Notepad),
-
-            // JetBrains family
-            "intellij" | "intellijidea" | "idea" => Some(Self::IntelliJ),
-            "webstorm" => Some(Self::WebStorm),
-            "phpstorm" | "pstorm" => Some(Self::PhpStorm),
-            "pycharm" | "charm" => Some(Self::PyCharm),
-            "rubymine" | "mine" => Some(Self::RubyMine),
-            "goland" => Some(Self::GoLand),
-            "clion" => Some(Self::CLion),
-            "rider" => Some(Self::Rider),
-            "datagrip" => Some(Self::DataGrip),
-            "androidstudio" | "studio" => Some(Self::AndroidStudio),
-
-            _ => None,
-        }
-    }
-
-    /// Returns the canonical string name for this editor kind.
-    ///
-    /// This is the preferred name for display and serialization.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use opensesame::EditorKind;
-    ///
-    /// assert_eq!(EditorKind::NeoVim.as_str(), "NeoVim");
-    /// assert_eq!(EditorKind::VsCode.as_str(), "VsCode");
-    /// ```
-    pub const fn as_str(&self) -> &'static str {
-        match self {
-            Self::VsCode => "VsCode",
-            Self::VsCodeInsiders => "VsCodeInsiders",
-            Self::VSCodium => "VSCodium",
-            Self::Cursor => "Cursor",
-            Self::Windsurf => "Windsurf",
-            Self::Vim => "Vim",
-            Self::NeoVim => "NeoVim",
-            Self::Vi => "Vi",
-            Self::GVim => "GVim",
-            Self::Emacs => "Emacs",
-            Self::EmacsClient => "EmacsClient",
-            Self::Sublime => "Sublime",
-            Self::Zed => "Zed",
-            Self::Helix => "Helix",
-            Self::Atom => "Atom",
-            Self::Kate => "Kate",
-            Self::Nano => "Nano",
-            Self::TextMate => "TextMate",
-            Self::Xcode => "Xcode",
-            Self::NotepadPlusPlus => "NotepadPlusPlus",
-            Self::Notepad => "Notepad",
-            Self::IntelliJ => "IntelliJ",
-            Self::WebStorm => "WebStorm",
-            Self::PhpStorm => "PhpStorm",
-            Self::PyCharm => "PyCharm",
-            Self::RubyMine => "RubyMine",
-            Self::GoLand => "GoLand",
-            Self::CLion => "CLion",
-            Self::Rider => "Rider",
-            Self::DataGrip => "DataGrip",
-            Self::AndroidStudio => "AndroidStudio",
-            Self::Unknown => "Unknown",
-        }
-    }
-
-    /// Detects the editor kind from a binary name.
-    ///
-    /// This handles both bare binary names (`vim`) and full paths
-    /// (`/usr/bin/vim`), extracting just the filename for comparison.
-    pub fn from_binary(binary: &str) -> Self {
-        let name = Path::new(binary)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(binary)
-            .to_lowercase();
-
-        // Strip common extensions on Windows
-        let name = name
-            .strip_suffix(".exe")
-            .or_else(|| name.strip_suffix(".cmd"))
-            .or_else(|| name.strip_suffix(".bat"))
-            .unwrap_or(&name);
-
-        match name {
-            // VS Code family
-            "code" | "vscode" => Self::VsCode,
-            "code-insiders" => Self::VsCodeInsiders,
-            "codium" | "vscodium" | "code-oss" => Self::VSCodium,
-            "cursor" => Self::Cursor,
-            "windsurf" => Self::Windsurf,
-
-            // Vim family
-            "vim" => Self::Vim,
-            "nvim" | "neovim" => Self::NeoVim,
-            "vi" => Self::Vi,
-            "gvim" | "mvim" => Self::GVim,
-
-            // Emacs family
-            "emacs" | "xemacs" => Self::Emacs,
-            "emacsclient" => Self::EmacsClient,
-
-            // Modern GUI editors
-            "subl" | "sublime" | "sublime_text" => Self::Sublime,
-            "zed" => Self::Zed,
-            "hx" | "helix" => Self::Helix,
-            "atom" => Self::Atom,
-            "kate" => Self::Kate,
-
-            // Terminal editors
-            "nano" => Self::Nano,
-
-            // macOS editors
-            "mate" | "textmate" => Self::TextMate,
-            "xed" | "xcode" => Self::Xcode,
-
-            // Windows editors
-            "notepad++" => Self::NotepadPlusPlus,
-            "notepad" => Self::Notepad,
-
-            // JetBrains family
-            "idea" | "intellij" | "idea64" => Self::IntelliJ,
-            "webstorm" | "webstorm64" => Self::WebStorm,
-            "pstorm" | "phpstorm" | "phpstorm64" => Self::PhpStorm,
-            "pycharm" | "pycharm64" | "charm" => Self::PyCharm,
-            "rubymine" | "mine" => Self::RubyMine,
-            "goland" | "goland64" => Self::GoLand,
-            "clion" | "clion64" => Self::CLion,
-            "rider" | "rider64" => Self::Rider,
-            "datagrip" | "datagrip64" => Self::DataGrip,
-            "studio" | "studio64" | "android-studio" => Self::AndroidStudio,
-
-            _ => Self::Unknown,
-        }
-    }
-
-    /// Returns the default binary name for this editor kind.
-    pub const fn default_binary(&self) -> &'static str {
-        match self {
-            Self::VsCode => "code",
-            Self::VsCodeInsiders => "code-insiders",
-            Self::VSCodium => "codium",
-            Self::Cursor => "cursor",
-            Self::Windsurf => "windsurf",
-            Self::Vim => "vim",
-            Self::NeoVim => "nvim",
-            Self::Vi => "vi",
-            Self::GVim => "gvim",
-            Self::Emacs => "emacs",
-            Self::EmacsClient => "emacsclient",
-            Self::Sublime => "subl",
-            Self::Zed => "zed",
-            Self::Helix => "hx",
-            Self::Atom => "atom",
-            Self::Kate => "kate",
-            Self::Nano => "nano",
-            Self::TextMate => "mate",
-            Self::Xcode => "xed",
-            Self::NotepadPlusPlus => "notepad++",
-            Self::Notepad => "notepad",
-            Self::IntelliJ => "idea",
-            Self::WebStorm => "webstorm",
-            Self::PhpStorm => "pstorm",
-            Self::PyCharm => "pycharm",
-            Self::RubyMine => "rubymine",
-            Self::GoLand => "goland",
-            Self::CLion => "clion",
-            Self::Rider => "rider",
-            Self::DataGrip => "datagrip",
-            Self::AndroidStudio => "studio",
-            Self::Unknown => "unknown",
-        }
-    }
-
-    /// Returns `true` if this editor runs in the terminal (requires TTY).
-    pub const fn is_terminal_editor(&self) -> bool {
-        matches!(self, Self::Vim | Self::NeoVim | Self::Vi | Self::Nano | Self::Emacs | Self::Helix)
-    }
-
-    /// Returns `true` if this editor supports column positioning.
-    pub const fn supports_column(&self) -> bool {
-        matches!(
-            self,
-            Self::VsCode
-                | Self::VsCodeInsiders
-                | Self::VSCodium
-                | Self::Cursor
-                | Self::Windsurf
-                | Self::Vim
-                | Self::NeoVim
-                | Self::Vi
-                | Self::GVim
-                | Self::Emacs
-                | Self::EmacsClient
-                | Self::Sublime
-                | Self::Zed
-                | Self::Helix
-                | Self::Atom
-                | Self::Kate
-                | Self::Nano
-                | Self::NotepadPlusPlus
-        )
-    }
-
-    /// Returns `true` if this editor supports the `--wait` flag.
-    pub const fn supports_wait(&self) -> bool {
-        matches!(
-            self,
-            Self::VsCode
-                | Self::VsCodeInsiders
-                | Self::VSCodium
-                | Self::Cursor
-                | Self::Windsurf
-                | Self::Sublime
-                | Self::Zed
-                | Self::Atom
-                | Self::TextMate
-                | Self::Xcode
-                | Self::IntelliJ
-                | Self::WebStorm
-                | Self::PhpStorm
-                | Self::PyCharm
-                | Self::RubyMine
-                | Self::GoLand
-                | Self::CLion
-                | Self::Rider
-                | Self::DataGrip
-                | Self::AndroidStudio
-        )
-    }
-}
-
-impl std::fmt::Display for EditorKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let name = match self {
-            Self::VsCode => "VS Code",
-            Self::VsCodeInsiders => "VS Code Insiders",
-            Self::VSCodium => "VSCodium",
-            Self::Cursor => "Cursor",
-            Self::Windsurf => "Windsurf",
-            Self::Vim => "Vim",
-            Self::NeoVim => "NeoVim",
-            Self::Vi => "Vi",
-            Self::GVim => "GVim",
-            Self::Emacs => "Emacs",
-            Self::EmacsClient => "Emacs Client",
-            Self::Sublime => "Sublime Text",
-            Self::Zed => "Zed",
-            Self::Helix => "Helix",
-            Self::Atom => "Atom",
-            Self::Kate => "Kate",
-            Self::Nano => "Nano",
-            Self::TextMate => "TextMate",
-            Self::Xcode => "Xcode",
-            Self::NotepadPlusPlus => "Notepad++",
-            Self::Notepad => "Notepad",
-            Self::IntelliJ => "IntelliJ IDEA",
-            Self::WebStorm => "WebStorm",
-            Self::PhpStorm => "PhpStorm",
-            Self::PyCharm => "PyCharm",
-            Self::RubyMine => "RubyMine",
-            Self::GoLand => "GoLand",
-            Self::CLion => "CLion",
-            Self::Rider => "Rider",
-            Self::DataGrip => "DataGrip",
-            Self::AndroidStudio => "Android Studio",
-            Self::Unknown => "Unknown Editor",
-        };
-        write!(f, "{name}")
-    }
-}
+use crate::diagnose::EditorDiagnostics;
+use crate::env::{Environment, SearchDirs, SystemEnvironment};
+use crate::error::{Error, ResolutionFailure, Result};
+use crate::fragment::LineRange;
+use crate::kind::{EditorFamily, EditorKind, takes_plus_line};
+use crate::options::{
+    ColumnSemantics, IndexBase, MultiFilePolicy, ScrollPosition, Split, TerminalLauncher,
+    VimPositionStyle, WindowMode,
+};
+use crate::remote::RemoteFile;
 
 /// Main entry point for opening files in editors.
 ///
@@ -470,6 +71,7 @@ impl Editor {
     ///     .open()?;
     /// # Ok::<(), opensesame::Error>(())
     /// ```
+    #[must_use]
     pub fn builder() -> EditorBuilder {
         EditorBuilder::new()
     }
@@ -491,6 +93,23 @@ impl Editor {
         Self::builder().file(file).open()
     }
 
+    /// Opens several files in the default editor.
+    ///
+    /// Editors that accept several files open them in one invocation, e.g.
+    /// as tabs or Vim buffers (see [`EditorBuilder::files()`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// Editor::open_all(["src/main.rs", "src/lib.rs"])?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn open_all<P: AsRef<Path>>(files: impl IntoIterator<Item = P>) -> Result<()> {
+        Self::builder().files(files).open()
+    }
+
     /// Opens a file at a specific line number.
     ///
     /// The line number is 1-indexed (first line is 1).
@@ -525,9 +144,183 @@ impl Editor {
         Self::builder().file(file).line(line).column(column).open()
     }
 
+    /// Opens a folder as a workspace in the default editor.
+    ///
+    /// The equivalent of `code .` or `zed ~/proj` (see
+    /// [`EditorBuilder::folder()`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// Editor::open_folder(".")?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn open_folder(folder: impl AsRef<Path>) -> Result<()> {
+        Self::builder().folder(folder).open()
+    }
+
+    /// Opens a file at the line after the first line containing `anchor`.
+    ///
+    /// Suited to documentation tooling that marks regions with comments
+    /// like `// ANCHOR: setup`: the editor opens on the first line of the
+    /// region. The anchor is matched as a plain substring.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::AnchorNotFound` if no line contains `anchor`, and
+    /// `Error::FileNotFound` if the file doesn't exist, in addition to the
+    /// errors returned by [`EditorBuilder::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// Editor::open_at_anchor("src/lib.rs", "ANCHOR: setup")?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn open_at_anchor(file: impl AsRef<Path>, anchor: &str) -> Result<()> {
+        let file = file.as_ref();
+        let line = anchor_line(file, anchor)?;
+        Self::open_at(file, line.saturating_add(1))
+    }
+
+    /// Opens a file at its first git conflict marker (`<<<<<<<`).
+    ///
+    /// Handy when resolving a merge: the editor opens on the first
+    /// unresolved conflict.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConflictNotFound` if the file has no conflict
+    /// markers, and `Error::FileNotFound` if the file doesn't exist, in
+    /// addition to the errors returned by [`EditorBuilder::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// Editor::open_at_conflict("src/lib.rs")?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn open_at_conflict(file: impl AsRef<Path>) -> Result<()> {
+        let file = file.as_ref();
+        let line = conflict_line(file)?;
+        Self::open_at(file, line)
+    }
+
+    /// Runs ex commands on a file in a headless editor, then saves and quits.
+    ///
+    /// Only Vim and NeoVim are supported; they run in silent ex mode as
+    /// `vim -es -c <command>... -c wq <file>`, with stdin closed. Vim accepts
+    /// at most nine commands here.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HeadlessUnsupported`] for other editors,
+    /// [`Error::InvalidConfig`] for too many commands, and
+    /// [`Error::EditorFailed`] if any command fails (ex mode exits non-zero).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::{Editor, EditorKind};
+    ///
+    /// Editor::run_headless("src/main.rs", EditorKind::Vim, &["%s/foo/bar/g"])?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn run_headless(file: impl AsRef<Path>, kind: EditorKind, commands: &[&str]) -> Result<()> {
+        if !kind.supports_headless() {
+            return Err(Error::HeadlessUnsupported { editor: kind });
+        }
+        let args = build_headless_args(kind, &file.as_ref().display().to_string(), commands)
+            .ok_or_else(|| Error::InvalidConfig {
+                message: format!(
+                    "{kind} accepts at most 9 headless commands, got {}",
+                    commands.len()
+                ),
+            })?;
+
+        let editor = find_editor_by_kind(&SystemEnvironment, kind)?;
+        let status = std::process::Command::new(&editor.binary)
+            .args(&args)
+            .stdin(std::process::Stdio::null())
+            .status()
+            .map_err(|e| Error::SpawnFailed {
+                binary: editor.binary.clone(),
+                source: e,
+            })?;
+        check_status(editor.binary, status)
+    }
+
+    /// Opens a file and blocks until the editor is closed.
+    ///
+    /// This is intended for commit-message-style flows where the caller
+    /// only continues once the user is done editing. GUI editors get their
+    /// wait flag (e.g., `code --wait`) and terminal editors block naturally.
+    ///
+    /// Editors of unknown kind are assumed to block, since `$EDITOR` values
+    /// that opensesame doesn't recognize are usually terminal editors.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::WaitUnsupported` if the resolved editor can neither
+    /// wait nor block (e.g., GVim, which forks), in addition to the errors returned by
+    /// [`EditorBuilder::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// Editor::edit_and_wait(".git/COMMIT_EDITMSG")?;
+    /// // The user has closed the editor; read the message back.
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn edit_and_wait(file: impl AsRef<Path>) -> Result<()> {
+        Self::edit_and_wait_builder(file).open()
+    }
+
+    /// Opens the first of a list of `(path, line, column)` diagnostics.
+    ///
+    /// Convenience for build tools that collect compiler errors; see
+    /// [`EditorBuilder::open_first_diagnostic()`] for configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// let diagnostics = [("src/main.rs", 42, 7), ("src/lib.rs", 10, 1)];
+    /// Editor::open_first_diagnostic(&diagnostics)?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn open_first_diagnostic<P: AsRef<Path>>(diagnostics: &[(P, u32, u32)]) -> Result<()> {
+        Self::builder().open_first_diagnostic(diagnostics)
+    }
+
+    /// Opens every file in a list of `(path, line, column)` diagnostics.
+    ///
+    /// GUI editors get one tab per file, with the first diagnostic active;
+    /// terminal editors only open the first diagnostic. See
+    /// [`EditorBuilder::open_all_diagnostics()`].
+    pub fn open_all_diagnostics<P: AsRef<Path>>(diagnostics: &[(P, u32, u32)]) -> Result<()> {
+        Self::builder().open_all_diagnostics(diagnostics)
+    }
+
+    /// Returns the builder used by [`edit_and_wait()`](Self::edit_and_wait).
+    fn edit_and_wait_builder(file: impl AsRef<Path>) -> EditorBuilder {
+        Self::builder().file(file).wait(true).strict_wait(true)
+    }
+
     /// Detects the default editor without opening anything.
     ///
-    /// Useful for checking which editor would be used.
+    /// Useful for checking which editor would be used. With the `cache`
+    /// feature enabled, the result is cached on disk (see
+    /// `EditorCache`).
     ///
     /// # Example
     ///
@@ -539,9 +332,74 @@ impl Editor {
     /// # Ok::<(), opensesame::Error>(())
     /// ```
     pub fn detect() -> Result<EditorKind> {
-        let detected = detect_editor()?;
+        #[cfg(feature = "cache")]
+        if let Some(cache) = crate::cache::EditorCache::at_default_location() {
+            return cache.detect().map(|detected| detected.kind);
+        }
+
+        let detected = detect_editor(&SystemEnvironment)?;
         Ok(detected.kind)
     }
+
+    /// Lists every editor the user could choose, tagged with its source.
+    ///
+    /// Gathers the editors named by `$VISUAL` and `$EDITOR`, then every known
+    /// editor installed in PATH, deduplicated by binary. This is read-only and
+    /// intended for "pick an editor" UIs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::Editor;
+    ///
+    /// for candidate in Editor::candidates() {
+    ///     println!("{} ({:?})", candidate.binary, candidate.source);
+    /// }
+    /// ```
+    pub fn candidates() -> Vec<DetectedEditor> {
+        candidates(&SystemEnvironment)
+    }
+
+    /// Lists every known editor installed on this machine.
+    ///
+    /// Searches PATH for each editor opensesame knows, in its order of
+    /// preference, without stopping at the first one. `$VISUAL` and
+    /// `$EDITOR` are ignored (see [`candidates()`](Self::candidates) to
+    /// include them), and each [`EditorKind`] appears once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::Editor;
+    ///
+    /// for editor in Editor::detect_all() {
+    ///     println!("{} ({})", editor.kind, editor.binary);
+    /// }
+    /// ```
+    pub fn detect_all() -> Vec<DetectedEditor> {
+        detect_all(&SystemEnvironment)
+    }
+
+    /// Reports what each editor source resolves to, without opening anything.
+    ///
+    /// The report covers `$VISUAL`, `$EDITOR`, and PATH search, and flags
+    /// environment variables that disagree or name missing binaries. This is
+    /// intended for "doctor"-style commands in applications.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::Editor;
+    ///
+    /// let diagnostics = Editor::diagnose();
+    /// if diagnostics.is_conflicting() {
+    ///     println!("$VISUAL and $EDITOR disagree");
+    /// }
+    /// print!("{diagnostics}");
+    /// ```
+    pub fn diagnose() -> EditorDiagnostics {
+        EditorDiagnostics::collect(&SystemEnvironment)
+    }
 }
 
 /// Builder for opening files in editors with fine-grained control.
@@ -563,7 +421,8 @@ impl Editor {
 ///
 /// # Configuration
 ///
-/// Use [`with_config()`](Self::with_config) to provide editor preferences from your application's config:
+/// Use [`with_config()`](Self::with_config) to provide editor preferences from
+/// your application's config:
 ///
 /// ```rust,no_run
 /// use opensesame::{Editor, EditorConfig};
@@ -577,25 +436,105 @@ impl Editor {
 /// # Ok::<(), opensesame::Error>(())
 /// ```
 #[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)] // Independent on/off options
 pub struct EditorBuilder {
     file: Option<PathBuf>,
     line: Option<u32>,
     column: Option<u32>,
     wait: bool,
+    /// Error instead of proceeding when `wait` is set but can't be honored.
+    strict_wait: bool,
+    index_base: IndexBase,
+    clamp_positions: bool,
+    /// Overrides the editor's native column convention.
+    column_semantics: Option<ColumnSemantics>,
+    split: Option<Split>,
+    window_mode: WindowMode,
+    folder: bool,
+    scroll: Option<ScrollPosition>,
+    /// Lines to select, from a `#L<start>-L<end>` reference.
+    selection: Option<LineRange>,
+    read_only: bool,
+    isolated: bool,
+    /// Reject control characters in the path and editor arguments.
+    sanitize: bool,
+    follow_symlinks: bool,
+    via_login_shell: bool,
+    create_if_missing: bool,
+    create_parent_dirs: bool,
+    start_emacs_daemon: bool,
+    multi_file_policy: MultiFilePolicy,
+    /// Unpositioned files opened alongside `file` in the same invocation.
+    extra_files: Vec<PathBuf>,
+    /// Program name to present as `argv[0]` (Unix only).
+    argv0: Option<String>,
+    /// Character encoding to open the file with.
+    encoding: Option<String>,
+    /// Bring the editor window to the front after opening (macOS only).
+    activate: bool,
+    /// Working directory to start the editor in.
+    current_dir: Option<PathBuf>,
+    /// Environment variables to set (`Some`) or remove (`None`), in order.
+    envs: Vec<(OsString, Option<OsString>)>,
+    /// Let the editor take focus when it opens (`None` means the default,
+    /// `true`).
+    focus: Option<bool>,
+    vim_position_style: VimPositionStyle,
+    terminal_launcher: TerminalLauncher,
+    /// Treat unknown editors from `$VISUAL`/`$EDITOR` as terminal editors
+    /// under a TTY (`None` means the default, `true`).
+    assume_terminal_for_unknown: Option<bool>,
+    /// Skip editors from environment variables whose binary can't be found
+    /// (`None` means the default, `true`).
+    verify_editor_exists: Option<bool>,
+    /// Pass `+LINE` to every unknown editor, not just known `+LINE` editors.
+    plus_line_fallback: bool,
+    /// Editor to use instead of a terminal editor when there is no TTY.
+    no_tty_fallback: Option<EditorKind>,
+    /// Pattern to search for, with its line range; overrides `line`/`column`.
+    #[cfg(feature = "regex")]
+    pattern: Option<(regex::Regex, u32, u32)>,
+    #[cfg(feature = "regex")]
+    pattern_miss: crate::pattern::PatternMiss,
+    /// Cursor positions set via `positions()`; overrides `line`/`column`.
+    positions: Vec<(u32, u32)>,
     editor: Option<EditorSpec>,
+    /// Set when `editor()`/`editor_binary()` were called with different editors.
+    editor_conflict: Option<String>,
     /// Configs in priority order (first = highest priority).
     configs: Vec<EditorConfig>,
     /// Custom resolution order.
     resolve_order: Option<Vec<ResolveFrom>>,
+    /// Extra directories searched for editor binaries after `PATH`.
+    search_dirs: Vec<PathBuf>,
+    verbose_errors: bool,
+    /// Error instead of falling back when a configured editor is missing.
+    strict: bool,
 }
 
+/// Starts a resolved editor on a file at a position.
+type Launch<T> =
+    fn(&EditorBuilder, &Path, Option<u32>, Option<u32>, &[(u32, u32)], DetectedEditor) -> Result<T>;
+
+/// A validated file with its line, column, and extra cursor positions.
+type Target = (PathBuf, Option<u32>, Option<u32>, Vec<(u32, u32)>);
+
 /// Specification for which editor to use.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 enum EditorSpec {
     Kind(EditorKind),
     Binary(String),
 }
 
+impl std::fmt::Display for EditorSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Kind(kind) => write!(f, "{kind}"),
+            Self::Binary(binary) => write!(f, "'{binary}'"),
+        }
+    }
+}
+
 impl EditorBuilder {
     /// Creates a new editor builder with default settings.
     fn new() -> Self {
@@ -605,328 +544,4023 @@ impl EditorBuilder {
     /// Sets the file to open.
     ///
     /// This is required before calling `open()`.
+    ///
+    /// Files on other machines can be given as `user@host:path` or
+    /// `scp://[user@]host/path`; they are opened through the editor's remote
+    /// support (see [`EditorKind::supports_remote()`]).
+    #[must_use]
     pub fn file(mut self, path: impl AsRef<Path>) -> Self {
         self.file = Some(path.as_ref().to_path_buf());
         self
     }
 
-    /// Sets the line number to open at (1-indexed).
+    /// Sets a folder to open as a workspace, instead of a file.
     ///
-    /// If the editor doesn't support line positioning, this is ignored.
-    pub const fn line(mut self, line: u32) -> Self {
-        self.line = Some(line);
-        self
-    }
-
-    /// Sets the column number to open at (1-indexed).
+    /// Line, column, and other positioning are ignored, and the folder must
+    /// exist: [`create_if_missing()`](Self::create_if_missing) doesn't apply.
+    /// The folder is passed the way the editor expects one, e.g. `code proj`
+    /// rather than `code --goto proj`.
     ///
-    /// If the editor doesn't support column positioning, this is ignored.
-    /// Requires `line()` to also be set.
-    pub const fn column(mut self, column: u32) -> Self {
-        self.column = Some(column);
-        self
-    }
-
-    /// Sets whether to wait for the editor to close before returning.
+    /// Opening fails with `Error::InvalidConfig` in editors that can't open
+    /// folders, such as nano (see [`EditorKind::supports_folders()`]), and if
+    /// the path isn't a directory.
     ///
-    /// Not all editors support this. For editors that don't, this is ignored.
-    pub const fn wait(mut self, wait: bool) -> Self {
-        self.wait = wait;
-        self
-    }
-
-    /// Specifies which editor to use by kind.
+    /// # Example
     ///
-    /// If not specified, the editor is detected automatically.
-    pub fn editor(mut self, kind: EditorKind) -> Self {
-        self.editor = Some(EditorSpec::Kind(kind));
-        self
-    }
-
-    /// Specifies which editor to use by binary name.
+    /// ```rust,no_run
+    /// use opensesame::Editor;
     ///
-    /// This is useful for editors not in the `EditorKind` enum.
-    pub fn editor_binary(mut self, binary: impl Into<String>) -> Self {
-        self.editor = Some(EditorSpec::Binary(binary.into()));
+    /// Editor::builder().folder(".").wait(true).open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    #[must_use]
+    pub fn folder(mut self, path: impl AsRef<Path>) -> Self {
+        self.file = Some(path.as_ref().to_path_buf());
+        self.folder = true;
         self
     }
 
-    /// Adds a configuration to be checked during editor resolution.
-    ///
-    /// Multiple configs can be added. They are checked in the order added,
-    /// with earlier configs taking priority.
+    /// Adds files to open in the same editor invocation.
     ///
-    /// The resolution order when configs are present (and no explicit editor is set):
-    /// 1. Configs (in order added)
-    /// 2. `$VISUAL` environment variable
-    /// 3. `$EDITOR` environment variable
-    /// 4. PATH search
+    /// The first file becomes the target of [`file()`](Self::file) if none
+    /// was set; the rest open alongside it. Repeated paths are skipped.
+    /// [`line()`](Self::line), [`column()`](Self::column), and other
+    /// positioning apply to the first file only; the others open at the top.
     ///
-    /// Use [`resolve_order()`](Self::resolve_order) to customize this behavior.
+    /// How the files reach the editor follows the
+    /// [`multi_file_policy()`](Self::multi_file_policy). Editors that take a
+    /// single file (see [`EditorKind::supports_multiple_files()`]) are
+    /// started once per file when they are GUI editors, and fail with
+    /// `Error::InvalidConfig` when they are terminal editors.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use opensesame::{Editor, EditorConfig};
-    ///
-    /// let user_config = EditorConfig::with_editor("nvim");
-    /// let app_defaults = EditorConfig::with_editor("code");
+    /// use opensesame::Editor;
     ///
     /// Editor::builder()
-    ///     .file("test.rs")
-    ///     .with_config(user_config)    // Checked first
-    ///     .with_config(app_defaults)   // Checked second (fallback)
+    ///     .files(["src/main.rs", "src/lib.rs"])
+    ///     .line(42)
     ///     .open()?;
     /// # Ok::<(), opensesame::Error>(())
     /// ```
-    pub fn with_config(mut self, config: EditorConfig) -> Self {
-        self.configs.push(config);
+    #[must_use]
+    pub fn files<P: AsRef<Path>>(mut self, files: impl IntoIterator<Item = P>) -> Self {
+        for path in files {
+            let path = path.as_ref();
+            if self.file.is_none() {
+                self.file = Some(path.to_path_buf());
+            } else if self.file.as_deref() != Some(path)
+                && !self.extra_files.iter().any(|file| file == path)
+            {
+                self.extra_files.push(path.to_path_buf());
+            }
+        }
         self
     }
 
-    /// Sets the order in which editor sources are checked.
+    /// Sets the file to open from a reference with an optional line fragment.
     ///
-    /// By default, when configs are provided, the order is:
-    /// `[Config, Visual, Editor, PathSearch]`
+    /// Accepts GitHub-style references such as `src/main.rs#L42` and
+    /// `src/main.rs#L42-L50` (see [`parse_line_fragment()`](crate::parse_line_fragment)).
+    /// The fragment is stripped from the path and its first line becomes the
+    /// line to open at. Editors where [`EditorKind::supports_selection()`]
+    /// is `true` also select the whole range; others only go to its start.
+    /// References without a fragment behave like [`file()`](Self::file).
     ///
-    /// Without configs, the legacy order is used:
-    /// `[Visual, Editor, PathSearch]`
+    /// `file://` URIs, as sent by LSP clients, are accepted too, with an
+    /// optional `#L<line>[,<column>]` fragment that sets the line and column
+    /// (see [`parse_file_uri()`](crate::parse_file_uri)).
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use opensesame::{Editor, ResolveFrom};
+    /// use opensesame::Editor;
     ///
-    /// // Ignore environment variables, only use PATH search
     /// Editor::builder()
-    ///     .file("test.rs")
-    ///     .resolve_order(&[ResolveFrom::PathSearch])
+    ///     .file_ref("src/main.rs#L42-L50")
+    ///     .open()?;
+    ///
+    /// Editor::builder()
+    ///     .file_ref("file:///home/me/project/src/main.rs#L10,5")
     ///     .open()?;
     /// # Ok::<(), opensesame::Error>(())
     /// ```
+    #[must_use]
+    pub fn file_ref(mut self, reference: &str) -> Self {
+        if let Some(location) = crate::uri::parse_file_uri(reference) {
+            self.file = Some(location.path);
+            self.selection = None;
+            if location.line.is_some() {
+                self.line = location.line;
+                self.column = location.column;
+            }
+            return self;
+        }
+        let (path, range) = crate::fragment::parse_line_fragment(reference);
+        self.file = Some(PathBuf::from(path));
+        if let Some(range) = range {
+            self.line = Some(range.start);
+        }
+        self.selection = range.filter(|range| range.end > range.start);
+        self
+    }
+
+    /// Sets the line number to open at (1-indexed).
     ///
-    /// # Predefined Orders
+    /// If the editor doesn't support line positioning, this is ignored.
+    #[must_use]
+    pub const fn line(mut self, line: u32) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the column number to open at (1-indexed).
     ///
-    /// - [`DEFAULT_RESOLVE_ORDER`](crate::DEFAULT_RESOLVE_ORDER): `[Config, Visual, Editor, PathSearch]`
-    /// - [`ENV_ONLY_RESOLVE_ORDER`](crate::ENV_ONLY_RESOLVE_ORDER): `[Visual, Editor, PathSearch]`
-    pub fn resolve_order(mut self, order: &[ResolveFrom]) -> Self {
-        self.resolve_order = Some(order.to_vec());
+    /// If the editor doesn't support column positioning, this is ignored.
+    /// Requires `line()` to also be set: opening with a column but no line
+    /// fails with `Error::ColumnWithoutLine`.
+    #[must_use]
+    pub const fn column(mut self, column: u32) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the line number if one is given.
+    ///
+    /// Convenience for optional sources such as parsed CLI arguments:
+    /// `None` leaves the builder unchanged.
+    #[must_use]
+    pub const fn maybe_line(mut self, line: Option<u32>) -> Self {
+        if let Some(line) = line {
+            self.line = Some(line);
+        }
+        self
+    }
+
+    /// Sets the column number if one is given.
+    ///
+    /// Convenience for optional sources such as parsed CLI arguments:
+    /// `None` leaves the builder unchanged.
+    #[must_use]
+    pub const fn maybe_column(mut self, column: Option<u32>) -> Self {
+        if let Some(column) = column {
+            self.column = Some(column);
+        }
+        self
+    }
+
+    /// Sets several `(line, column)` positions to go to in the file.
+    ///
+    /// The first position replaces any `line()`/`column()` values. Editors
+    /// where [`EditorKind::supports_multiple_positions()`] is `true` receive
+    /// every position, one `--goto` each; this is not multi-cursor editing,
+    /// and the cursor ends up at the last position. Other editors open at
+    /// the first position only.
+    #[must_use]
+    pub fn positions(mut self, positions: &[(u32, u32)]) -> Self {
+        self.positions = positions.to_vec();
+        self
+    }
+
+    /// Sets the numbering base of the values passed to `line()` and `column()`.
+    ///
+    /// Defaults to [`IndexBase::OneBased`]. With [`IndexBase::ZeroBased`],
+    /// both values are shifted by one before the editor command is built, so
+    /// a zero-based `line(0)` opens the first line instead of erroring.
+    #[must_use]
+    pub const fn index_base(mut self, index_base: IndexBase) -> Self {
+        self.index_base = index_base;
+        self
+    }
+
+    /// Sets whether out-of-range positions are clamped instead of rejected.
+    ///
+    /// By default, a line or column of `0` (with [`IndexBase::OneBased`])
+    /// makes opening fail with `Error::InvalidPosition`. When `true`, it is
+    /// bumped to `1` instead, for positions from tools that are sometimes
+    /// zero-indexed. Clamping also applies where a position overflows a
+    /// known limit, such as a zero-based `u32::MAX`, which stays at
+    /// `u32::MAX`. Lines past the end of the file are left to the editor,
+    /// which opens at the last line. Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// // Opens at line 1, column 1
+    /// Editor::builder()
+    ///     .file("src/main.rs")
+    ///     .line(0)
+    ///     .column(0)
+    ///     .clamp_positions(true)
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn clamp_positions(mut self, clamp: bool) -> Self {
+        self.clamp_positions = clamp;
+        self
+    }
+
+    /// Sets whether to wait for the editor to close before returning.
+    ///
+    /// Not all editors support this. For editors that don't, this is ignored.
+    #[must_use]
+    pub const fn wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// Sets how the target editor counts columns.
+    ///
+    /// Columns given to `column()` are character columns. By default they
+    /// are converted to the resolved editor's native convention (see
+    /// [`EditorKind::column_semantics()`]); use this to override it, e.g.
+    /// with a custom tab width for an editor that counts display cells.
+    ///
+    /// Conversion reads the target line from the file. If the file or line
+    /// can't be read, the column is passed through unchanged.
+    #[must_use]
+    pub const fn column_semantics(mut self, semantics: ColumnSemantics) -> Self {
+        self.column_semantics = Some(semantics);
+        self
+    }
+
+    /// Sets whether to open the file read-only.
+    ///
+    /// Uses the editor's view-mode flag (see [`EditorKind::read_only_args()`]).
+    /// Editors without one, such as VS Code, open the file normally; check
+    /// [`EditorKind::supports_read_only()`] to warn users beforehand.
+    #[must_use]
+    pub const fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets whether to reject paths and editor arguments that contain
+    /// newlines, carriage returns, or NUL bytes.
+    ///
+    /// opensesame spawns editors directly, without a shell, but launchers
+    /// such as tmux hand the command to a shell, where a newline in a file
+    /// name would start a new command. With sanitizing enabled, such values
+    /// fail with [`Error::InvalidConfig`] before anything is spawned.
+    /// Defaults to `false`.
+    #[must_use]
+    pub const fn sanitize(mut self, sanitize: bool) -> Self {
+        self.sanitize = sanitize;
+        self
+    }
+
+    /// Sets whether to launch the editor without the user's config files.
+    ///
+    /// Useful for reproducible launches in tests and sandboxes: Vim gets
+    /// `-u NONE -U NONE`, NeoVim `--clean`, Emacs `-Q`, Nano
+    /// `--ignorercfiles`, and VS Code-family editors a fresh
+    /// `--user-data-dir` in the system temp directory. The directory is
+    /// created for each launch, readable only by the current user, and
+    /// removed once the editor exits, if opensesame waits for it (see
+    /// [`ResolvedPlan::will_block`]). Other editors launch normally; check
+    /// [`EditorKind::supports_isolation()`] beforehand.
+    #[must_use]
+    pub const fn isolated(mut self, isolated: bool) -> Self {
+        self.isolated = isolated;
+        self
+    }
+
+    /// Sets how Vim-family editors place the cursor.
+    ///
+    /// The default, [`VimPositionStyle::CursorCall`], passes
+    /// `+call cursor(LINE,COL)`. [`VimPositionStyle::GotoLineNormal`] passes
+    /// `+LINE -c "normal! COL|"` instead, which is more robust when plugins
+    /// move the cursor on startup. Other editors ignore this setting.
+    #[must_use]
+    pub const fn vim_position_style(mut self, style: VimPositionStyle) -> Self {
+        self.vim_position_style = style;
+        self
+    }
+
+    /// Sets whether to bring the editor window to the front after opening.
+    ///
+    /// Editors launched from a terminal often open behind it. On macOS this
+    /// runs `open -a <App>` for GUI editors with a known application name
+    /// as soon as the editor has started, before waiting for it; a failure
+    /// to activate is ignored. Other
+    /// platforms and terminal editors ignore this setting. Defaults to
+    /// `false`.
+    #[must_use]
+    pub const fn activate(mut self, activate: bool) -> Self {
+        self.activate = activate;
+        self
+    }
+
+    /// Sets the working directory the editor process starts in.
+    ///
+    /// Terminal editors, and the file browsers and project detection of
+    /// many editors, depend on the editor's working directory. Defaults to
+    /// the current process's.
+    ///
+    /// Relative file paths are still resolved against the current process's
+    /// directory: they are passed to the editor as absolute paths when a
+    /// working directory is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// Editor::builder()
+    ///     .file("/home/me/project/src/main.rs")
+    ///     .current_dir("/home/me/project")
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    #[must_use]
+    pub fn current_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.current_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets an environment variable for the editor process.
+    ///
+    /// Variables accumulate and override those inherited from the current
+    /// process, e.g. `NVIM_APPNAME` to pick a NeoVim config or `TERM` for
+    /// terminal editors. Setting the same variable again replaces its value.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// Editor::builder()
+    ///     .file("src/main.rs")
+    ///     .env("NVIM_APPNAME", "nvim-minimal")
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    #[must_use]
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.envs.push((
+            key.as_ref().to_os_string(),
+            Some(value.as_ref().to_os_string()),
+        ));
+        self
+    }
+
+    /// Sets several environment variables for the editor process.
+    ///
+    /// The same as calling [`env()`](Self::env) for each pair.
+    #[must_use]
+    pub fn envs<K, V>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        for (key, value) in vars {
+            self = self.env(key, value);
+        }
+        self
+    }
+
+    /// Removes an inherited environment variable from the editor process.
+    ///
+    /// Useful to scrub variables the editor shouldn't see, such as
+    /// `GIT_EDITOR` set by a surrounding git command. A later
+    /// [`env()`](Self::env) for the same variable sets it again.
+    #[must_use]
+    pub fn env_remove(mut self, key: impl AsRef<OsStr>) -> Self {
+        self.envs.push((key.as_ref().to_os_string(), None));
+        self
+    }
+
+    /// Sets whether the editor may take focus when it opens the file.
+    ///
+    /// Defaults to `true`. When `false`, for tools that open files in the
+    /// background, editors with a no-focus flag get it (Sublime Text's
+    /// `--background`, see [`EditorKind::supports_background()`]) and
+    /// [`activate()`](Self::activate) is not applied. Other editors ignore
+    /// this setting and focus as they normally would.
+    #[must_use]
+    pub const fn focus(mut self, focus: bool) -> Self {
+        self.focus = Some(focus);
+        self
+    }
+
+    /// Sets the character encoding to open the file with.
+    ///
+    /// For legacy files that aren't UTF-8. VS Code-family editors get
+    /// `--encoding <encoding>` and Vim, GVim, and NeoVim re-read the file
+    /// with `-c "e ++enc=<encoding>"`. Other editors ignore it; check
+    /// [`EditorKind::supports_encoding()`] beforehand. The name is passed
+    /// through as given (e.g., `latin1`, `cp1252`, `shift_jis`).
+    ///
+    /// # Errors
+    ///
+    /// Opening fails with `Error::InvalidConfig` if the name contains
+    /// anything other than ASCII letters, digits, `-`, `_`, `.`, or `:`.
+    #[must_use]
+    pub fn encoding(mut self, encoding: impl Into<String>) -> Self {
+        self.encoding = Some(encoding.into());
+        self
+    }
+
+    /// Sets the program name the editor sees as `argv[0]`.
+    ///
+    /// Some editors change behavior based on the name they were started
+    /// under: Vim launched as `view` opens files read-only, and as `ex`
+    /// starts in Ex mode. The resolved binary is still the one executed.
+    ///
+    /// Only supported on Unix; other platforms ignore it. Also ignored when
+    /// a [`terminal_launcher()`](Self::terminal_launcher) wraps the editor
+    /// in another program.
+    #[must_use]
+    pub fn argv0(mut self, argv0: impl Into<String>) -> Self {
+        self.argv0 = Some(argv0.into());
+        self
+    }
+
+    /// Sets whether to create the file if it doesn't exist.
+    ///
+    /// Defaults to `false`: opening a path that doesn't exist fails with
+    /// `Error::FileNotFound` before any editor is started. When `true`, an
+    /// empty file is created just before launching instead, so every editor
+    /// behaves the same (some, notably JetBrains IDEs, refuse a missing
+    /// path; [`EditorKind::opens_nonexistent()`] lists which). Missing parent
+    /// directories are only created with
+    /// [`create_parent_dirs()`](Self::create_parent_dirs). Remote targets are
+    /// never checked or created.
+    ///
+    /// # Errors
+    ///
+    /// Opening fails with `Error::Io` if the file can't be created.
+    #[must_use]
+    pub const fn create_if_missing(mut self, create: bool) -> Self {
+        self.create_if_missing = create;
+        self
+    }
+
+    /// Sets whether [`create_if_missing()`](Self::create_if_missing) also
+    /// creates missing parent directories.
+    ///
+    /// Defaults to `false`, so a typo in a directory name fails with
+    /// `Error::Io` instead of silently creating a new tree.
+    #[must_use]
+    pub const fn create_parent_dirs(mut self, create: bool) -> Self {
+        self.create_parent_dirs = create;
+        self
+    }
+
+    /// Sets whether `emacsclient` starts an Emacs daemon when none is
+    /// running.
+    ///
+    /// When `true`, `emacsclient` is passed `-a ''` (an empty alternate
+    /// editor), which starts `emacs --daemon` and retries. Defaults to
+    /// `false`, so `emacsclient` fails if no server is running. Other
+    /// editors ignore this.
+    #[must_use]
+    pub const fn start_emacs_daemon(mut self, start: bool) -> Self {
+        self.start_emacs_daemon = start;
+        self
+    }
+
+    /// Sets how several files (see [`files()`](Self::files) and
+    /// [`open_all_diagnostics()`](Self::open_all_diagnostics)) are handed to
+    /// the editor.
+    ///
+    /// Defaults to [`MultiFilePolicy::SingleProcess`], which opens every
+    /// file in one invocation when the editor supports it (see
+    /// [`EditorKind::supports_multiple_files()`]).
+    ///
+    /// # Errors
+    ///
+    /// Opening several files in a terminal editor fails with
+    /// `Error::InvalidConfig` under [`MultiFilePolicy::SeparateProcesses`],
+    /// or when the editor takes only one file.
+    #[must_use]
+    pub const fn multi_file_policy(mut self, policy: MultiFilePolicy) -> Self {
+        self.multi_file_policy = policy;
+        self
+    }
+
+    /// Sets whether to start editors missing from `PATH` through the user's
+    /// login shell.
+    ///
+    /// Apps launched from the macOS Dock or a Linux desktop often get a
+    /// minimal `PATH`, so `code` isn't found even though it works in a
+    /// terminal. When `true` and the editor binary can't be found directly,
+    /// it is run as `$SHELL -lc 'exec <command>'`, letting the shell's
+    /// profile set up `PATH`. Each argument is quoted for a POSIX shell, so
+    /// `$SHELL` is only used if it is one (sh, bash, zsh, dash, ksh, mksh,
+    /// yash, ash); fish, tcsh, and other shells fall back to `/bin/sh`,
+    /// which reads `~/.profile` instead of their own startup files. An
+    /// explicit [`editor()`](Self::editor) or
+    /// [`editor_binary()`](Self::editor_binary) that isn't found is then
+    /// tried this way instead of failing with `Error::EditorNotFound`, and
+    /// `$VISUAL` and `$EDITOR` are used even if their binary isn't found
+    /// (unless [`verify_editor_exists()`](Self::verify_editor_exists) says
+    /// otherwise).
+    ///
+    /// Editors found directly are spawned as usual, and
+    /// [`argv0()`](Self::argv0) is ignored for editors started through the
+    /// shell. Only supported on Unix; other platforms ignore it. Defaults to
+    /// `false`.
+    #[must_use]
+    pub const fn via_login_shell(mut self, via_login_shell: bool) -> Self {
+        self.via_login_shell = via_login_shell;
+        self
+    }
+
+    /// Sets whether to open a symlink's target instead of the link itself.
+    ///
+    /// When `true`, the file path is canonicalized before building the
+    /// command, so the editor shows (and saves to) the real file. This suits
+    /// dotfile managers that symlink config files into place. Paths that
+    /// can't be resolved, such as files that don't exist yet, are used as
+    /// given. Defaults to `false`.
+    #[must_use]
+    pub const fn follow_symlinks(mut self, follow: bool) -> Self {
+        self.follow_symlinks = follow;
+        self
+    }
+
+    /// Sets how terminal editors are hosted.
+    ///
+    /// Defaults to [`TerminalLauncher::Inline`], which runs the editor in the
+    /// current terminal. The other launchers open it in a new tmux window or
+    /// pane, or a new terminal emulator window (Kitty, WezTerm, Alacritty,
+    /// GNOME Terminal, Terminal.app, ...), leaving the caller's terminal
+    /// untouched; `open()` then returns once the launcher has started. Use
+    /// [`TerminalLauncher::detect()`] to pick one from the current terminal,
+    /// or a new window when there is no terminal, as in GUI applications.
+    /// GUI editors ignore this setting.
+    #[must_use]
+    pub const fn terminal_launcher(mut self, launcher: TerminalLauncher) -> Self {
+        self.terminal_launcher = launcher;
+        self
+    }
+
+    /// Sets an editor to use when a terminal editor is resolved without a TTY.
+    ///
+    /// Applications embedded in a GUI often have no controlling terminal, so
+    /// a resolved `vim` or `nano` can't run. With a fallback set, such
+    /// editors are replaced by the given kind (typically a GUI editor like
+    /// [`EditorKind::VsCode`]) when stdin is not a terminal. Has no effect
+    /// when a non-inline [`terminal_launcher()`](Self::terminal_launcher)
+    /// is configured, since that provides its own terminal.
+    #[must_use]
+    pub const fn no_tty_fallback(mut self, kind: EditorKind) -> Self {
+        self.no_tty_fallback = Some(kind);
+        self
+    }
+
+    /// Sets whether unrecognized editors from `$VISUAL`/`$EDITOR` are
+    /// treated as terminal editors when stdin is a TTY.
+    ///
+    /// An unknown binary named by `$VISUAL` or `$EDITOR` is most likely a
+    /// terminal editor (`micro`, `joe`, `ne`, ...), so by default it inherits
+    /// stdio and honors the [`terminal_launcher()`](Self::terminal_launcher)
+    /// when opensesame runs in a terminal. Defaults to `true`.
+    #[must_use]
+    pub const fn assume_terminal_for_unknown(mut self, assume: bool) -> Self {
+        self.assume_terminal_for_unknown = Some(assume);
+        self
+    }
+
+    /// Sets whether editors named by environment variables must exist.
+    ///
+    /// By default, a `$VISUAL` or `$EDITOR` naming a binary that isn't in
+    /// `PATH` (or a path that doesn't exist) is skipped, and resolution moves
+    /// on to the next source. Pass `false` to use the variable as-is, for
+    /// setups where the binary is only reachable when the editor is
+    /// launched. Defaults to `true`, or to `false` with
+    /// [`via_login_shell()`](Self::via_login_shell), whose login shell may
+    /// find binaries that aren't in this process's `PATH`.
+    #[must_use]
+    pub const fn verify_editor_exists(mut self, verify: bool) -> Self {
+        self.verify_editor_exists = Some(verify);
+        self
+    }
+
+    /// Sets whether every unknown editor is positioned with `+LINE file`.
+    ///
+    /// Unknown editors whose binary is a common terminal editor that accepts
+    /// `+LINE` (joe, micro, mg, ...) are always positioned that way. Other
+    /// unknown editors only get the file, since one that doesn't understand
+    /// `+LINE` would open a file named `+42`. Pass `true` to guess `+LINE`
+    /// for them too. Defaults to `false`.
+    #[must_use]
+    pub const fn plus_line_fallback(mut self, enabled: bool) -> Self {
+        self.plus_line_fallback = enabled;
+        self
+    }
+
+    /// Opens at the first match of `regex` within lines `start_line..=end_line`.
+    ///
+    /// The file is read when opening, and the cursor is placed at the start
+    /// of the first match. Line numbers follow the configured
+    /// [`index_base()`](Self::index_base). This overrides
+    /// [`line()`](Self::line) and [`column()`](Self::column). When nothing
+    /// matches, behavior follows [`pattern_miss()`](Self::pattern_miss).
+    ///
+    /// Requires the `regex` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// // Jump to the TODO in the function spanning lines 40-80
+    /// Editor::builder()
+    ///     .file("src/main.rs")
+    ///     .pattern_in_range(regex::Regex::new(r"TODO").unwrap(), 40, 80)
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn pattern_in_range(mut self, regex: regex::Regex, start_line: u32, end_line: u32) -> Self {
+        self.pattern = Some((regex, start_line, end_line));
+        self
+    }
+
+    /// Sets what happens when the [`pattern_in_range()`](Self::pattern_in_range)
+    /// pattern has no match.
+    ///
+    /// Defaults to [`PatternMiss::Error`](crate::PatternMiss::Error).
+    ///
+    /// Requires the `regex` feature.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub const fn pattern_miss(mut self, miss: crate::pattern::PatternMiss) -> Self {
+        self.pattern_miss = miss;
+        self
+    }
+
+    /// Lays out the file and the [`files()`](Self::files) opened with it in
+    /// splits.
+    ///
+    /// Only editors where [`EditorKind::supports_split()`] is `true` have a
+    /// CLI flag for this; for other editors it is ignored. The flag only
+    /// arranges the files on the command line, so a single file is opened
+    /// without a split.
+    #[must_use]
+    pub const fn split(mut self, split: Split) -> Self {
+        self.split = Some(split);
+        self
+    }
+
+    /// Sets whether GUI editors open the file in an existing or a new window.
+    ///
+    /// Only editors where [`EditorKind::supports_window_mode()`] is `true`
+    /// (the VS Code family and Sublime Text) have a CLI flag for this; for
+    /// other editors it is ignored. Defaults to [`WindowMode::Default`],
+    /// which leaves the choice to the editor's settings.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::{Editor, WindowMode};
+    ///
+    /// Editor::builder()
+    ///     .file("src/main.rs")
+    ///     .window_mode(WindowMode::Reuse)
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn window_mode(mut self, mode: WindowMode) -> Self {
+        self.window_mode = mode;
+        self
+    }
+
+    /// Sets where the target line appears in the editor window.
+    ///
+    /// Only editors where [`EditorKind::supports_scroll()`] is `true` can do
+    /// this from the CLI; for other editors, or without a line, it is
+    /// ignored.
+    #[must_use]
+    pub const fn scroll(mut self, scroll: ScrollPosition) -> Self {
+        self.scroll = Some(scroll);
+        self
+    }
+
+    /// Sets whether an unsupported `wait(true)` is an error.
+    ///
+    /// By default, requesting a wait from an editor that can't wait is
+    /// silently ignored. In strict mode, `open()` instead returns
+    /// `Error::WaitUnsupported` when the resolved editor wouldn't block
+    /// until the file is closed, as [`EditorKind::will_block()`] tells:
+    /// terminal editors and GUI editors that stay in the foreground block
+    /// anyway, and editors that fork need a wait flag.
+    #[must_use]
+    pub const fn strict_wait(mut self, strict: bool) -> Self {
+        self.strict_wait = strict;
         self
     }
 
-    /// Opens the file in the editor.
-    ///
-    /// This method spawns the editor process. For GUI editors, it returns
-    /// immediately. For terminal editors, it blocks until the editor closes.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - No file was specified
-    /// - The file doesn't exist
-    /// - No editor could be found
-    /// - The editor failed to start
-    pub fn open(self) -> Result<()> {
-        // Validate file is specified
-        let file = self.file.clone().ok_or(Error::NoFileSpecified)?;
+    /// Specifies which editor to use by kind.
+    ///
+    /// If not specified, the editor is detected automatically.
+    ///
+    /// Only one editor may be specified: combining this with a different
+    /// `editor()` or `editor_binary()` call makes `open()` return
+    /// `Error::InvalidConfig`.
+    #[must_use]
+    pub fn editor(self, kind: EditorKind) -> Self {
+        self.set_editor(EditorSpec::Kind(kind))
+    }
+
+    /// Specifies which editor to use by binary name.
+    ///
+    /// This is useful for editors not in the `EditorKind` enum.
+    ///
+    /// Only one editor may be specified: combining this with a different
+    /// `editor()` or `editor_binary()` call makes `open()` return
+    /// `Error::InvalidConfig`.
+    #[must_use]
+    pub fn editor_binary(self, binary: impl Into<String>) -> Self {
+        self.set_editor(EditorSpec::Binary(binary.into()))
+    }
+
+    /// Records the explicit editor, remembering conflicts for `open()` to report.
+    fn set_editor(mut self, spec: EditorSpec) -> Self {
+        if let Some(ref existing) = self.editor {
+            if *existing != spec && self.editor_conflict.is_none() {
+                self.editor_conflict = Some(format!(
+                    "conflicting editor specifications: {existing} and {spec}"
+                ));
+            }
+        }
+        self.editor = Some(spec);
+        self
+    }
+
+    /// Adds a configuration to be checked during editor resolution.
+    ///
+    /// Multiple configs can be added. They are checked in the order added,
+    /// with earlier configs taking priority.
+    ///
+    /// The resolution order when configs are present (and no explicit editor is set):
+    /// 1. Configs (in order added)
+    /// 2. `$VISUAL` environment variable
+    /// 3. `$EDITOR` environment variable
+    /// 4. PATH search
+    ///
+    /// Use [`resolve_order()`](Self::resolve_order) to customize this behavior.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::{Editor, EditorConfig};
+    ///
+    /// let user_config = EditorConfig::with_editor("nvim");
+    /// let app_defaults = EditorConfig::with_editor("code");
+    ///
+    /// Editor::builder()
+    ///     .file("test.rs")
+    ///     .with_config(user_config)    // Checked first
+    ///     .with_config(app_defaults)   // Checked second (fallback)
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_config(mut self, config: EditorConfig) -> Self {
+        self.configs.push(config);
+        self
+    }
+
+    /// Adds several editor configurations, in priority order.
+    ///
+    /// Equivalent to calling [`with_config()`](Self::with_config) for each
+    /// config in turn; configs added earlier keep higher priority. Useful for
+    /// layered config files loaded into a `Vec<EditorConfig>`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::{Editor, EditorConfig};
+    ///
+    /// let layers = vec![
+    ///     EditorConfig::with_editor("nvim"), // project
+    ///     EditorConfig::with_editor("code"), // user
+    /// ];
+    ///
+    /// Editor::builder()
+    ///     .file("test.rs")
+    ///     .with_configs(layers)
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_configs(mut self, configs: impl IntoIterator<Item = EditorConfig>) -> Self {
+        self.configs.extend(configs);
+        self
+    }
+
+    /// Adds directories to search for editor binaries not found in `PATH`.
+    ///
+    /// Portable and standalone installs often live outside `PATH`; listing
+    /// their directories here lets detection, [`editor()`](Self::editor) and
+    /// [`editor_binary()`](Self::editor_binary) find them. `PATH` is still
+    /// searched first. Repeated calls add to the list.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    /// use std::path::PathBuf;
+    ///
+    /// Editor::builder()
+    ///     .file("test.rs")
+    ///     .editor_search_dirs(&[PathBuf::from("/opt/VSCode-linux-x64/bin")])
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    #[must_use]
+    pub fn editor_search_dirs(mut self, dirs: &[PathBuf]) -> Self {
+        self.search_dirs.extend_from_slice(dirs);
+        self
+    }
+
+    /// Sets whether a failed lookup reports what was tried.
+    ///
+    /// When `true`, failing to find an editor returns
+    /// [`Error::NoEditorFoundDetailed`] instead of [`Error::NoEditorFound`],
+    /// listing each source that was checked and why it found nothing
+    /// ("$VISUAL is not set", "config 0: 'nvim' not found in PATH", ...).
+    /// Use [`Error::tried()`] to inspect the list. Defaults to `false`.
+    #[must_use]
+    pub const fn verbose_errors(mut self, verbose: bool) -> Self {
+        self.verbose_errors = verbose;
+        self
+    }
+
+    /// Sets whether a configured editor that isn't installed is an error.
+    ///
+    /// By default, a config naming an editor that can't be found is skipped
+    /// and resolution falls through to `$VISUAL`, `$EDITOR`, and PATH search.
+    /// When `true`, opening fails with [`Error::ConfigEditorNotFound`] naming
+    /// the missing binary if no config's editor is installed. Configs are
+    /// still tried in order, so a missing editor in one config can be
+    /// covered by the next. Has no effect when [`ResolveFrom::Config`] isn't
+    /// in the resolution order. Defaults to `false`.
+    #[must_use]
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns the environment used for editor lookup.
+    fn environment(&self) -> SearchDirs<'_> {
+        SearchDirs {
+            inner: &SystemEnvironment,
+            dirs: &self.search_dirs,
+        }
+    }
+
+    /// Sets the order in which editor sources are checked.
+    ///
+    /// By default, when configs are provided, the order is:
+    /// `[Config, Visual, Editor, PathSearch]`
+    ///
+    /// Without configs, the legacy order is used:
+    /// `[Visual, Editor, PathSearch]`
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::{Editor, ResolveFrom};
+    ///
+    /// // Ignore environment variables, only use PATH search
+    /// Editor::builder()
+    ///     .file("test.rs")
+    ///     .resolve_order(&[ResolveFrom::PathSearch])
+    ///     .open()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    ///
+    /// # Predefined Orders
+    ///
+    /// - [`DEFAULT_RESOLVE_ORDER`](crate::DEFAULT_RESOLVE_ORDER):
+    ///   `[Config, Visual, Editor, PathSearch]`
+    /// - [`ENV_ONLY_RESOLVE_ORDER`](crate::ENV_ONLY_RESOLVE_ORDER): `[Visual, Editor, PathSearch]`
+    /// - [`GIT_RESOLVE_ORDER`](crate::GIT_RESOLVE_ORDER):
+    ///   `[GitEnvEditor, Config, Visual, Editor, PathSearch]`
+    #[must_use]
+    pub fn resolve_order(mut self, order: &[ResolveFrom]) -> Self {
+        self.resolve_order = Some(order.to_vec());
+        self
+    }
+
+    /// Opens the file in the editor.
+    ///
+    /// This method spawns the editor process. For GUI editors, it returns
+    /// immediately. For terminal editors, it blocks until the editor closes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No file was specified
+    /// - The file doesn't exist
+    /// - No editor could be found
+    /// - The editor failed to start
+    pub fn open(self) -> Result<()> {
+        self.open_reporting()?;
+        Ok(())
+    }
+
+    /// Opens the file in the editor, returning where the editor came from.
+    ///
+    /// The same as [`open()`](Self::open), but reports the
+    /// [`EditorSource`] of the editor that opened the file, e.g. for logging
+    /// "opened via $EDITOR". When the preferred editor fails to start and
+    /// another candidate is used, the source is the candidate's.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`open()`](Self::open).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// let source = Editor::builder().file("src/main.rs").open_reporting()?;
+    /// eprintln!("opened via {source}");
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn open_reporting(mut self) -> Result<EditorSource> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "open",
+            file = ?self.file,
+            line = ?self.line,
+            column = ?self.column
+        )
+        .entered();
+        if self.extra_files.is_empty() {
+            return self.open_current();
+        }
+
+        let editor = self.resolve_editor()?;
+        if self.single_process(&editor, self.extra_files.len() + 1)? {
+            return self.open_current();
+        }
+
+        // One process per file, with the first file last so it ends up on top
+        let first = self.file.take();
+        let (line, column, positions) = (
+            self.line.take(),
+            self.column.take(),
+            std::mem::take(&mut self.positions),
+        );
+        for file in std::mem::take(&mut self.extra_files) {
+            self.file = Some(file);
+            self.open_current()?;
+        }
+        self.file = first;
+        (self.line, self.column, self.positions) = (line, column, positions);
+        self.open_current()
+    }
+
+    /// Starts the editor without waiting for it, returning the child process.
+    ///
+    /// Resolution, positioning, and fallback work as in [`open()`](Self::open),
+    /// but the child is returned as soon as it starts, for callers that
+    /// poll, supervise, or kill it (e.g., from an async task). The child
+    /// inherits the caller's stdin, stdout, and stderr, so terminal editors
+    /// still get the terminal; wait on the child before using it again. GUI
+    /// editors that hand the file to an existing window often exit right
+    /// away; `--wait` (see [`wait()`](Self::wait)) keeps them alive until the
+    /// file is closed. [`activate()`](Self::activate) is not applied.
+    ///
+    /// The caller is responsible for reaping the process with
+    /// [`Child::wait()`] (or `try_wait()`); otherwise it lingers as a zombie
+    /// on Unix once it exits.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`open()`](Self::open), except that the
+    /// editor's exit status is left to the caller.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// let mut child = Editor::builder()
+    ///     .file("src/main.rs")
+    ///     .line(42)
+    ///     .wait(true)
+    ///     .spawn()?;
+    /// if child.try_wait()?.is_none() {
+    ///     eprintln!("waiting for the editor to close...");
+    /// }
+    /// child.wait()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn spawn(self) -> Result<Child> {
+        self.launch_current(Self::spawn_with)
+    }
+
+    /// Starts the editor without waiting for it, returning its process ID and
+    /// handle.
+    ///
+    /// The same as [`spawn()`](Self::spawn), with the process ID alongside
+    /// the child for logging.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`spawn()`](Self::spawn).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// let (pid, mut child) = Editor::builder()
+    ///     .file("src/main.rs")
+    ///     .line(42)
+    ///     .wait(true)
+    ///     .spawn_with_pid()?;
+    /// eprintln!("editor running as pid {pid}");
+    /// child.wait()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn spawn_with_pid(self) -> Result<(u32, Child)> {
+        let child = self.spawn()?;
+        Ok((child.id(), child))
+    }
+
+    /// Returns the program and arguments [`open()`](Self::open) would run,
+    /// without running anything.
+    ///
+    /// The command is built exactly as for `open()`: the same editor
+    /// resolution, positioning arguments, and extra arguments from the
+    /// environment or config. Unlike `open()`, the file doesn't have to
+    /// exist. Useful for logging, debugging, and allow-listing commands in
+    /// a sandbox.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`open()`](Self::open) up to the point of
+    /// starting the editor, except `Error::FileNotFound`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// let (program, args) = Editor::builder().file("src/main.rs").line(42).command_line()?;
+    /// eprintln!("running: {program} {}", args.join(" "));
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn command_line(&self) -> Result<(String, Vec<String>)> {
+        self.command_line_in(&self.environment())
+    }
+
+    /// Returns the program and arguments, looking the editor up in `env`.
+    fn command_line_in(&self, env: &dyn Environment) -> Result<(String, Vec<String>)> {
+        let (file, line, column, extra_positions) = self.target()?;
+        let editor = self.resolve_editor_in(env)?;
+        let plan = self.plan_with(&file, line, column, &extra_positions, editor)?;
+        Ok((plan.program, plan.args))
+    }
+
+    /// Resolves the editor and builds its command without starting it.
+    ///
+    /// The returned [`ResolvedPlan`] describes what [`open()`](Self::open)
+    /// would do: which editor, with which arguments, whether it blocks, and
+    /// where the editor came from. Nothing is spawned and no file is
+    /// created until [`ResolvedPlan::execute()`] is called.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`open()`](Self::open) up to the point of
+    /// starting the editor.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// let plan = Editor::builder().file("src/main.rs").line(42).inspect()?;
+    /// println!("{} {}", plan.program, plan.args.join(" "));
+    /// if plan.will_block {
+    ///     eprintln!("waiting for {} to close...", plan.editor.kind);
+    /// }
+    /// plan.execute()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn inspect(self) -> Result<ResolvedPlan> {
+        self.inspect_in(&self.environment())
+    }
+
+    /// Plans opening the file, looking the editor up in `env`.
+    fn inspect_in(&self, env: &dyn Environment) -> Result<ResolvedPlan> {
+        let (file, line, column, extra_positions) = self.target()?;
+        let editor = self.resolve_editor_in(env)?;
+        self.check_exists(&file)?;
+        let create = self.creates_missing(&file);
+        let mut plan = self.plan_with(&file, line, column, &extra_positions, editor)?;
+        if create {
+            plan.create = Some((file, self.create_parent_dirs));
+        }
+        Ok(plan)
+    }
+
+    /// Opens the first of a list of `(path, line, column)` diagnostics.
+    ///
+    /// The line and column follow the configured
+    /// [`index_base()`](Self::index_base), and replace any position set on
+    /// the builder.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoFileSpecified` if `diagnostics` is empty, in
+    /// addition to the errors returned by [`open()`](Self::open).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// let diagnostics = [("src/main.rs", 42, 7), ("src/lib.rs", 10, 1)];
+    /// Editor::builder().wait(false).open_first_diagnostic(&diagnostics)?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn open_first_diagnostic<P: AsRef<Path>>(
+        self,
+        diagnostics: &[(P, u32, u32)],
+    ) -> Result<()> {
+        let (file, line, column) = diagnostics.first().ok_or(Error::NoFileSpecified)?;
+        self.file(file).line(*line).column(*column).open()
+    }
+
+    /// Opens every file in a list of `(path, line, column)` diagnostics.
+    ///
+    /// Each file is opened once. How the files reach the editor follows the
+    /// [`multi_file_policy()`](Self::multi_file_policy):
+    ///
+    /// - [`MultiFilePolicy::SingleProcess`] passes every file to one
+    ///   invocation, positioned at the first diagnostic only; the other
+    ///   files open at the top. GUI editors that take a single file fall
+    ///   back to separate processes.
+    /// - [`MultiFilePolicy::SeparateProcesses`] starts the editor once per
+    ///   file, each at its first diagnostic. The file with the first
+    ///   diagnostic is opened last, so it ends up as the active tab.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoFileSpecified` if `diagnostics` is empty, and stops
+    /// at the first file that fails to open. Returns `Error::InvalidConfig`
+    /// if several files would need separate processes of a terminal editor,
+    /// which would block one after the other.
+    pub fn open_all_diagnostics<P: AsRef<Path>>(self, diagnostics: &[(P, u32, u32)]) -> Result<()> {
+        let search_dirs = self.search_dirs.clone();
+        let env = SearchDirs {
+            inner: &SystemEnvironment,
+            dirs: &search_dirs,
+        };
+        self.each_diagnostic_launch(diagnostics, &env, |builder| {
+            builder.open_current().map(drop)
+        })
+    }
+
+    /// Runs `launch` once per editor invocation of
+    /// [`open_all_diagnostics()`](Self::open_all_diagnostics), looking the
+    /// editor up in `env`.
+    fn each_diagnostic_launch<P: AsRef<Path>>(
+        mut self,
+        diagnostics: &[(P, u32, u32)],
+        env: &dyn Environment,
+        mut launch: impl FnMut(&Self) -> Result<()>,
+    ) -> Result<()> {
+        let (first, rest) = diagnostics.split_first().ok_or(Error::NoFileSpecified)?;
+        let first_file = first.0.as_ref();
+        let mut others: Vec<(&Path, u32, u32)> = Vec::new();
+        for (file, line, column) in rest {
+            let file = file.as_ref();
+            if file != first_file && others.iter().all(|(other, _, _)| *other != file) {
+                others.push((file, *line, *column));
+            }
+        }
+
+        if !others.is_empty() {
+            let editor = self.resolve_editor_in(env)?;
+            if self.single_process(&editor, others.len() + 1)? {
+                self.extra_files = others
+                    .iter()
+                    .map(|(file, _, _)| file.to_path_buf())
+                    .collect();
+            } else {
+                for (file, line, column) in others {
+                    self.set_diagnostic(file, line, column);
+                    launch(&self)?;
+                }
+            }
+        }
+
+        self.set_diagnostic(first_file, first.1, first.2);
+        launch(&self)
+    }
+
+    /// Returns `true` if `count` files should open in one invocation of
+    /// `editor`, or `false` for one process per file.
+    fn single_process(&self, editor: &DetectedEditor, count: usize) -> Result<bool> {
+        if self.multi_file_policy == MultiFilePolicy::SingleProcess
+            && editor.kind.supports_multiple_files()
+        {
+            return Ok(true);
+        }
+        if editor.kind.is_terminal_editor_hinted(Some(&editor.binary)) {
+            return Err(Error::InvalidConfig {
+                message: format!(
+                    "{} is a terminal editor and can't open {count} files in separate processes",
+                    editor.kind
+                ),
+            });
+        }
+        Ok(false)
+    }
+
+    /// Points the builder at a single diagnostic.
+    fn set_diagnostic(&mut self, file: &Path, line: u32, column: u32) {
+        self.file = Some(file.to_path_buf());
+        self.line = Some(line);
+        self.column = Some(column);
+        self.positions.clear();
+    }
+
+    /// Opens the configured file and position, returning the source of the
+    /// editor used.
+    fn open_current(&self) -> Result<EditorSource> {
+        self.launch_current(Self::open_with)
+    }
+
+    /// Starts the editor on the configured file and position with `launch`,
+    /// falling back to other candidates if the preferred editor can't start.
+    fn launch_current<T>(&self, launch: Launch<T>) -> Result<T> {
+        let (file, line, column, extra_positions) = self.target()?;
+
+        // Resolve the editor
+        let editor = self.resolve_editor()?;
+        self.check_exists(&file)?;
+        if self.creates_missing(&file) {
+            create_missing(&file, self.create_parent_dirs)?;
+        }
+        let preferred = editor.binary.clone();
+        let error = match launch(self, &file, line, column, &extra_positions, editor) {
+            Err(e @ Error::SpawnFailed { .. }) if self.editor.is_none() => e,
+            result => return result,
+        };
+
+        // The preferred editor failed to start; try the other candidates
+        for editor in self
+            .resolve_all()
+            .into_iter()
+            .filter(|e| e.binary != preferred)
+        {
+            match launch(self, &file, line, column, &extra_positions, editor) {
+                Err(Error::SpawnFailed { .. }) => {}
+                result => return result,
+            }
+        }
+        Err(error)
+    }
+
+    /// Validates the configured file and position.
+    fn target(&self) -> Result<Target> {
+        // Validate file is specified
+        let file = self.target_file()?;
+        if self.sanitize {
+            check_sanitized("file path", &file.to_string_lossy())?;
+            for extra in &self.extra_files {
+                check_sanitized("file path", &extra.to_string_lossy())?;
+            }
+        }
+
+        if let Some(ref encoding) = self.encoding {
+            check_encoding(encoding)?;
+        }
+        if self.folder {
+            return Ok((file, None, None, Vec::new()));
+        }
+
+        // Validate position (must be >= 1 once normalized)
+        let (line, column) = self.position()?;
+        #[cfg(feature = "regex")]
+        let (line, column) = match self.pattern_position(&file)? {
+            Some((l, c)) => (Some(l), Some(c)),
+            None => (line, column),
+        };
+        let extra_positions = self.extra_positions()?;
+        Ok((file, line, column, extra_positions))
+    }
+
+    /// Returns `true` if `file` should be created before opening it.
+    fn creates_missing(&self, file: &Path) -> bool {
+        self.create_if_missing && !self.folder && remote_target(file).is_none()
+    }
+
+    /// Checks that `file` and any extra files exist, unless they will be created.
+    fn check_exists(&self, file: &Path) -> Result<()> {
+        if self.folder && remote_target(file).is_none() {
+            if !file.exists() {
+                return Err(Error::FileNotFound {
+                    path: file.to_path_buf(),
+                });
+            }
+            if !file.is_dir() {
+                return Err(Error::InvalidConfig {
+                    message: format!("{} is not a folder", file.display()),
+                });
+            }
+        }
+        if self.create_if_missing && !self.folder {
+            return Ok(());
+        }
+        for path in std::iter::once(file).chain(self.extra_files.iter().map(PathBuf::as_path)) {
+            if !path.exists() && remote_target(path).is_none() {
+                return Err(Error::FileNotFound {
+                    path: path.to_path_buf(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Opens the file in a specific resolved editor.
+    fn open_with(
+        &self,
+        file: &Path,
+        line: Option<u32>,
+        column: Option<u32>,
+        extra_positions: &[(u32, u32)],
+        editor: DetectedEditor,
+    ) -> Result<EditorSource> {
+        let plan = self.plan_with(file, line, column, extra_positions, editor)?;
+        let source = plan.source.clone();
+        plan.execute()?;
+        Ok(source)
+    }
+
+    /// Plans opening the file in a specific resolved editor.
+    fn plan_with(
+        &self,
+        file: &Path,
+        line: Option<u32>,
+        column: Option<u32>,
+        extra_positions: &[(u32, u32)],
+        editor: DetectedEditor,
+    ) -> Result<ResolvedPlan> {
+        let (editor, mut command, isolation_dir) =
+            self.command_with(file, line, column, extra_positions, editor)?;
+        if let Some(ref dir) = self.current_dir {
+            command.current_dir(dir);
+        }
+        for (key, value) in &self.envs {
+            match value {
+                Some(value) => command.env(key, value),
+                None => command.env_remove(key),
+            };
+        }
+        let gui = is_gui(&editor);
+        Ok(ResolvedPlan {
+            program: command.get_program().to_string_lossy().into_owned(),
+            args: command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            will_block: blocks(&editor, self.wait),
+            detach: gui && !self.wait && !editor.kind.forks_on_launch(),
+            source: editor.source.clone(),
+            current_dir: self.current_dir.clone(),
+            editor,
+            command,
+            activate: self.activate && self.focus.unwrap_or(true) && cfg!(target_os = "macos"),
+            create: None,
+            isolation_dir,
+        })
+    }
+
+    /// Starts a specific resolved editor on the file without waiting for it.
+    fn spawn_with(
+        &self,
+        file: &Path,
+        line: Option<u32>,
+        column: Option<u32>,
+        extra_positions: &[(u32, u32)],
+        editor: DetectedEditor,
+    ) -> Result<Child> {
+        self.plan_with(file, line, column, extra_positions, editor)?
+            .spawn()
+    }
+
+    /// Builds the command that opens the file in a specific resolved editor.
+    ///
+    /// Returns the editor actually used, which differs from `editor` when a
+    /// no-TTY fallback applies, and the data directory of an isolated launch.
+    fn command_with(
+        &self,
+        file: &Path,
+        line: Option<u32>,
+        column: Option<u32>,
+        extra_positions: &[(u32, u32)],
+        editor: DetectedEditor,
+    ) -> Result<(DetectedEditor, Command, Option<TempDir>)> {
+        let launcher = self.terminal_launcher.effective(&SystemEnvironment);
+        let has_tty = std::io::stdin().is_terminal();
+        let editor = self.apply_no_tty_fallback(&self.environment(), editor, launcher, has_tty)?;
+        self.check_wait(&editor)?;
+        if self.folder && !editor.kind.supports_folders() {
+            return Err(Error::InvalidConfig {
+                message: format!("{} can't open folders", editor.kind),
+            });
+        }
+        if !self.extra_files.is_empty() && !editor.kind.supports_multiple_files() {
+            return Err(Error::InvalidConfig {
+                message: format!("{} can't open several files in one invocation", editor.kind),
+            });
+        }
+        if self.sanitize {
+            for arg in &editor.extra_args {
+                check_sanitized("editor argument", arg)?;
+            }
+        }
+
+        // Route remote targets through the editor's remote mechanism
+        let remote = remote_target(file);
+        if remote.is_some() && !editor.kind.supports_remote() {
+            return Err(Error::RemoteUnsupported {
+                editor: editor.kind,
+            });
+        }
+
+        // Translate character columns into the editor's convention
+        let column = match (line, column) {
+            (Some(l), Some(c)) => Some(self.editor_column(editor.kind, file, l, c)),
+            _ => column,
+        };
+        let extra_positions = extra_positions
+            .iter()
+            .map(|&(l, c)| (l, self.editor_column(editor.kind, file, l, c)))
+            .collect();
+
+        // VS Code-family editors keep their profile in the isolated directory
+        let isolation_dir = if self.isolated && editor.kind.family() == EditorFamily::VsCode {
+            Some(isolation_dir()?)
+        } else {
+            None
+        };
+
+        // Build the command
+        let options = ArgOptions {
+            line,
+            column,
+            // A terminal emacsclient frame blocks anyway; `-n` would detach it
+            wait: self.wait || is_tty_emacsclient(&editor),
+            split: self.split,
+            window_mode: self.window_mode,
+            folder: self.folder,
+            scroll: self.scroll,
+            // The selection keeps its length if the line was shifted
+            select_to: self
+                .selection
+                .filter(|range| self.line == Some(range.start))
+                .zip(line)
+                .map(|(range, line)| line.saturating_add(range.end - range.start)),
+            extra_positions,
+            remote,
+            read_only: self.read_only,
+            background: !self.focus.unwrap_or(true),
+            isolated: self.isolated.then(|| {
+                isolation_dir
+                    .as_ref()
+                    .map(|dir| dir.path().display().to_string())
+                    .unwrap_or_default()
+            }),
+            plus_line_fallback: self.uses_plus_line_fallback(&editor),
+            start_emacs_daemon: self.start_emacs_daemon,
+            encoding: self.encoding.clone(),
+            vim_position_style: self.vim_position_style,
+            template: self.args_template(&editor),
+            extra_files: self
+                .extra_files
+                .iter()
+                .map(|file| self.editor_path(file).display().to_string())
+                .collect(),
+        };
+        let launch = LaunchOptions {
+            terminal_launcher: launcher,
+            assume_terminal: self.assumes_terminal(&editor, has_tty),
+            argv0: self.argv0.clone(),
+            login_shell: self.login_shell_for(&editor),
+            current_dir: self.current_dir.clone(),
+        };
+        let cmd = build_command(&editor, &self.editor_path(file), &options, &launch);
+        Ok((editor, cmd, isolation_dir))
+    }
+
+    /// Returns the editor `open()` would use, without opening anything.
+    ///
+    /// Runs the same resolution as [`open()`](Self::open): an explicit
+    /// [`editor()`](Self::editor) or [`editor_binary()`](Self::editor_binary),
+    /// otherwise the configs and [`resolve_order()`](Self::resolve_order). The
+    /// returned [`DetectedEditor::source`] says where the editor came from.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error `open()` would return for an editor that can't be
+    /// found, such as [`Error::NoEditorFound`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::{Editor, EditorConfig};
+    ///
+    /// let editor = Editor::builder()
+    ///     .with_config(EditorConfig::with_editor("nvim"))
+    ///     .resolve()?;
+    /// println!("will open with {} (from {})", editor.kind, editor.source);
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn resolve(&self) -> Result<DetectedEditor> {
+        self.resolve_editor()
+    }
+
+    /// Lists every editor this builder could open the file with, in priority order.
+    ///
+    /// With an explicit [`editor()`](Self::editor) or
+    /// [`editor_binary()`](Self::editor_binary), this is that editor alone
+    /// (or nothing, if it isn't installed). Otherwise it contains every
+    /// candidate from the configured sources, deduplicated by binary; the
+    /// first entry is the editor [`open()`](Self::open) tries first.
+    ///
+    /// `open()` already moves on to the next candidate when an editor fails
+    /// to start; this is for callers implementing their own retry policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::Editor;
+    ///
+    /// for editor in Editor::builder().resolve_all() {
+    ///     println!("{} (from {})", editor.binary, editor.source);
+    /// }
+    /// ```
+    pub fn resolve_all(&self) -> Vec<DetectedEditor> {
+        if self.editor.is_some() || self.editor_conflict.is_some() {
+            return self.resolve_editor().into_iter().collect();
+        }
+        resolve_all_with_order(
+            &self.environment(),
+            self.effective_resolve_order(),
+            &self.configs,
+            self.file_extension(),
+            self.verifies_editors(),
+        )
+        .into_iter()
+        .map(|editor| self.locate(editor))
+        .collect()
+    }
+
+    /// Converts the builder into a closure that opens the file when called.
+    ///
+    /// The closure is `Send`, so async callers can run the (potentially
+    /// blocking) open on a worker thread without opensesame depending on a
+    /// particular runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// let open = Editor::builder()
+    ///     .file("src/main.rs")
+    ///     .line(42)
+    ///     .wait(true)
+    ///     .into_spawn_fn();
+    ///
+    /// // e.g. tokio::task::spawn_blocking(open), or:
+    /// let handle = std::thread::spawn(open);
+    /// handle.join().unwrap()?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn into_spawn_fn(self) -> impl FnOnce() -> Result<()> + Send + 'static {
+        move || self.open()
+    }
+
+    /// Opens the file, or prints its location if no editor is available.
+    ///
+    /// When no editor can be found, the location is written to `writer` as
+    /// `file[:line[:column]]` followed by a newline and `Ok(())` is returned,
+    /// so build tools running headless (e.g., in CI) still surface where to
+    /// look. Every other error is returned as from [`open()`](Self::open).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use opensesame::Editor;
+    ///
+    /// Editor::builder()
+    ///     .file("src/main.rs")
+    ///     .line(42)
+    ///     .column(7)
+    ///     .open_or_print(std::io::stdout())?;
+    /// # Ok::<(), opensesame::Error>(())
+    /// ```
+    pub fn open_or_print(self, mut writer: impl std::io::Write) -> Result<()> {
+        let location = self.location()?;
+        match self.open() {
+            Err(Error::NoEditorFound | Error::NoEditorFoundDetailed(_)) => {
+                writeln!(writer, "{location}")?;
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Returns `path` as the editor should see it from its working directory.
+    ///
+    /// Relative local paths become absolute when the editor starts in
+    /// another directory.
+    fn editor_path(&self, path: &Path) -> PathBuf {
+        if self.current_dir.is_some() && path.is_relative() && remote_target(path).is_none() {
+            if let Ok(absolute) = std::path::absolute(path) {
+                return absolute;
+            }
+        }
+        path.to_path_buf()
+    }
+
+    /// Returns the file to open, resolving symlinks if requested.
+    fn target_file(&self) -> Result<PathBuf> {
+        let file = self.file.clone().ok_or(Error::NoFileSpecified)?;
+        if self.follow_symlinks {
+            if let Ok(target) = std::fs::canonicalize(&file) {
+                return Ok(target);
+            }
+        }
+        Ok(file)
+    }
+
+    /// Returns the extension of the file to open, for extension-mapped configs.
+    fn file_extension(&self) -> Option<&str> {
+        self.file.as_deref()?.extension()?.to_str()
+    }
+
+    /// Formats the target as `file[:line[:column]]` with 1-indexed values.
+    fn location(&self) -> Result<String> {
+        let file = self.file.as_ref().ok_or(Error::NoFileSpecified)?;
+        let file = file.display();
+        Ok(match self.position()? {
+            (Some(line), Some(column)) => format!("{file}:{line}:{column}"),
+            (Some(line), None) => format!("{file}:{line}"),
+            _ => file.to_string(),
+        })
+    }
+
+    /// Swaps a terminal editor for the `no_tty_fallback` editor if needed.
+    ///
+    /// The fallback applies only when there is no TTY and terminal editors
+    /// would run inline.
+    fn apply_no_tty_fallback(
+        &self,
+        env: &dyn Environment,
+        editor: DetectedEditor,
+        launcher: TerminalLauncher,
+        has_tty: bool,
+    ) -> Result<DetectedEditor> {
+        match self.no_tty_fallback {
+            Some(fallback)
+                if editor.is_terminal_editor()
+                    && !has_tty
+                    && launcher == TerminalLauncher::Inline =>
+            {
+                find_editor_by_kind(env, fallback)
+            }
+            _ => Ok(editor),
+        }
+    }
+
+    /// Returns `true` if `editor`, when unknown, gets `+LINE` positioning.
+    fn uses_plus_line_fallback(&self, editor: &DetectedEditor) -> bool {
+        self.plus_line_fallback || takes_plus_line(editor.kind, Some(&editor.binary))
+    }
+
+    /// Returns `true` if an unknown editor should be run as a terminal editor.
+    ///
+    /// Applies to unknown editors named by `$VISUAL` or `$EDITOR` when
+    /// stdin is a TTY, unless disabled via `assume_terminal_for_unknown`.
+    fn assumes_terminal(&self, editor: &DetectedEditor, has_tty: bool) -> bool {
+        let from_editor_var = matches!(
+            editor.source,
+            EditorSource::Environment(ref var) if var == "VISUAL" || var == "EDITOR"
+        );
+        editor.kind.is_unknown()
+            && from_editor_var
+            && has_tty
+            && self.assume_terminal_for_unknown.unwrap_or(true)
+    }
+
+    /// Verifies that a requested wait can be honored by the given editor.
+    ///
+    /// Only enforced in strict wait mode. An editor can wait exactly when
+    /// the plan would block with waiting requested (see [`blocks()`]).
+    fn check_wait(&self, editor: &DetectedEditor) -> Result<()> {
+        if self.wait && self.strict_wait && !blocks(editor, true) {
+            return Err(Error::WaitUnsupported {
+                editor: editor.kind,
+            });
+        }
+        Ok(())
+    }
+
+    /// Converts a character column to the column the editor expects.
+    ///
+    /// Falls back to the unconverted column if the line can't be read.
+    fn editor_column(&self, kind: EditorKind, file: &Path, line: u32, column: u32) -> u32 {
+        let semantics = self.column_semantics.unwrap_or_else(|| {
+            // `N|` counts screen columns rather than bytes
+            let vim = matches!(
+                kind,
+                EditorKind::Vim | EditorKind::NeoVim | EditorKind::Vi | EditorKind::GVim
+            );
+            if vim && self.vim_position_style == VimPositionStyle::GotoLineNormal {
+                ColumnSemantics::DisplayCell { tab_width: 8 }
+            } else {
+                kind.column_semantics()
+            }
+        });
+        if semantics == ColumnSemantics::Char {
+            return column;
+        }
+        read_line(file, line)
+            .and_then(|text| semantics.from_char_column(&text, column))
+            .unwrap_or(column)
+    }
+
+    /// Returns the requested line and column, converted to 1-indexed values.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidPosition` if a value is out of range for the
+    /// configured [`IndexBase`], and `Error::ColumnWithoutLine` if a column
+    /// is set without a line.
+    fn position(&self) -> Result<(Option<u32>, Option<u32>)> {
+        if let Some(&(line, column)) = self.positions.first() {
+            return Ok((
+                Some(self.to_one_based(line)?),
+                Some(self.to_one_based(column)?),
+            ));
+        }
+        if let (None, Some(column)) = (self.line, self.column) {
+            return Err(Error::ColumnWithoutLine { column });
+        }
+
+        let convert = |value: Option<u32>| value.map(|v| self.to_one_based(v)).transpose();
+        Ok((convert(self.line)?, convert(self.column)?))
+    }
+
+    /// Returns the positions after the first from `positions()`, 1-indexed.
+    fn extra_positions(&self) -> Result<Vec<(u32, u32)>> {
+        self.positions
+            .iter()
+            .skip(1)
+            .map(|&(line, column)| Ok((self.to_one_based(line)?, self.to_one_based(column)?)))
+            .collect()
+    }
+
+    /// Finds the position of the `pattern_in_range()` match, if a pattern is set.
+    #[cfg(feature = "regex")]
+    fn pattern_position(&self, file: &Path) -> Result<Option<(u32, u32)>> {
+        use crate::pattern::{PatternMiss, find_in_range};
+
+        let Some((ref regex, start, end)) = self.pattern else {
+            return Ok(None);
+        };
+        let start = self.to_one_based(start)?;
+        let end = self.to_one_based(end)?;
+        if start > end {
+            return Err(Error::InvalidPosition);
+        }
+
+        match (find_in_range(file, regex, start, end)?, self.pattern_miss) {
+            (Some(position), _) => Ok(Some(position)),
+            (None, PatternMiss::RangeStart) => Ok(Some((start, 1))),
+            (None, _) => Err(Error::InvalidPosition),
+        }
+    }
+
+    /// Converts a single line or column value to 1-indexed, clamping it
+    /// into range if `clamp_positions` is set.
+    fn to_one_based(&self, value: u32) -> Result<u32> {
+        match self.index_base.to_one_based(value) {
+            Some(value) => Ok(value),
+            None if self.clamp_positions => Ok(value.max(1)),
+            None => Err(Error::InvalidPosition),
+        }
+    }
+
+    /// Returns the resolution order to use when no editor is set explicitly.
+    fn effective_resolve_order(&self) -> &[ResolveFrom] {
+        if let Some(ref custom_order) = self.resolve_order {
+            // Use custom order if explicitly set
+            custom_order.as_slice()
+        } else if !self.configs.is_empty() {
+            // With configs, use default order (includes Config)
+            DEFAULT_RESOLVE_ORDER
+        } else {
+            // Without configs, use legacy behavior (env vars + PATH)
+            ENV_ONLY_RESOLVE_ORDER
+        }
+    }
+
+    /// Resolves which editor to use.
+    fn resolve_editor(&self) -> Result<DetectedEditor> {
+        self.resolve_editor_in(&self.environment())
+    }
+
+    /// Resolves which editor to use, looking editors up in `env`.
+    fn resolve_editor_in(&self, env: &dyn Environment) -> Result<DetectedEditor> {
+        if let Some(ref message) = self.editor_conflict {
+            return Err(Error::InvalidConfig {
+                message: message.clone(),
+            });
+        }
+
+        // If an explicit editor was set via .editor() or .editor_binary(), use it
+        // This always takes highest priority and bypasses all resolution logic
+        let editor = match self.editor {
+            Some(EditorSpec::Kind(kind)) => find_editor_by_kind(env, kind)
+                .or_else(|e| self.assume_in_login_shell(e, kind, kind.default_binary()))?,
+            Some(EditorSpec::Binary(ref binary)) => find_editor(env, binary).or_else(|e| {
+                self.assume_in_login_shell(e, EditorKind::from_binary(binary), binary)
+            })?,
+            None => self.resolve_with_order(env)?,
+        };
+        Ok(self.locate(editor))
+    }
+
+    /// Resolves an editor from the configured sources, explaining a failure
+    /// if verbose errors are enabled.
+    fn resolve_with_order(&self, env: &dyn Environment) -> Result<DetectedEditor> {
+        let order = self.effective_resolve_order();
+        let extension = self.file_extension();
+        let verify = self.verifies_editors();
+        if self.strict && order.contains(&ResolveFrom::Config) {
+            self.check_configs(env, extension)?;
+        }
+        match resolve_editor_with_order(env, order, &self.configs, extension, verify) {
+            Err(Error::NoEditorFound) if self.verbose_errors => {
+                Err(Error::NoEditorFoundDetailed(Box::new(ResolutionFailure {
+                    tried: explain_failure(env, order, &self.configs, extension),
+                })))
+            }
+            result => result,
+        }
+    }
+
+    /// Returns whether editors from environment variables must be installed.
+    fn verifies_editors(&self) -> bool {
+        self.verify_editor_exists
+            .unwrap_or(!(self.via_login_shell && cfg!(unix)))
+    }
+
+    /// Fails if configs name editors but none of them is installed.
+    fn check_configs(&self, env: &dyn Environment, extension: Option<&str>) -> Result<()> {
+        let configured = self.configs.iter().find_map(|config| {
+            config.editor.clone().or_else(|| {
+                config
+                    .editor_kind
+                    .as_ref()
+                    .map(|kind| kind.0.default_binary().to_string())
+            })
+        });
+        let Some(binary) = configured else {
+            return Ok(());
+        };
+        match resolve_editor_with_order(env, &[ResolveFrom::Config], &self.configs, extension, true)
+        {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::ConfigEditorNotFound { binary }),
+        }
+    }
+
+    /// Accepts an explicit editor that wasn't found, on the assumption the
+    /// login shell's `PATH` has it.
+    fn assume_in_login_shell(
+        &self,
+        error: Error,
+        kind: EditorKind,
+        binary: &str,
+    ) -> Result<DetectedEditor> {
+        match error {
+            Error::EditorNotFound { .. } if self.via_login_shell && cfg!(unix) => {
+                Ok(DetectedEditor {
+                    binary: binary.to_string(),
+                    kind,
+                    extra_args: Vec::new(),
+                    source: EditorSource::Explicit,
+                })
+            }
+            error => Err(error),
+        }
+    }
+
+    /// Returns the argument template of the config `editor` was resolved from.
+    ///
+    /// Editors picked through the config's `extension_map` don't use it.
+    fn args_template(&self, editor: &DetectedEditor) -> Option<Vec<String>> {
+        let EditorSource::Config { index } = editor.source else {
+            return None;
+        };
+        let config = self
+            .configs
+            .get(index)
+            .filter(|config| !config.args_template.is_empty())?;
+        let from_config = config.editor.as_deref() == Some(editor.binary.as_str())
+            || config
+                .editor_kind
+                .as_ref()
+                .is_some_and(|kind| kind.0 == editor.kind);
+        let mapped = self
+            .file_extension()
+            .and_then(|extension| config.editor_for_extension(extension))
+            .is_some_and(|kind| kind == editor.kind);
+        (from_config || !mapped).then(|| config.args_template.clone())
+    }
+
+    /// Returns the login shell to start `editor` through, if enabled and the
+    /// binary can't be found directly.
+    fn login_shell_for(&self, editor: &DetectedEditor) -> Option<String> {
+        if !self.via_login_shell
+            || !cfg!(unix)
+            || self.environment().which(&editor.binary).is_some()
+        {
+            return None;
+        }
+        Some(posix_login_shell(SystemEnvironment.var("SHELL").as_deref()))
+    }
+
+    /// Points an editor found only in a search directory at its full path.
+    ///
+    /// Editors in `PATH` keep their bare binary name so they are spawned
+    /// exactly as configured.
+    fn locate(&self, mut editor: DetectedEditor) -> DetectedEditor {
+        if self.search_dirs.is_empty() || SystemEnvironment.which(&editor.binary).is_some() {
+            return editor;
+        }
+        if let Some(path) = self.environment().which(&editor.binary) {
+            editor.binary = path.to_string_lossy().into_owned();
+        }
+        editor
+    }
+}
+
+/// What opening a file will do, resolved before anything is started.
+///
+/// Returned by [`EditorBuilder::inspect()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ResolvedPlan {
+    /// The editor that will be started.
+    pub editor: DetectedEditor,
+    /// The program that will be spawned.
+    ///
+    /// Usually the editor binary; a terminal emulator or login shell when
+    /// the editor is wrapped in one.
+    pub program: String,
+    /// The arguments passed to [`program`](Self::program).
+    pub args: Vec<String>,
+    /// Whether [`execute()`](Self::execute) blocks until the file is closed
+    /// (see [`EditorKind::will_block()`]).
+    pub will_block: bool,
+    /// How the editor was resolved.
+    pub source: EditorSource,
+    /// The working directory the editor starts in, if not the current one
+    /// (see [`EditorBuilder::current_dir()`]).
+    pub current_dir: Option<PathBuf>,
+    command: Command,
+    /// Return once a GUI editor that stays in the foreground has started.
+    detach: bool,
+    activate: bool,
+    /// File to create before starting the editor, and whether to create its
+    /// parent directories.
+    create: Option<(PathBuf, bool)>,
+    /// `--user-data-dir` of an isolated launch, removed when the plan is
+    /// dropped unless the editor may still be running.
+    isolation_dir: Option<TempDir>,
+}
+
+impl ResolvedPlan {
+    /// Starts the editor and waits for it to exit.
+    ///
+    /// GUI editors that stay in the foreground (Kate, Notepad, ...) are
+    /// detached instead unless waiting was requested, so this returns as
+    /// soon as they start (see [`will_block`](Self::will_block)); their
+    /// exit status is then not checked. Unlike [`EditorBuilder::open()`],
+    /// this doesn't fall back to other candidates if the planned editor
+    /// can't start.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Io` if the file can't be created, `Error::SpawnFailed`
+    /// if the editor can't be started, and `Error::EditorFailed` or
+    /// `Error::EditorTerminated` if it doesn't exit successfully.
+    pub fn execute(mut self) -> Result<()> {
+        let mut child = self.start()?;
+        // Bring the window forward while the user edits, not after
+        if self.activate {
+            activate(self.editor.kind);
+        }
+        if !self.will_block {
+            // The editor outlives this call and still uses its data directory
+            self.keep_isolation_dir();
+        }
+        if self.detach {
+            // Reap the editor in the background so it doesn't linger as a zombie
+            std::thread::spawn(move || child.wait());
+        } else {
+            let status = child.wait()?;
+            check_status(self.editor.binary, status)?;
+        }
+        Ok(())
+    }
+
+    /// Starts the editor without waiting for it.
+    ///
+    /// The child inherits the caller's stdin, stdout, and stderr, so
+    /// terminal editors still get the terminal. The caller is responsible
+    /// for reaping it with [`Child::wait()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Io` if the file can't be created, and
+    /// `Error::SpawnFailed` if the editor can't be started.
+    pub fn spawn(mut self) -> Result<Child> {
+        let child = self.start()?;
+        self.keep_isolation_dir();
+        Ok(child)
+    }
+
+    /// Leaves the isolated data directory in place for an editor that
+    /// outlives the plan.
+    fn keep_isolation_dir(&mut self) {
+        if let Some(dir) = self.isolation_dir.take() {
+            let _ = dir.keep();
+        }
+    }
+
+    /// Creates the file if planned, then spawns the command.
+    fn start(&mut self) -> Result<Child> {
+        trace_event!(
+            debug,
+            program = %self.program,
+            args = ?self.args,
+            editor = %self.editor.kind,
+            source = %self.source,
+            "starting editor"
+        );
+        if let Some((ref file, parents)) = self.create {
+            create_missing(file, parents)?;
+        }
+        self.command.spawn().map_err(|e| Error::SpawnFailed {
+            binary: self.editor.binary.clone(),
+            source: e,
+        })
+    }
+}
+
+/// Reads a single 1-indexed line from a file, without its line terminator.
+///
+/// Returns `None` if the file can't be read or has fewer lines.
+fn read_line(path: &Path, line: u32) -> Option<String> {
+    let index = usize::try_from(line.checked_sub(1)?).ok()?;
+    let file = std::fs::File::open(path).ok()?;
+    std::io::BufReader::new(file).lines().nth(index)?.ok()
+}
+
+/// Returns `true` if `editor` opens in its own window rather than the terminal.
+///
+/// Unknown editors and `emacsclient` opening a terminal frame count as
+/// terminal editors.
+fn is_gui(editor: &DetectedEditor) -> bool {
+    editor.kind.is_gui_editor() && !is_tty_emacsclient(editor)
+}
+
+/// Returns `true` if running `editor` lasts until the file is closed.
+///
+/// Terminal editors always do; GUI editors as [`EditorKind::will_block()`]
+/// says.
+fn blocks(editor: &DetectedEditor, wait: bool) -> bool {
+    !is_gui(editor) || editor.kind.will_block(wait)
+}
+
+/// Maps an editor's exit status to an error if it did not succeed.
+fn check_status(binary: String, status: std::process::ExitStatus) -> Result<()> {
+    if status.success() {
+        return Ok(());
+    }
+    match status.code() {
+        Some(code) => Err(Error::EditorFailed {
+            binary,
+            status: code,
+        }),
+        None => Err(Error::EditorTerminated { binary }),
+    }
+}
+
+/// Creates `file` as an empty file unless it already exists.
+fn create_missing(file: &Path, parents: bool) -> Result<()> {
+    if parents {
+        if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    match std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(file)
+    {
+        Err(e) if e.kind() != std::io::ErrorKind::AlreadyExists => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the line holding `anchor` in `file`.
+fn anchor_line(file: &Path, anchor: &str) -> Result<u32> {
+    let missing = || Error::AnchorNotFound {
+        anchor: anchor.to_string(),
+        path: file.to_path_buf(),
+    };
+    found_line(file, crate::anchor::find_anchor(file, anchor), missing)
+}
+
+/// Returns the line of the first conflict marker in `file`.
+fn conflict_line(file: &Path) -> Result<u32> {
+    let missing = || Error::ConflictNotFound {
+        path: file.to_path_buf(),
+    };
+    found_line(file, crate::anchor::find_conflict(file), missing)
+}
+
+/// Maps the result of a line scan of `file` to the line or an error.
+fn found_line(
+    file: &Path,
+    found: std::io::Result<Option<u32>>,
+    missing: impl FnOnce() -> Error,
+) -> Result<u32> {
+    match found {
+        Ok(Some(line)) => Ok(line),
+        Ok(None) => Err(missing()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(Error::FileNotFound {
+            path: file.to_path_buf(),
+        }),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Rejects encoding names that could smuggle extra editor commands.
+///
+/// Vim receives the name inside an ex command, where `|` would start a new
+/// command.
+fn check_encoding(encoding: &str) -> Result<()> {
+    let valid = !encoding.is_empty()
+        && encoding
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidConfig {
+            message: format!("invalid encoding name: {encoding:?}"),
+        })
+    }
+}
+
+/// Rejects values that a shell would split into separate commands.
+fn check_sanitized(what: &str, value: &str) -> Result<()> {
+    if value.contains(['\n', '\r', '\0']) {
+        return Err(Error::InvalidConfig {
+            message: format!("{what} contains a line break or NUL byte: {value:?}"),
+        });
+    }
+    Ok(())
+}
+
+/// Brings an editor's window to the front with `open -a` (best effort).
+fn activate(kind: EditorKind) {
+    if let Some(args) = activate_args(kind) {
+        let _ = std::process::Command::new("open")
+            .args(args)
+            .stdin(std::process::Stdio::null())
+            .status();
+    }
+}
+
+/// Creates the scratch directory for an isolated launch.
+///
+/// Each launch gets a fresh, randomly named directory that only the current
+/// user can access, so no one else can plant or read profile data in it.
+fn isolation_dir() -> Result<TempDir> {
+    let mut builder = tempfile::Builder::new();
+    builder.prefix("opensesame-isolated-");
+    #[cfg(unix)]
+    builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o700));
+    Ok(builder.tempdir()?)
+}
+
+/// Parses a file argument as a remote target, unless it exists locally.
+fn remote_target(file: &Path) -> Option<RemoteFile> {
+    if file.exists() {
+        return None;
+    }
+    file.to_str().and_then(RemoteFile::parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::build_args;
+    use crate::env::FakeEnvironment;
+
+    /// Returns an explicitly chosen editor of `kind` with its default binary.
+    fn explicit(kind: EditorKind) -> DetectedEditor {
+        DetectedEditor {
+            binary: kind.default_binary().to_string(),
+            kind,
+            extra_args: Vec::new(),
+            source: EditorSource::Explicit,
+        }
+    }
+
+    #[test]
+    fn test_read_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.rs");
+        std::fs::write(&path, "first\n\tsecond\r\nthird").unwrap();
+
+        assert_eq!(read_line(&path, 1).as_deref(), Some("first"));
+        assert_eq!(read_line(&path, 2).as_deref(), Some("\tsecond"));
+        assert_eq!(read_line(&path, 3).as_deref(), Some("third"));
+        assert_eq!(read_line(&path, 4), None);
+        assert_eq!(read_line(&path, 0), None);
+        assert_eq!(read_line(&dir.path().join("missing.rs"), 1), None);
+    }
+
+    #[test]
+    fn test_builder_no_file_error() {
+        let result = Editor::builder().open();
+        assert!(matches!(result, Err(Error::NoFileSpecified)));
+    }
+
+    #[test]
+    fn test_builder_invalid_position() {
+        let result = Editor::builder().file("test.rs").line(0).open();
+        assert!(matches!(result, Err(Error::InvalidPosition)));
+
+        let result = Editor::builder().file("test.rs").line(1).column(0).open();
+        assert!(matches!(result, Err(Error::InvalidPosition)));
+    }
+
+    #[test]
+    fn test_clamp_positions() {
+        let builder = Editor::builder().file("src/editor.rs").line(0).column(0);
+        assert!(matches!(builder.position(), Err(Error::InvalidPosition)));
+
+        let builder = builder.clamp_positions(true);
+        assert_eq!(builder.position().unwrap(), (Some(1), Some(1)));
+
+        let builder = Editor::builder()
+            .positions(&[(3, 0), (0, 7)])
+            .clamp_positions(true);
+        assert_eq!(builder.position().unwrap(), (Some(3), Some(1)));
+        assert_eq!(builder.extra_positions().unwrap(), [(1, 7)]);
+
+        let builder = Editor::builder()
+            .line(u32::MAX)
+            .index_base(IndexBase::ZeroBased)
+            .clamp_positions(true);
+        assert_eq!(builder.position().unwrap(), (Some(u32::MAX), None));
+    }
+
+    #[test]
+    fn test_clamp_positions_opens_at_first_line() {
+        let env = FakeEnvironment::new().with_binary("code");
+        let builder = || {
+            Editor::builder()
+                .file("src/editor.rs")
+                .line(0)
+                .editor_binary("code")
+        };
+
+        assert!(matches!(
+            builder().command_line_in(&env),
+            Err(Error::InvalidPosition)
+        ));
+        let (_, args) = builder()
+            .clamp_positions(true)
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(args, ["--goto", "src/editor.rs:1"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_isolated_data_dir_is_private_and_per_launch() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, _) = stand_in_editor(dir.path(), "code");
+        let builder = || {
+            Editor::builder()
+                .file("src/editor.rs")
+                .isolated(true)
+                .editor_binary(binary.to_str().unwrap())
+        };
+
+        let first = builder().inspect().unwrap();
+        let second = builder().inspect().unwrap();
+        assert_eq!(first.args[0], "--user-data-dir");
+        assert_ne!(first.args[1], second.args[1]);
+        let data_dir = PathBuf::from(&first.args[1]);
+        let mode = std::fs::metadata(&data_dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        // Removed with the plan, or once an editor that's waited for exits
+        drop(first);
+        assert!(!data_dir.exists());
+        let plan = builder().wait(true).inspect().unwrap();
+        let data_dir = PathBuf::from(&plan.args[1]);
+        plan.execute().unwrap();
+        assert!(!data_dir.exists());
+
+        // Kept for an editor that returns while it's still open
+        let plan = builder().inspect().unwrap();
+        let data_dir = PathBuf::from(&plan.args[1]);
+        plan.execute().unwrap();
+        assert!(data_dir.is_dir());
+        std::fs::remove_dir(data_dir).unwrap();
+    }
+
+    #[test]
+    fn test_builder_column_without_line() {
+        let result = Editor::builder().file("src/editor.rs").column(10).open();
+        assert!(
+            matches!(result, Err(Error::ColumnWithoutLine { column: 10 })),
+            "{result:?}"
+        );
+
+        let result = Editor::builder()
+            .file("src/editor.rs")
+            .maybe_line(None)
+            .maybe_column(Some(3))
+            .command_line();
+        assert!(
+            matches!(result, Err(Error::ColumnWithoutLine { column: 3 })),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_builder_maybe_line_and_column() {
+        let builder = Editor::builder().maybe_line(None).maybe_column(None);
+        assert_eq!(builder.line, None);
+        assert_eq!(builder.column, None);
+
+        let builder = Editor::builder().maybe_line(Some(5)).maybe_column(Some(3));
+        assert_eq!(builder.line, Some(5));
+        assert_eq!(builder.column, Some(3));
+
+        // None does not clear a previously set value
+        let builder = builder.maybe_line(None).maybe_column(None);
+        assert_eq!(builder.line, Some(5));
+        assert_eq!(builder.column, Some(3));
+    }
+
+    #[test]
+    fn test_builder_file_ref_strips_fragment() {
+        let builder = Editor::builder().file_ref("src/editor.rs#L42");
+        assert_eq!(builder.file.as_deref(), Some(Path::new("src/editor.rs")));
+        assert_eq!(builder.line, Some(42));
+
+        let builder = Editor::builder().file_ref("src/editor.rs#L42-L50");
+        assert_eq!(builder.file.as_deref(), Some(Path::new("src/editor.rs")));
+        assert_eq!(builder.line, Some(42));
+        assert_eq!(builder.selection, Some(LineRange { start: 42, end: 50 }));
+
+        let builder = Editor::builder().line(7).file_ref("notes#1.md");
+        assert_eq!(builder.file.as_deref(), Some(Path::new("notes#1.md")));
+        assert_eq!(builder.line, Some(7));
+    }
+
+    #[test]
+    fn test_file_ref_range_selects_lines() {
+        let env = FakeEnvironment::new()
+            .with_binary("vim")
+            .with_binary("code");
+
+        let (_, args) = Editor::builder()
+            .file_ref("src/editor.rs#L42-L50")
+            .editor_binary("vim")
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(args, ["+42", "src/editor.rs", "-c", "normal! V50Go"]);
+
+        // Editors that can't select a range still go to its start
+        let (_, args) = Editor::builder()
+            .file_ref("src/editor.rs#L42-L50")
+            .editor_binary("code")
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(args, ["--goto", "src/editor.rs:42"]);
+
+        // Moving the cursor elsewhere drops the selection
+        let (_, args) = Editor::builder()
+            .file_ref("src/editor.rs#L42-L50")
+            .line(10)
+            .editor_binary("vim")
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(args, ["+10", "src/editor.rs"]);
+    }
+
+    #[test]
+    fn test_builder_file_ref_accepts_file_uri() {
+        let builder = Editor::builder().file_ref("file:///tmp/my%20project/main.rs#L10,5");
+        assert_eq!(
+            builder.file.as_deref(),
+            Some(Path::new("/tmp/my project/main.rs"))
+        );
+        assert_eq!((builder.line, builder.column), (Some(10), Some(5)));
+
+        let builder = Editor::builder()
+            .line(7)
+            .column(2)
+            .file_ref("file:///tmp/main.rs");
+        assert_eq!(builder.file.as_deref(), Some(Path::new("/tmp/main.rs")));
+        assert_eq!((builder.line, builder.column), (Some(7), Some(2)));
+    }
+
+    #[test]
+    fn test_open_or_print_falls_back_to_location() {
+        let mut output = Vec::new();
+        Editor::builder()
+            .file("src/editor.rs")
+            .line(42)
+            .column(7)
+            .resolve_order(&[])
+            .open_or_print(&mut output)
+            .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "src/editor.rs:42:7\n");
+
+        let mut output = Vec::new();
+        Editor::builder()
+            .file("src/editor.rs")
+            .resolve_order(&[])
+            .open_or_print(&mut output)
+            .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "src/editor.rs\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_open_or_print_opens_when_editor_available() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, log) = stand_in_editor(dir.path(), "code");
+        let mut output = Vec::new();
+        Editor::builder()
+            .file("src/editor.rs")
+            .line(42)
+            .editor_binary(binary.to_str().unwrap())
+            .open_or_print(&mut output)
+            .unwrap();
+        assert!(output.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(log).unwrap(),
+            "--goto src/editor.rs:42\n"
+        );
+    }
+
+    #[test]
+    fn test_open_or_print_keeps_other_errors() {
+        let mut output = Vec::new();
+        let result = Editor::builder()
+            .resolve_order(&[])
+            .open_or_print(&mut output);
+        assert!(matches!(result, Err(Error::NoFileSpecified)));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_builder_positions_override_line_and_column() {
+        let builder = Editor::builder()
+            .file("test.rs")
+            .line(1)
+            .positions(&[(10, 4), (20, 8)]);
+        assert_eq!(builder.position().unwrap(), (Some(10), Some(4)));
+        assert_eq!(builder.extra_positions().unwrap(), vec![(20, 8)]);
+
+        let result = Editor::builder()
+            .file("test.rs")
+            .positions(&[(1, 1), (0, 1)])
+            .open();
+        assert!(matches!(result, Err(Error::InvalidPosition)));
+    }
+
+    #[test]
+    fn test_builder_zero_based_position() {
+        let builder = Editor::builder()
+            .file("test.rs")
+            .index_base(IndexBase::ZeroBased)
+            .line(0)
+            .column(0);
+        assert_eq!(builder.position().unwrap(), (Some(1), Some(1)));
+    }
+
+    #[test]
+    fn test_builder_index_bases_produce_same_args() {
+        let one_based = Editor::builder()
+            .file("test.rs")
+            .line(42)
+            .column(10)
+            .position()
+            .unwrap();
+        let zero_based = Editor::builder()
+            .file("test.rs")
+            .index_base(IndexBase::ZeroBased)
+            .line(41)
+            .column(9)
+            .position()
+            .unwrap();
+        assert_eq!(one_based, zero_based);
+
+        let options = |(line, column)| ArgOptions {
+            line,
+            column,
+            ..Default::default()
+        };
+        let file = "test.rs";
+        for kind in [EditorKind::VsCode, EditorKind::Vim, EditorKind::Kate] {
+            assert_eq!(
+                build_args(kind, file, &options(one_based)),
+                build_args(kind, file, &options(zero_based)),
+            );
+        }
+    }
+
+    #[test]
+    fn test_builder_conflicting_editors_error() {
+        let result = Editor::builder()
+            .file("test.rs")
+            .editor(EditorKind::VsCode)
+            .editor_binary("nvim")
+            .open();
+
+        match result {
+            Err(Error::InvalidConfig { message }) => {
+                assert!(message.contains("VS Code"));
+                assert!(message.contains("nvim"));
+            }
+            other => panic!("expected InvalidConfig, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_builder_same_editor_twice_is_not_a_conflict() {
+        let builder = Editor::builder()
+            .editor(EditorKind::VsCode)
+            .editor(EditorKind::VsCode);
+        assert!(builder.editor_conflict.is_none());
+
+        let builder = Editor::builder()
+            .editor_binary("nvim")
+            .editor_binary("nvim");
+        assert!(builder.editor_conflict.is_none());
+    }
+
+    #[test]
+    fn test_edit_and_wait_forces_wait() {
+        let builder = Editor::edit_and_wait_builder("COMMIT_EDITMSG");
+        assert!(builder.wait);
+        assert!(builder.strict_wait);
+        assert_eq!(builder.file.as_deref(), Some(Path::new("COMMIT_EDITMSG")));
+    }
+
+    #[test]
+    fn test_edit_and_wait_rejects_editor_without_wait() {
+        let builder = Editor::edit_and_wait_builder("COMMIT_EDITMSG");
+
+        let result = builder.check_wait(&explicit(EditorKind::GVim));
+        assert!(matches!(
+            result,
+            Err(Error::WaitUnsupported {
+                editor: EditorKind::GVim
+            })
+        ));
+
+        // GUI editors with a wait flag and blocking terminal editors are fine
+        assert!(builder.check_wait(&explicit(EditorKind::VsCode)).is_ok());
+        assert!(builder.check_wait(&explicit(EditorKind::Vim)).is_ok());
+    }
+
+    #[test]
+    fn test_strict_wait_rejects_gui_editor_without_wait() {
+        let builder = Editor::builder()
+            .file("test.rs")
+            .wait(true)
+            .strict_wait(true);
+        // GVim forks into the background and has no wait flag
+        assert!(matches!(
+            builder.check_wait(&explicit(EditorKind::GVim)),
+            Err(Error::WaitUnsupported { .. })
+        ));
+
+        // Editors that stay in the foreground block when asked to wait
+        assert!(builder.check_wait(&explicit(EditorKind::Kate)).is_ok());
+        assert!(builder.check_wait(&explicit(EditorKind::Notepad)).is_ok());
+        assert!(builder.check_wait(&explicit(EditorKind::Emacs)).is_ok());
+    }
+
+    #[test]
+    fn test_strict_wait_agrees_with_will_block() {
+        let builder = Editor::builder()
+            .file("test.rs")
+            .wait(true)
+            .strict_wait(true);
+        for &kind in EditorKind::all() {
+            let editor = explicit(kind);
+            assert_eq!(
+                builder.check_wait(&editor).is_ok(),
+                blocks(&editor, true),
+                "{kind:?}"
+            );
+            if kind.is_gui_editor() {
+                assert_eq!(
+                    builder.check_wait(&editor).is_ok(),
+                    kind.will_block(true),
+                    "{kind:?}"
+                );
+            }
+        }
+
+        // Hinted terminal editors and terminal emacsclient frames block
+        let micro = DetectedEditor {
+            binary: "micro".to_string(),
+            ..explicit(EditorKind::Unknown)
+        };
+        assert!(builder.check_wait(&micro).is_ok());
+        let emacsclient = DetectedEditor {
+            extra_args: vec!["-nw".to_string()],
+            ..explicit(EditorKind::EmacsClient)
+        };
+        assert!(builder.check_wait(&emacsclient).is_ok());
+    }
+
+    #[test]
+    fn test_strict_wait_allows_terminal_editor() {
+        let builder = Editor::builder()
+            .file("test.rs")
+            .wait(true)
+            .strict_wait(true);
+        assert!(builder.check_wait(&explicit(EditorKind::Nano)).is_ok());
+        assert!(builder.check_wait(&explicit(EditorKind::Helix)).is_ok());
+    }
+
+    #[test]
+    fn test_lenient_wait_is_default() {
+        let builder = Editor::builder().file("test.rs").wait(true);
+        assert!(!builder.strict_wait);
+        assert!(builder.check_wait(&explicit(EditorKind::GVim)).is_ok());
+
+        // Strict mode has no effect without a wait request
+        let builder = Editor::builder().file("test.rs").strict_wait(true);
+        assert!(builder.check_wait(&explicit(EditorKind::GVim)).is_ok());
+    }
+
+    #[test]
+    fn test_editor_column_uses_native_semantics() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.rs");
+        std::fs::write(&file, "fn main() {\n\tlet λ = 1;\n}\n").unwrap();
+        let builder = Editor::builder();
+
+        // Column 8 is the '=' after "\tlet λ "
+        assert_eq!(builder.editor_column(EditorKind::VsCode, &file, 2, 8), 8);
+        assert_eq!(builder.editor_column(EditorKind::Vim, &file, 2, 8), 9);
+        assert_eq!(builder.editor_column(EditorKind::Emacs, &file, 2, 8), 15);
+    }
+
+    #[test]
+    fn test_editor_column_with_custom_tab_width() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.rs");
+        std::fs::write(&file, "\t\tx\n").unwrap();
+
+        for (tab_width, expected) in [(2, 5), (4, 9), (8, 17)] {
+            let builder =
+                Editor::builder().column_semantics(ColumnSemantics::DisplayCell { tab_width });
+            assert_eq!(
+                builder.editor_column(EditorKind::Kate, &file, 1, 3),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_editor_column_vim_normal_style_uses_screen_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.rs");
+        std::fs::write(&file, "\tλx\n").unwrap();
+
+        let builder = Editor::builder();
+        assert_eq!(builder.editor_column(EditorKind::Vim, &file, 1, 3), 4);
+        let builder = builder.vim_position_style(VimPositionStyle::GotoLineNormal);
+        assert_eq!(builder.editor_column(EditorKind::Vim, &file, 1, 3), 10);
+        assert_eq!(builder.editor_column(EditorKind::VsCode, &file, 1, 3), 3);
+    }
+
+    #[test]
+    fn test_editor_column_falls_back_when_unreadable() {
+        let builder = Editor::builder();
+        let missing = Path::new("/nonexistent/opensesame/test.rs");
+        assert_eq!(builder.editor_column(EditorKind::Vim, missing, 2, 8), 8);
+    }
+
+    #[test]
+    fn test_into_spawn_fn_is_send_and_runs_on_thread() {
+        fn assert_send<T: Send + 'static>(_: &T) {}
+
+        let open = Editor::builder().line(42).into_spawn_fn();
+        assert_send(&open);
+
+        let result = std::thread::spawn(open).join().unwrap();
+        assert!(matches!(result, Err(Error::NoFileSpecified)));
+    }
+
+    #[test]
+    fn test_remote_target_detection() {
+        let remote = remote_target(Path::new("alice@devbox:/srv/main.rs")).unwrap();
+        assert_eq!(remote.host, "devbox");
+        assert!(remote_target(Path::new("scp://devbox/main.rs")).is_some());
+        assert!(remote_target(Path::new("src/editor.rs")).is_none());
+
+        // Local files that happen to look remote are opened locally
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("alice@devbox:notes.txt");
+        std::fs::write(&local, "").unwrap();
+        assert!(remote_target(&local).is_none());
+    }
+
+    #[test]
+    fn test_no_tty_fallback_replaces_terminal_editor() {
+        let env = crate::env::FakeEnvironment::new().with_binary("code");
+        let builder = Editor::builder().no_tty_fallback(EditorKind::VsCode);
+        let vim = find_editor(
+            &crate::env::FakeEnvironment::new().with_binary("vim"),
+            "vim",
+        )
+        .unwrap();
+
+        let editor = builder
+            .apply_no_tty_fallback(&env, vim.clone(), TerminalLauncher::Inline, false)
+            .unwrap();
+        assert_eq!(editor.kind, EditorKind::VsCode);
+        assert_eq!(editor.binary, "code");
+
+        // With a TTY, or a launcher providing a terminal, vim is kept
+        let editor = builder
+            .apply_no_tty_fallback(&env, vim.clone(), TerminalLauncher::Inline, true)
+            .unwrap();
+        assert_eq!(editor.kind, EditorKind::Vim);
+        let editor = builder
+            .apply_no_tty_fallback(&env, vim, TerminalLauncher::Kitty, false)
+            .unwrap();
+        assert_eq!(editor.kind, EditorKind::Vim);
+    }
+
+    #[test]
+    fn test_unknown_editor_from_env_assumes_terminal() {
+        let env = crate::env::FakeEnvironment::new()
+            .with_var("EDITOR", "micro")
+            .with_binary("micro");
+        let micro = crate::detect::try_env_var(&env, "EDITOR").unwrap();
+        assert_eq!(micro.kind, EditorKind::Unknown);
+
+        let builder = Editor::builder();
+        assert!(builder.assumes_terminal(&micro, true));
+        assert!(!builder.assumes_terminal(&micro, false));
+
+        let builder = Editor::builder().assume_terminal_for_unknown(false);
+        assert!(!builder.assumes_terminal(&micro, true));
+    }
+
+    #[test]
+    fn test_unknown_editor_from_elsewhere_not_assumed_terminal() {
+        let env = crate::env::FakeEnvironment::new().with_binary("micro");
+        let explicit = find_editor(&env, "micro").unwrap();
+        assert!(!Editor::builder().assumes_terminal(&explicit, true));
+
+        // Known editors are never "assumed"; their kind decides
+        let env = env.with_var("EDITOR", "code").with_binary("code");
+        let code = crate::detect::try_env_var(&env, "EDITOR").unwrap();
+        assert!(!Editor::builder().assumes_terminal(&code, true));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_pattern_in_range_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(
+            &path,
+            "// TODO: top\nfn run() {\n    todo!(); // TODO: fix\n}\n",
+        )
+        .unwrap();
+        let todo = regex::Regex::new("TODO").unwrap();
+
+        let builder = Editor::builder().pattern_in_range(todo.clone(), 2, 4);
+        assert_eq!(builder.pattern_position(&path).unwrap(), Some((3, 17)));
+
+        let builder = Editor::builder()
+            .index_base(IndexBase::ZeroBased)
+            .pattern_in_range(todo, 0, 1);
+        assert_eq!(builder.pattern_position(&path).unwrap(), Some((1, 4)));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_pattern_in_range_not_found() {
+        use crate::pattern::PatternMiss;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "fn run() {\n}\n").unwrap();
+        let fixme = regex::Regex::new("FIXME").unwrap();
+
+        let builder = Editor::builder().pattern_in_range(fixme.clone(), 1, 2);
+        assert!(matches!(
+            builder.pattern_position(&path),
+            Err(Error::InvalidPosition)
+        ));
+
+        let builder = builder.pattern_miss(PatternMiss::RangeStart);
+        assert_eq!(builder.pattern_position(&path).unwrap(), Some((1, 1)));
+
+        let builder = Editor::builder().pattern_in_range(fixme, 5, 2);
+        assert!(matches!(
+            builder.pattern_position(&path),
+            Err(Error::InvalidPosition)
+        ));
+
+        assert_eq!(Editor::builder().pattern_position(&path).unwrap(), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_follow_symlinks_resolves_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("dotfiles").join("vimrc");
+        std::fs::create_dir(target.parent().unwrap()).unwrap();
+        std::fs::write(&target, "set number\n").unwrap();
+        let link = dir.path().join(".vimrc");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let builder = Editor::builder().file(&link);
+        assert_eq!(builder.target_file().unwrap(), link);
+
+        let builder = builder.follow_symlinks(true);
+        assert_eq!(
+            builder.target_file().unwrap(),
+            std::fs::canonicalize(&target).unwrap()
+        );
+
+        // Unresolvable paths are kept as given
+        let missing = dir.path().join("missing.rs");
+        let builder = Editor::builder().file(&missing).follow_symlinks(true);
+        assert_eq!(builder.target_file().unwrap(), missing);
+    }
+
+    /// Writes a stand-in editor named `name` that appends its arguments to `log`.
+    #[cfg(unix)]
+    fn stand_in_editor(dir: &Path, name: &str) -> (PathBuf, PathBuf) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let log = dir.join("args.log");
+        let binary = dir.join(name);
+        std::fs::write(
+            &binary,
+            format!("#!/bin/sh\necho \"$@\" >> '{}'\n", log.display()),
+        )
+        .unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+        (binary, log)
+    }
+
+    /// Writes a stand-in editor named `name` that exits with status 3.
+    ///
+    /// Only a launch that waits for the editor sees the failure, so the
+    /// result tells blocking and detached launches apart.
+    #[cfg(unix)]
+    fn failing_editor(dir: &Path, name: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let binary = dir.join(name);
+        std::fs::write(&binary, "#!/bin/sh\nexit 3\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+        binary
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_gui_editor_detaches_unless_waiting() {
+        let dir = tempfile::tempdir().unwrap();
+        let kate = failing_editor(dir.path(), "kate");
+        let builder = || {
+            Editor::builder()
+                .file("src/editor.rs")
+                .editor_binary(kate.to_str().unwrap())
+        };
+
+        let plan = builder().inspect().unwrap();
+        assert!(!plan.will_block);
+        plan.execute().unwrap();
+
+        let result = builder().wait(true).open();
+        assert!(
+            matches!(result, Err(Error::EditorFailed { .. })),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_terminal_editors_still_block() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["vim", "nano", "hx"] {
+            let binary = failing_editor(dir.path(), name);
+            let result = Editor::builder()
+                .file("src/editor.rs")
+                .editor_binary(binary.to_str().unwrap())
+                .open();
+            assert!(
+                matches!(result, Err(Error::EditorFailed { .. })),
+                "{name}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_via_login_shell_accepts_editor_missing_from_path() {
+        let builder = Editor::builder().editor_binary("opensesame-login-only");
+        assert!(builder.resolve_all().is_empty());
+
+        let builder = builder.via_login_shell(true);
+        let resolved = builder.resolve_all();
+        assert_eq!(resolved.len(), 1);
+        assert!(builder.login_shell_for(&resolved[0]).is_some());
+
+        let sh = DetectedEditor {
+            binary: "sh".to_string(),
+            kind: EditorKind::Unknown,
+            extra_args: Vec::new(),
+            source: EditorSource::Explicit,
+        };
+        assert_eq!(builder.login_shell_for(&sh), None);
+    }
+
+    #[test]
+    fn test_verbose_errors_lists_tried_sources() {
+        let builder = || {
+            Editor::builder()
+                .file("test.rs")
+                .with_config(EditorConfig::with_editor("opensesame-missing-editor"))
+                .resolve_order(&[ResolveFrom::Config])
+        };
+
+        let err = builder().open().unwrap_err();
+        assert!(matches!(err, Error::NoEditorFound));
+        assert!(err.tried().is_empty());
+
+        let err = builder().verbose_errors(true).open().unwrap_err();
+        assert!(matches!(err, Error::NoEditorFoundDetailed(_)));
+        assert_eq!(
+            err.tried(),
+            [(
+                ResolveFrom::Config,
+                "config 0: 'opensesame-missing-editor' not found in PATH".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_strict_errors_on_missing_config_editor() {
+        let env = crate::env::FakeEnvironment::new()
+            .with_var("EDITOR", "nano")
+            .with_binary("nano")
+            .with_binary("vim");
+        let builder = || {
+            Editor::builder().with_config(EditorConfig::with_editor("opensesame-missing-editor"))
+        };
+
+        let err = builder().strict(true).resolve_with_order(&env).unwrap_err();
+        assert!(err.is_editor_not_found());
+        assert!(matches!(
+            err,
+            Error::ConfigEditorNotFound { ref binary } if binary == "opensesame-missing-editor"
+        ));
+
+        // Non-strict resolution falls through to $EDITOR
+        let editor = builder().resolve_with_order(&env).unwrap();
+        assert_eq!(editor.binary, "nano");
+        assert_eq!(
+            editor.source,
+            EditorSource::Environment("EDITOR".to_string())
+        );
+    }
+
+    #[test]
+    fn test_strict_accepts_installed_or_absent_config_editor() {
+        let env = crate::env::FakeEnvironment::new()
+            .with_binary("nano")
+            .with_binary("vim");
+
+        // A later config covers a missing editor in an earlier one
+        let editor = Editor::builder()
+            .with_config(EditorConfig::with_editor_kind(EditorKind::Helix))
+            .with_config(EditorConfig::with_editor("vim"))
+            .strict(true)
+            .resolve_with_order(&env)
+            .unwrap();
+        assert_eq!(editor.binary, "vim");
+        assert_eq!(editor.source, EditorSource::Config { index: 1 });
+
+        // Configs that name no editor, or a Config-less order, don't trigger it
+        let editor = Editor::builder()
+            .with_config(EditorConfig::new())
+            .strict(true)
+            .resolve_with_order(&env)
+            .unwrap();
+        assert_eq!(editor.source, EditorSource::PathSearch);
+        let editor = Editor::builder()
+            .with_config(EditorConfig::with_editor("opensesame-missing-editor"))
+            .resolve_order(&[ResolveFrom::PathSearch])
+            .strict(true)
+            .resolve_with_order(&env)
+            .unwrap();
+        assert_eq!(editor.source, EditorSource::PathSearch);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_spawn_returns_running_child() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, log) = stand_in_editor(dir.path(), "vim");
+
+        let mut child = Editor::builder()
+            .file("src/editor.rs")
+            .line(7)
+            .editor_binary(binary.to_str().unwrap())
+            .spawn()
+            .unwrap();
+        assert!(child.wait().unwrap().success());
+        assert_eq!(std::fs::read_to_string(log).unwrap(), "+7 src/editor.rs\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_spawn_leaves_exit_status_to_caller() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = failing_editor(dir.path(), "vim");
+        let builder = || {
+            Editor::builder()
+                .file("src/editor.rs")
+                .editor_binary(binary.to_str().unwrap())
+        };
+
+        let mut child = builder().spawn().unwrap();
+        assert_eq!(child.wait().unwrap().code(), Some(3));
+        assert!(matches!(
+            builder().open(),
+            Err(Error::EditorFailed { status: 3, .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_spawn_with_pid() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, log) = stand_in_editor(dir.path(), "code");
 
-        // Validate position (must be >= 1)
-        if let Some(line) = self.line {
-            if line == 0 {
-                return Err(Error::InvalidPosition);
-            }
-        }
-        if let Some(column) = self.column {
-            if column == 0 {
-                return Err(Error::InvalidPosition);
-            }
-        }
+        let (pid, mut child) = Editor::builder()
+            .file("src/editor.rs")
+            .line(42)
+            .editor_binary(binary.to_str().unwrap())
+            .spawn_with_pid()
+            .unwrap();
+        assert_ne!(pid, 0);
+        assert_eq!(pid, child.id());
+        assert!(child.wait().unwrap().success());
+        assert_eq!(
+            std::fs::read_to_string(log).unwrap(),
+            "--goto src/editor.rs:42\n"
+        );
+    }
 
-        // Resolve the editor
-        let editor = self.resolve_editor()?;
+    #[test]
+    fn test_terminal_editor_command_line() {
+        let env = FakeEnvironment::new().with_binary("nano");
 
-        // Build and execute the command
-        let mut cmd = build_command(&editor, &file, self.line, self.column, self.wait);
+        let plan = Editor::builder()
+            .file("README.md")
+            .line(3)
+            .editor_binary("nano")
+            .inspect_in(&env)
+            .unwrap();
+        assert_eq!(plan.program, "nano");
+        assert_eq!(plan.args, ["+3", "README.md"]);
+        assert!(plan.will_block);
+    }
 
-        // Execute
-        let status = cmd.status().map_err(|e| Error::SpawnFailed {
-            binary: editor.binary.clone(),
-            source: e,
-        })?;
+    #[test]
+    #[cfg(unix)]
+    fn test_create_if_missing_creates_empty_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, log) = stand_in_editor(dir.path(), "opensesame-stand-in");
+        let file = dir.path().join("new.txt");
 
-        // Check exit status
-        if !status.success() {
-            if let Some(code) = status.code() {
-                return Err(Error::EditorFailed {
-                    binary: editor.binary,
-                    status: code,
-                });
+        Editor::builder()
+            .file(&file)
+            .editor_binary(binary.to_str().unwrap())
+            .create_if_missing(true)
+            .open()
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "");
+        assert!(std::fs::read_to_string(log).unwrap().contains("new.txt"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_create_if_missing_keeps_existing_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, _) = stand_in_editor(dir.path(), "opensesame-stand-in");
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "keep me\n").unwrap();
+
+        Editor::builder()
+            .file(&file)
+            .editor_binary(binary.to_str().unwrap())
+            .create_if_missing(true)
+            .open()
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "keep me\n");
+    }
+
+    #[test]
+    fn test_missing_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = FakeEnvironment::new().with_binary("opensesame-stand-in");
+        let file = dir.path().join("new.txt");
+
+        let result = Editor::builder()
+            .file(&file)
+            .editor_binary("opensesame-stand-in")
+            .inspect_in(&env);
+        match result {
+            Err(e @ Error::FileNotFound { .. }) => {
+                assert!(e.is_file_not_found());
+                assert!(e.to_string().contains("new.txt"));
             }
-            return Err(Error::EditorTerminated {
-                binary: editor.binary,
-            });
+            other => panic!("expected FileNotFound, got {other:?}"),
         }
+        assert!(!file.exists());
 
-        Ok(())
+        let result = Editor::builder()
+            .file(&file)
+            .editor_binary("opensesame-stand-in")
+            .create_if_missing(false)
+            .inspect_in(&env);
+        assert!(matches!(result, Err(Error::FileNotFound { .. })));
     }
 
-    /// Resolves which editor to use.
-    fn resolve_editor(&self) -> Result<DetectedEditor> {
-        // If an explicit editor was set via .editor() or .editor_binary(), use it
-        // This always takes highest priority and bypasses all resolution logic
-        if let Some(ref spec) = self.editor {
-            return match spec {
-                EditorSpec::Kind(kind) => find_editor_by_kind(*kind),
-                EditorSpec::Binary(binary) => find_editor(binary),
-            };
-        }
+    #[test]
+    fn test_create_if_missing_skips_existence_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = FakeEnvironment::new().with_binary("idea");
+        let file = dir.path().join("new.kt");
 
-        // Determine the resolution order
-        let order = if let Some(ref custom_order) = self.resolve_order {
-            // Use custom order if explicitly set
-            custom_order.as_slice()
-        } else if !self.configs.is_empty() {
-            // With configs, use default order (includes Config)
-            DEFAULT_RESOLVE_ORDER
-        } else {
-            // Without configs, use legacy behavior (env vars + PATH)
-            ENV_ONLY_RESOLVE_ORDER
+        let plan = Editor::builder()
+            .file(&file)
+            .editor_binary("idea")
+            .create_if_missing(true)
+            .inspect_in(&env)
+            .unwrap();
+        assert_eq!(plan.args, [file.to_str().unwrap()]);
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_existing_directory_passes_existence_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = FakeEnvironment::new().with_binary("opensesame-stand-in");
+
+        let plan = Editor::builder()
+            .file(dir.path())
+            .editor_binary("opensesame-stand-in")
+            .inspect_in(&env)
+            .unwrap();
+        assert_eq!(plan.args, [dir.path().to_str().unwrap()]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_create_if_missing_parent_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, _) = stand_in_editor(dir.path(), "opensesame-stand-in");
+        let file = dir.path().join("a/b/new.txt");
+        let builder = || {
+            Editor::builder()
+                .file(&file)
+                .editor_binary(binary.to_str().unwrap())
+                .create_if_missing(true)
         };
 
-        resolve_editor_with_order(order, &self.configs)
+        assert!(matches!(builder().open(), Err(Error::Io(_))));
+        assert!(!file.exists());
+
+        builder().create_parent_dirs(true).open().unwrap();
+        assert!(file.is_file());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    #[cfg(unix)]
+    fn test_editor_search_dirs_finds_portable_install() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, _) = stand_in_editor(dir.path(), "opensesame-portable-edit");
+        let builder = Editor::builder()
+            .file("README.md")
+            .editor_binary("opensesame-portable-edit");
+        assert!(matches!(builder.resolve_all().as_slice(), []));
+
+        let builder = builder.editor_search_dirs(&[dir.path().to_path_buf()]);
+        let resolved = builder.resolve_all();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].binary, binary.to_str().unwrap());
+
+        let plan = builder.inspect().unwrap();
+        assert_eq!(plan.program, binary.to_str().unwrap());
+        assert_eq!(plan.args, ["README.md"]);
+    }
 
     #[test]
-    fn test_editor_kind_from_binary() {
-        assert_eq!(EditorKind::from_binary("code"), EditorKind::VsCode);
-        assert_eq!(EditorKind::from_binary("code.exe"), EditorKind::VsCode);
-        assert_eq!(EditorKind::from_binary("code.cmd"), EditorKind::VsCode);
-        assert_eq!(EditorKind::from_binary("/usr/bin/code"), EditorKind::VsCode);
-        assert_eq!(EditorKind::from_binary("vim"), EditorKind::Vim);
-        assert_eq!(EditorKind::from_binary("nvim"), EditorKind::NeoVim);
-        assert_eq!(EditorKind::from_binary("emacs"), EditorKind::Emacs);
-        assert_eq!(EditorKind::from_binary("subl"), EditorKind::Sublime);
-        assert_eq!(EditorKind::from_binary("zed"), EditorKind::Zed);
-        assert_eq!(EditorKind::from_binary("hx"), EditorKind::Helix);
-        assert_eq!(EditorKind::from_binary("nano"), EditorKind::Nano);
-        assert_eq!(EditorKind::from_binary("cursor"), EditorKind::Cursor);
-        assert_eq!(EditorKind::from_binary("windsurf"), EditorKind::Windsurf);
-        assert_eq!(EditorKind::from_binary("notepad++"), EditorKind::NotepadPlusPlus);
-        assert_eq!(EditorKind::from_binary("idea"), EditorKind::IntelliJ);
-        assert_eq!(EditorKind::from_binary("unknown-editor"), EditorKind::Unknown);
+    #[cfg(unix)]
+    fn test_resolve_reports_config_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, _) = stand_in_editor(dir.path(), "opensesame-config-edit");
+        let config = EditorConfig {
+            args: vec!["-p".to_string()],
+            ..EditorConfig::with_editor("opensesame-config-edit")
+        };
+        let builder = Editor::builder()
+            .with_config(EditorConfig::with_editor("opensesame-missing-editor"))
+            .with_config(config)
+            .editor_search_dirs(&[dir.path().to_path_buf()]);
+
+        let editor = builder.resolve().unwrap();
+        assert_eq!(editor.binary, binary.to_str().unwrap());
+        assert_eq!(editor.kind, EditorKind::Unknown);
+        assert_eq!(editor.extra_args, vec!["-p"]);
+        assert_eq!(editor.source, EditorSource::Config { index: 1 });
+
+        let err = Editor::builder()
+            .with_config(EditorConfig::with_editor("opensesame-missing-editor"))
+            .resolve_order(&[ResolveFrom::Config])
+            .resolve()
+            .unwrap_err();
+        assert!(matches!(err, Error::NoEditorFound));
     }
 
     #[test]
-    fn test_editor_kind_display() {
-        assert_eq!(EditorKind::VsCode.to_string(), "VS Code");
-        assert_eq!(EditorKind::NeoVim.to_string(), "NeoVim");
-        assert_eq!(EditorKind::Helix.to_string(), "Helix");
+    #[cfg(unix)]
+    fn test_open_reporting_returns_config_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_, log) = stand_in_editor(dir.path(), "opensesame-reporting-edit");
+        let source = Editor::builder()
+            .file("README.md")
+            .with_config(EditorConfig::with_editor("opensesame-reporting-edit"))
+            .editor_search_dirs(&[dir.path().to_path_buf()])
+            .open_reporting()
+            .unwrap();
+        assert_eq!(source, EditorSource::Config { index: 0 });
+        assert_eq!(std::fs::read_to_string(log).unwrap(), "README.md\n");
+
+        // The explicit editor reports itself as such
+        let dir = tempfile::tempdir().unwrap();
+        stand_in_editor(dir.path(), "opensesame-reporting-edit");
+        let source = Editor::builder()
+            .file("README.md")
+            .editor_binary("opensesame-reporting-edit")
+            .editor_search_dirs(&[dir.path().to_path_buf()])
+            .open_reporting()
+            .unwrap();
+        assert_eq!(source, EditorSource::Explicit);
+    }
+
+    /// Returns the arguments of each launch `open_all_diagnostics()` makes,
+    /// looking the editor up in `env` instead of starting it.
+    fn diagnostic_launches(
+        builder: EditorBuilder,
+        diagnostics: &[(&str, u32, u32)],
+        env: &FakeEnvironment,
+    ) -> Result<Vec<Vec<String>>> {
+        let mut launches = Vec::new();
+        builder.each_diagnostic_launch(diagnostics, env, |builder| {
+            launches.push(builder.command_line_in(env)?.1);
+            Ok(())
+        })?;
+        Ok(launches)
     }
 
     #[test]
-    fn test_editor_kind_properties() {
-        assert!(EditorKind::Vim.is_terminal_editor());
-        assert!(EditorKind::NeoVim.is_terminal_editor());
-        assert!(EditorKind::Nano.is_terminal_editor());
-        assert!(!EditorKind::VsCode.is_terminal_editor());
+    fn test_open_diagnostics_empty_list() {
+        let empty: [(&str, u32, u32); 0] = [];
+        assert!(matches!(
+            Editor::builder().open_first_diagnostic(&empty),
+            Err(Error::NoFileSpecified)
+        ));
+        assert!(matches!(
+            Editor::builder().open_all_diagnostics(&empty),
+            Err(Error::NoFileSpecified)
+        ));
+    }
 
-        assert!(EditorKind::VsCode.supports_column());
-        assert!(EditorKind::Vim.supports_column());
-        assert!(!EditorKind::TextMate.supports_column());
-        assert!(!EditorKind::IntelliJ.supports_column());
+    #[test]
+    #[cfg(unix)]
+    fn test_open_first_diagnostic() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, log) = stand_in_editor(dir.path(), "code");
+        let diagnostics = [("src/editor.rs", 42, 7), ("src/lib.rs", 10, 1)];
 
-        assert!(EditorKind::VsCode.supports_wait());
-        assert!(!EditorKind::Vim.supports_wait());
+        Editor::builder()
+            .editor_binary(binary.to_str().unwrap())
+            .open_first_diagnostic(&diagnostics)
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(log).unwrap(),
+            "--goto src/editor.rs:42:7\n"
+        );
     }
 
     #[test]
-    fn test_builder_no_file_error() {
-        let result = Editor::builder().open();
-        assert!(matches!(result, Err(Error::NoFileSpecified)));
+    fn test_open_all_diagnostics_gui_editor() {
+        let env = FakeEnvironment::new().with_binary("code");
+        let diagnostics = [
+            ("src/editor.rs", 42, 7),
+            ("src/lib.rs", 10, 1),
+            ("src/editor.rs", 50, 3),
+            ("src/lib.rs", 12, 5),
+            ("src/kind.rs", 3, 9),
+        ];
+
+        let builder = Editor::builder()
+            .editor_binary("code")
+            .multi_file_policy(MultiFilePolicy::SeparateProcesses);
+        assert_eq!(
+            diagnostic_launches(builder, &diagnostics, &env).unwrap(),
+            [
+                ["--goto", "src/lib.rs:10:1"],
+                ["--goto", "src/kind.rs:3:9"],
+                ["--goto", "src/editor.rs:42:7"],
+            ]
+        );
     }
 
     #[test]
-    fn test_builder_invalid_position() {
-        let result = Editor::builder()
+    fn test_command_line_vscode_dry_run() {
+        let env = FakeEnvironment::new().with_binary("code");
+
+        let (program, args) = Editor::builder()
             .file("test.rs")
-            .line(0)
-            .open();
-        assert!(matches!(result, Err(Error::InvalidPosition)));
+            .line(42)
+            .column(10)
+            .editor_binary("code")
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(program, "code");
+        assert_eq!(args, ["--goto", "test.rs:42:10"]);
+    }
 
-        let result = Editor::builder()
+    #[test]
+    fn test_command_line_includes_extra_args() {
+        let env = FakeEnvironment::new().with_binary("code");
+        let mut config = EditorConfig::with_editor("code");
+        config.args = vec!["--new-window".to_string()];
+
+        let (program, args) = Editor::builder()
             .file("test.rs")
-            .line(1)
-            .column(0)
-            .open();
-        assert!(matches!(result, Err(Error::InvalidPosition)));
+            .line(42)
+            .with_config(config)
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(program, "code");
+        assert_eq!(args, ["--new-window", "--goto", "test.rs:42"]);
+    }
+
+    #[test]
+    fn test_files_vscode_single_invocation() {
+        let env = FakeEnvironment::new().with_binary("code");
+
+        let plan = Editor::builder()
+            .files(["src/editor.rs", "src/lib.rs", "src/editor.rs"])
+            .line(42)
+            .column(7)
+            .wait(true)
+            .editor_binary("code")
+            .inspect_in(&env)
+            .unwrap();
+        assert_eq!(
+            plan.args,
+            ["--goto", "src/editor.rs:42:7", "src/lib.rs", "--wait"]
+        );
+    }
+
+    #[test]
+    fn test_files_vim_buffers() {
+        let env = FakeEnvironment::new().with_binary("vim");
+
+        let (_, args) = Editor::builder()
+            .file("src/editor.rs")
+            .files(["src/lib.rs", "src/args.rs"])
+            .line(42)
+            .editor_binary("vim")
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(args, ["+42", "src/editor.rs", "src/lib.rs", "src/args.rs"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_files_single_file_editors() {
+        let dir = tempfile::tempdir().unwrap();
+        let (notepad, log) = stand_in_editor(dir.path(), "notepad");
+        let (ed, _) = stand_in_editor(dir.path(), "ed");
+        let builder = |binary: &Path| {
+            Editor::builder()
+                .files(["src/editor.rs", "src/lib.rs"])
+                .editor_binary(binary.to_str().unwrap())
+        };
+
+        assert!(matches!(
+            builder(&ed).open(),
+            Err(Error::InvalidConfig { .. })
+        ));
+        assert!(matches!(
+            builder(&notepad).spawn(),
+            Err(Error::InvalidConfig { .. })
+        ));
+
+        builder(&notepad).wait(true).open().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(log).unwrap(),
+            "src/lib.rs\nsrc/editor.rs\n"
+        );
+    }
+
+    #[test]
+    fn test_open_all_requires_a_file() {
+        let empty: [&str; 0] = [];
+        assert!(matches!(
+            Editor::open_all(empty),
+            Err(Error::NoFileSpecified)
+        ));
+    }
+
+    #[test]
+    fn test_config_args_template() {
+        let env = FakeEnvironment::new().with_binary("opensesame-niche-edit");
+        let mut config = EditorConfig::with_editor("opensesame-niche-edit");
+        config.args = vec!["--new".to_string()];
+        config.args_template = ["--open", "{file}", "--goto-line", "{line}"]
+            .map(String::from)
+            .to_vec();
+        let builder = || {
+            Editor::builder()
+                .file("src/editor.rs")
+                .with_config(config.clone())
+        };
+
+        let (_, args) = builder().line(42).command_line_in(&env).unwrap();
+        assert_eq!(
+            args,
+            ["--new", "--open", "src/editor.rs", "--goto-line", "42"]
+        );
+
+        let (_, args) = builder().command_line_in(&env).unwrap();
+        assert_eq!(args, ["--new", "--open", "src/editor.rs"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_inspect_vscode_plan() {
+        let dir = tempfile::tempdir().unwrap();
+        let (binary, log) = stand_in_editor(dir.path(), "code");
+        let file = dir.path().join("new.rs");
+
+        let plan = Editor::builder()
+            .file(&file)
+            .line(42)
+            .wait(true)
+            .create_if_missing(true)
+            .editor_binary(binary.to_str().unwrap())
+            .inspect()
+            .unwrap();
+        let target = format!("{}:42", file.display());
+        assert_eq!(plan.editor.kind, EditorKind::VsCode);
+        assert_eq!(plan.program, binary.to_str().unwrap());
+        assert_eq!(plan.args, ["--goto", target.as_str(), "--wait"]);
+        assert!(plan.will_block);
+        assert_eq!(plan.source, EditorSource::Explicit);
+
+        // Nothing happens until the plan is executed
+        assert!(!log.exists());
+        assert!(!file.exists());
+        plan.execute().unwrap();
+        assert!(file.exists());
+        assert_eq!(
+            std::fs::read_to_string(log).unwrap(),
+            format!("--goto {target} --wait\n")
+        );
+    }
+
+    #[test]
+    fn test_inspect_without_wait_does_not_block() {
+        let env = FakeEnvironment::new().with_binary("code");
+
+        let plan = Editor::builder()
+            .file("src/editor.rs")
+            .wait(false)
+            .editor_binary("code")
+            .inspect_in(&env)
+            .unwrap();
+        assert!(!plan.will_block);
+        assert_eq!(plan.args, ["--goto", "src/editor.rs"]);
+    }
+
+    #[test]
+    fn test_emacsclient_daemon_and_wait() {
+        let env = FakeEnvironment::new().with_binary("emacsclient");
+        let builder = || {
+            Editor::builder()
+                .file("src/editor.rs")
+                .line(42)
+                .editor_binary("emacsclient")
+        };
+
+        let (_, args) = builder()
+            .start_emacs_daemon(true)
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(args, ["-a", "", "-n", "+42", "src/editor.rs"]);
+
+        let (_, args) = builder()
+            .wait(true)
+            .strict_wait(true)
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(args, ["+42", "src/editor.rs"]);
+    }
+
+    #[test]
+    fn test_folder_command_line() {
+        let env = FakeEnvironment::new()
+            .with_binary("code")
+            .with_binary("zed");
+        let args = |name| {
+            let (_, args) = Editor::builder()
+                .folder("src")
+                .line(42)
+                .editor_binary(name)
+                .command_line_in(&env)
+                .unwrap();
+            args
+        };
+        assert_eq!(args("code"), ["src"]);
+        assert_eq!(args("zed"), ["src"]);
+    }
+
+    #[test]
+    fn test_folder_errors_in_nano() {
+        let env = FakeEnvironment::new().with_binary("nano");
+        let result = Editor::builder()
+            .folder("src")
+            .editor_binary("nano")
+            .inspect_in(&env);
+        assert!(
+            matches!(result, Err(Error::InvalidConfig { .. })),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_folder_must_be_a_directory() {
+        let env = FakeEnvironment::new().with_binary("code");
+        let open = |path: &str| {
+            Editor::builder()
+                .folder(path)
+                .editor_binary("code")
+                .create_if_missing(true)
+                .inspect_in(&env)
+        };
+        assert!(matches!(
+            open("README.md"),
+            Err(Error::InvalidConfig { .. })
+        ));
+        assert!(matches!(
+            open("no-such-folder"),
+            Err(Error::FileNotFound { .. })
+        ));
+        assert!(!Path::new("no-such-folder").exists());
+    }
+
+    #[test]
+    fn test_focus_false_opens_sublime_in_background() {
+        let env = FakeEnvironment::new().with_binary("subl");
+        let plan = Editor::builder()
+            .file("src/editor.rs")
+            .line(42)
+            .editor_binary("subl")
+            .focus(false)
+            .activate(true)
+            .inspect_in(&env)
+            .unwrap();
+        assert_eq!(plan.args, ["--background", "src/editor.rs:42"]);
+        assert!(!plan.activate);
+    }
+
+    #[test]
+    fn test_focus_false_is_ignored_by_unsupported_editors() {
+        let args = |focus| {
+            Editor::builder()
+                .file("src/editor.rs")
+                .line(42)
+                .editor(EditorKind::VsCode)
+                .focus(focus)
+                .command_line()
+                .map(|(_, args)| args)
+        };
+        if let (Ok(background), Ok(focused)) = (args(false), args(true)) {
+            assert_eq!(background, focused);
+        }
     }
 
     #[test]
-    fn test_editor_kind_from_name() {
-        // Case insensitive
-        assert_eq!(EditorKind::from_name("NeoVim"), Some(EditorKind::NeoVim));
-        assert_eq!(EditorKind::from_name("neovim"), Some(EditorKind::NeoVim));
-        assert_eq!(EditorKind::from_name("NEOVIM"), Some(EditorKind::NeoVim));
+    fn test_current_dir_is_set_on_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = FakeEnvironment::new().with_binary("vim");
+        let plan = Editor::builder()
+            .file("src/editor.rs")
+            .line(42)
+            .editor_binary("vim")
+            .current_dir(dir.path())
+            .inspect_in(&env)
+            .unwrap();
+        assert_eq!(plan.current_dir.as_deref(), Some(dir.path()));
+        assert_eq!(plan.command.get_current_dir(), Some(dir.path()));
 
-        // With separators
-        assert_eq!(EditorKind::from_name("vs-code"), Some(EditorKind::VsCode));
-        assert_eq!(EditorKind::from_name("vs_code"), Some(EditorKind::VsCode));
+        // The relative file is passed so the editor finds it from its own directory
+        let file = std::path::absolute("src/editor.rs").unwrap();
+        assert_eq!(plan.args, ["+42", file.to_str().unwrap()]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_current_dir_applies_to_spawned_editor() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("pwd.log");
+        let binary = dir.path().join("code");
+        std::fs::write(
+            &binary,
+            format!(
+                "#!/bin/sh
+pwd > '{}'
+",
+                log.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut child = Editor::builder()
+            .file("src/editor.rs")
+            .editor_binary(binary.to_str().unwrap())
+            .current_dir(dir.path())
+            .spawn()
+            .unwrap();
+        assert!(child.wait().unwrap().success());
+        let pwd = std::fs::read_to_string(log).unwrap();
+        assert_eq!(
+            Path::new(pwd.trim()).canonicalize().unwrap(),
+            dir.path().canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_env_is_set_on_command() {
+        let env = FakeEnvironment::new().with_binary("nvim");
+        let plan = Editor::builder()
+            .file("src/editor.rs")
+            .editor_binary("nvim")
+            .env("NVIM_APPNAME", "first")
+            .envs([("TERM", "xterm-256color"), ("NVIM_APPNAME", "minimal")])
+            .env_remove("GIT_EDITOR")
+            .inspect_in(&env)
+            .unwrap();
+        let mut envs: Vec<_> = plan.command.get_envs().collect();
+        envs.sort();
+        assert_eq!(
+            envs,
+            [
+                (OsStr::new("GIT_EDITOR"), None),
+                (OsStr::new("NVIM_APPNAME"), Some(OsStr::new("minimal"))),
+                (OsStr::new("TERM"), Some(OsStr::new("xterm-256color"))),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_env_applies_to_spawned_editor() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("env.log");
+        let binary = dir.path().join("code");
+        let script = format!(
+            "#!/bin/sh\necho \"$OPENSESAME_TEST_VAR\" > '{}'\n",
+            log.display()
+        );
+        std::fs::write(&binary, script).unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut child = Editor::builder()
+            .file("src/editor.rs")
+            .editor_binary(binary.to_str().unwrap())
+            .env("OPENSESAME_TEST_VAR", "hello")
+            .spawn()
+            .unwrap();
+        assert!(child.wait().unwrap().success());
+        assert_eq!(std::fs::read_to_string(log).unwrap(), "hello\n");
+    }
+
+    #[test]
+    fn test_window_mode_command_line() {
+        let env = FakeEnvironment::new().with_binary("code");
+        let args = |mode| {
+            let (_, args) = Editor::builder()
+                .file("src/editor.rs")
+                .line(42)
+                .window_mode(mode)
+                .editor_binary("code")
+                .command_line_in(&env)
+                .unwrap();
+            args
+        };
+
+        assert_eq!(
+            args(WindowMode::Reuse),
+            ["--reuse-window", "--goto", "src/editor.rs:42"]
+        );
+        assert_eq!(
+            args(WindowMode::New),
+            ["--new-window", "--goto", "src/editor.rs:42"]
+        );
+        assert_eq!(args(WindowMode::Default), ["--goto", "src/editor.rs:42"]);
+    }
 
-        // Aliases
-        assert_eq!(EditorKind::from_name("nvim"), Some(EditorKind::NeoVim));
-        assert_eq!(EditorKind::from_name("code"), Some(EditorKind::VsCode));
-        assert_eq!(EditorKind::from_name("subl"), Some(EditorKind::Sublime));
-        assert_eq!(EditorKind::from_name("hx"), Some(EditorKind::Helix));
-        assert_eq!(EditorKind::from_name("idea"), Some(EditorKind::IntelliJ));
+    #[test]
+    fn test_scroll_centers_line_in_vim() {
+        let env = FakeEnvironment::new().with_binary("vim");
 
-        // Unknown returns None
-        assert_eq!(EditorKind::from_name("unknown"), None);
-        assert_eq!(EditorKind::from_name(""), None);
+        let (_, args) = Editor::builder()
+            .file("src/editor.rs")
+            .line(42)
+            .scroll(ScrollPosition::Center)
+            .editor_binary("vim")
+            .command_line_in(&env)
+            .unwrap();
+        assert_eq!(args, ["+42", "src/editor.rs", "-c", "normal! zz"]);
     }
 
     #[test]
-    fn test_editor_kind_as_str() {
-        assert_eq!(EditorKind::VsCode.as_str(), "VsCode");
-        assert_eq!(EditorKind::NeoVim.as_str(), "NeoVim");
-        assert_eq!(EditorKind::Helix.as_str(), "Helix");
-        assert_eq!(EditorKind::IntelliJ.as_str(), "IntelliJ");
-        assert_eq!(EditorKind::Unknown.as_str(), "Unknown");
+    fn test_open_all_diagnostics_single_process() {
+        let env = FakeEnvironment::new().with_binary("code");
+        let diagnostics = [
+            ("src/editor.rs", 42, 7),
+            ("src/lib.rs", 10, 1),
+            ("src/editor.rs", 50, 3),
+        ];
+
+        let builder = Editor::builder().editor_binary("code").wait(false);
+        assert_eq!(
+            diagnostic_launches(builder, &diagnostics, &env).unwrap(),
+            [["--goto", "src/editor.rs:42:7", "src/lib.rs"]]
+        );
     }
 
     #[test]
-    fn test_editor_kind_roundtrip() {
-        // Test that from_name(as_str()) returns the same kind
-        let kinds = [
-            EditorKind::VsCode,
-            EditorKind::NeoVim,
-            EditorKind::Vim,
-            EditorKind::Emacs,
-            EditorKind::Sublime,
-            EditorKind::Zed,
-            EditorKind::Helix,
-            EditorKind::Cursor,
-            EditorKind::Windsurf,
-            EditorKind::IntelliJ,
+    fn test_open_all_diagnostics_vim_opens_buffers() {
+        let env = FakeEnvironment::new().with_binary("vim");
+        let diagnostics = [
+            ("src/editor.rs", 42, 7),
+            ("src/lib.rs", 10, 1),
+            ("src/kind.rs", 3, 9),
         ];
 
-        for kind in kinds {
-            let name = kind.as_str();
-            let parsed = EditorKind::from_name(name);
-            assert_eq!(parsed, Some(kind), "roundtrip failed for {kind:?}");
+        let builder = Editor::builder().editor_binary("vim");
+        assert_eq!(
+            diagnostic_launches(builder, &diagnostics, &env).unwrap(),
+            [[
+                "+call cursor(42,7)",
+                "src/editor.rs",
+                "src/lib.rs",
+                "src/kind.rs"
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_open_all_diagnostics_single_file_terminal_editor() {
+        let env = FakeEnvironment::new().with_binary("ed");
+        let diagnostics = [("src/editor.rs", 42, 7), ("src/lib.rs", 10, 1)];
+
+        let result = diagnostic_launches(Editor::builder().editor_binary("ed"), &diagnostics, &env);
+        assert!(matches!(result, Err(Error::InvalidConfig { .. })));
+
+        // A single file is fine
+        let single = [("src/editor.rs", 42, 7), ("src/editor.rs", 50, 1)];
+        let launches = diagnostic_launches(Editor::builder().editor_binary("ed"), &single, &env);
+        assert_eq!(launches.unwrap(), [["src/editor.rs"]]);
+    }
+
+    #[test]
+    fn test_open_all_diagnostics_separate_terminal_processes() {
+        let env = FakeEnvironment::new().with_binary("vim");
+        let diagnostics = [("src/editor.rs", 42, 7), ("src/lib.rs", 10, 1)];
+
+        let builder = Editor::builder()
+            .editor_binary("vim")
+            .multi_file_policy(MultiFilePolicy::SeparateProcesses);
+        let result = diagnostic_launches(builder, &diagnostics, &env);
+        assert!(matches!(result, Err(Error::InvalidConfig { .. })));
+    }
+
+    #[test]
+    fn test_open_at_anchor_missing_anchor() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "// ANCHOR: setup\nfn setup() {}\n").unwrap();
+
+        assert_eq!(anchor_line(&file, "ANCHOR: setup").unwrap(), 1);
+        match Editor::open_at_anchor(&file, "ANCHOR: teardown") {
+            Err(Error::AnchorNotFound { anchor, path }) => {
+                assert_eq!(anchor, "ANCHOR: teardown");
+                assert_eq!(path, file);
+            }
+            other => panic!("expected AnchorNotFound, got {other:?}"),
+        }
+        assert!(matches!(
+            Editor::open_at_anchor(dir.path().join("missing.rs"), "ANCHOR"),
+            Err(Error::FileNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_open_at_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(
+            &file,
+            "fn a() {}\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> topic\n",
+        )
+        .unwrap();
+        assert_eq!(conflict_line(&file).unwrap(), 2);
+
+        std::fs::write(&file, "fn a() {}\n").unwrap();
+        match Editor::open_at_conflict(&file) {
+            Err(Error::ConflictNotFound { path }) => assert_eq!(path, file),
+            other => panic!("expected ConflictNotFound, got {other:?}"),
+        }
+        assert!(matches!(
+            Editor::open_at_conflict(dir.path().join("missing.rs")),
+            Err(Error::FileNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_rejects_newline_in_path() {
+        let result = Editor::builder()
+            .file("notes\nrm -rf ~.txt")
+            .editor_binary("definitely-not-an-editor-binary")
+            .sanitize(true)
+            .open();
+        match result {
+            Err(Error::InvalidConfig { message }) => assert!(message.contains("file path")),
+            other => panic!("expected InvalidConfig, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_check_encoding() {
+        for valid in [
+            "latin1",
+            "utf-16le",
+            "cp1252",
+            "shift_jis",
+            "ISO-8859-15",
+            "ucs-2",
+        ] {
+            assert!(check_encoding(valid).is_ok(), "{valid}");
         }
+        for invalid in ["", "latin1 | !rm", "utf 8", "latin1\n"] {
+            assert!(check_encoding(invalid).is_err(), "{invalid:?}");
+        }
+
+        let result = Editor::builder()
+            .file("legacy.txt")
+            .encoding("latin1|q")
+            .open();
+        assert!(matches!(result, Err(Error::InvalidConfig { .. })));
+    }
+
+    #[test]
+    fn test_check_sanitized() {
+        assert!(check_sanitized("file path", "src/editor.rs").is_ok());
+        assert!(check_sanitized("file path", "it's $HOME; ok.rs").is_ok());
+        assert!(check_sanitized("file path", "a\nb").is_err());
+        assert!(check_sanitized("file path", "a\rb").is_err());
+        assert!(check_sanitized("editor argument", "a\0b").is_err());
+    }
+
+    #[test]
+    fn test_plus_line_fallback_default() {
+        let unknown = |binary: &str| DetectedEditor {
+            binary: binary.to_string(),
+            kind: EditorKind::from_binary(binary),
+            extra_args: Vec::new(),
+            source: EditorSource::Explicit,
+        };
+        // Only binaries known to accept `+LINE` get it by default
+        assert!(!Editor::builder().uses_plus_line_fallback(&unknown("my-gui-editor")));
+        assert!(Editor::builder().uses_plus_line_fallback(&unknown("/usr/bin/micro")));
+        assert!(
+            Editor::builder()
+                .plus_line_fallback(true)
+                .uses_plus_line_fallback(&unknown("my-gui-editor"))
+        );
+        assert!(
+            Editor::builder()
+                .plus_line_fallback(false)
+                .uses_plus_line_fallback(&unknown("micro"))
+        );
+    }
+
+    #[test]
+    fn test_no_tty_fallback_keeps_gui_editor() {
+        let env = crate::env::FakeEnvironment::new()
+            .with_binary("code")
+            .with_binary("zed");
+        let zed = find_editor(&env, "zed").unwrap();
+        let editor = Editor::builder()
+            .no_tty_fallback(EditorKind::VsCode)
+            .apply_no_tty_fallback(&env, zed, TerminalLauncher::Inline, false)
+            .unwrap();
+        assert_eq!(editor.kind, EditorKind::Zed);
+    }
+
+    #[test]
+    fn test_run_headless_unsupported_editor() {
+        let result = Editor::run_headless("test.rs", EditorKind::VsCode, &["wq"]);
+        assert!(matches!(
+            result,
+            Err(Error::HeadlessUnsupported {
+                editor: EditorKind::VsCode
+            })
+        ));
+
+        let result = Editor::run_headless("test.rs", EditorKind::Vim, &["retab"; 10]);
+        assert!(matches!(result, Err(Error::InvalidConfig { .. })));
     }
 
     #[test]
     fn test_builder_with_config_stores_config() {
         let config = EditorConfig::with_editor("nvim");
-        let builder = Editor::builder()
-            .file("test.rs")
-            .with_config(config);
+        let builder = Editor::builder().file("test.rs").with_config(config);
 
         assert_eq!(builder.configs.len(), 1);
         assert_eq!(builder.configs[0].editor.as_deref(), Some("nvim"));
@@ -946,6 +4580,87 @@ mod tests {
         assert_eq!(builder.configs[1].editor.as_deref(), Some("code"));
     }
 
+    #[test]
+    fn test_builder_with_configs_preserves_order() {
+        let builder = Editor::builder()
+            .with_config(EditorConfig::with_editor("hx"))
+            .with_configs([
+                EditorConfig::with_editor("nvim"),
+                EditorConfig::with_editor("code"),
+                EditorConfig::with_editor("zed"),
+            ]);
+
+        let editors: Vec<_> = builder
+            .configs
+            .iter()
+            .map(|c| c.editor.as_deref())
+            .collect();
+        assert_eq!(
+            editors,
+            vec![Some("hx"), Some("nvim"), Some("code"), Some("zed")]
+        );
+    }
+
+    #[test]
+    fn test_builder_resolve_all_explicit_editor() {
+        let editors = Editor::builder()
+            .editor_binary("definitely-not-an-editor-binary")
+            .resolve_all();
+        assert!(editors.is_empty());
+
+        let editors = Editor::builder().resolve_order(&[]).resolve_all();
+        assert!(editors.is_empty());
+    }
+
+    #[test]
+    fn test_builder_missing_env_editor_falls_through_to_path() {
+        let env = crate::env::FakeEnvironment::new()
+            .with_var("EDITOR", "/opt/gone/bin/nvim")
+            .with_binary("vim");
+
+        let editor = Editor::builder().resolve_with_order(&env).unwrap();
+        assert_eq!(editor.binary, "vim");
+        assert_eq!(editor.source, EditorSource::PathSearch);
+
+        let editor = Editor::builder()
+            .verify_editor_exists(false)
+            .resolve_with_order(&env)
+            .unwrap();
+        assert_eq!(editor.binary, "/opt/gone/bin/nvim");
+        assert_eq!(editor.kind, EditorKind::NeoVim);
+        assert_eq!(
+            editor.source,
+            EditorSource::Environment("EDITOR".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_via_login_shell_keeps_missing_env_editor() {
+        let env = crate::env::FakeEnvironment::new()
+            .with_var("EDITOR", "code --wait")
+            .with_binary("vim");
+
+        let editor = Editor::builder()
+            .via_login_shell(true)
+            .resolve_with_order(&env)
+            .unwrap();
+        assert_eq!(editor.binary, "code");
+        assert_eq!(
+            editor.source,
+            EditorSource::Environment("EDITOR".to_string())
+        );
+        assert!(!Editor::builder().via_login_shell(true).verifies_editors());
+
+        // An explicit setting still wins
+        let editor = Editor::builder()
+            .via_login_shell(true)
+            .verify_editor_exists(true)
+            .resolve_with_order(&env)
+            .unwrap();
+        assert_eq!(editor.binary, "vim");
+    }
+
     #[test]
     fn test_builder_resolve_order_stores_order() {
         let builder = Editor::builder()
@@ -959,6 +4674,16 @@ mod tests {
         assert_eq!(order[1], ResolveFrom::Visual);
     }
 
+    #[test]
+    fn test_builder_file_extension() {
+        assert_eq!(
+            Editor::builder().file("notes/README.md").file_extension(),
+            Some("md")
+        );
+        assert_eq!(Editor::builder().file("Makefile").file_extension(), None);
+        assert_eq!(Editor::builder().file_extension(), None);
+    }
+
     #[test]
     fn test_builder_default_has_empty_configs() {
         let builder = Editor::builder();
@@ -0,0 +1,296 @@
+//! Host environment access.
+//!
+//! Editor detection reads environment variables, searches `PATH`, and
+//! follows the `update-alternatives` link. Routing
+//! those lookups through [`Environment`] keeps detection logic independent of
+//! the real process environment, so it can be exercised with fake values.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+/// Source of environment variables and executable lookups.
+pub trait Environment {
+    /// Returns the value of an environment variable, if set and valid UTF-8.
+    fn var(&self, name: &str) -> Option<String>;
+
+    /// Resolves a binary name (or path) to an executable path.
+    fn which(&self, binary: &str) -> Option<PathBuf>;
+
+    /// Follows the symlink chain at `link` to its final target.
+    ///
+    /// Returns `None` if `link` doesn't exist.
+    fn resolve_link(&self, link: &Path) -> Option<PathBuf>;
+}
+
+/// The real process environment.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemEnvironment;
+
+impl Environment for SystemEnvironment {
+    fn var(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok()
+    }
+
+    fn which(&self, binary: &str) -> Option<PathBuf> {
+        cached_which(&WHICH_CACHE, binary, |binary| which::which(binary).ok())
+    }
+
+    fn resolve_link(&self, link: &Path) -> Option<PathBuf> {
+        std::fs::canonicalize(link).ok()
+    }
+}
+
+/// `PATH` lookups of bare binary names, kept for the lifetime of the process.
+type WhichCache = Mutex<BTreeMap<String, Option<PathBuf>>>;
+
+/// The process-wide cache behind [`SystemEnvironment::which()`].
+static WHICH_CACHE: WhichCache = Mutex::new(BTreeMap::new());
+
+/// Looks up `binary` with `lookup`, reusing an earlier result from `cache`.
+///
+/// Only bare names are cached: a path is cheap to check and may be relative
+/// to a working directory that changes. Misses are cached too, since most
+/// of the fallback editors aren't installed. The lookup runs without the
+/// lock held, so concurrent lookups don't wait on each other.
+fn cached_which(
+    cache: &WhichCache,
+    binary: &str,
+    lookup: impl FnOnce(&str) -> Option<PathBuf>,
+) -> Option<PathBuf> {
+    if binary.contains(std::path::is_separator) {
+        return lookup(binary);
+    }
+    if let Some(found) = cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(binary)
+    {
+        return found.clone();
+    }
+    let found = lookup(binary);
+    cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(binary.to_string(), found.clone());
+    found
+}
+
+/// Forgets where editor binaries were found in `PATH`.
+///
+/// Editor lookups are cached for the lifetime of the process, so opening
+/// many files doesn't search `PATH` for every known editor each time. Call
+/// this after `PATH` changes or an editor is installed or removed, so the
+/// next lookup searches again. Safe to call from any thread.
+///
+/// This doesn't touch the on-disk cache of the `cache` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::{clear_editor_cache, Editor};
+///
+/// clear_editor_cache();
+/// let candidates = Editor::candidates();
+/// # let _ = candidates;
+/// ```
+pub fn clear_editor_cache() {
+    WHICH_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clear();
+}
+
+/// An environment that also finds binaries in extra directories.
+///
+/// `PATH` (through the inner environment) is searched first; the extra
+/// directories are only consulted for binaries it cannot find. Used for
+/// portable installs kept outside `PATH`.
+#[derive(Clone, Copy)]
+pub struct SearchDirs<'a> {
+    pub inner: &'a dyn Environment,
+    pub dirs: &'a [PathBuf],
+}
+
+impl Environment for SearchDirs<'_> {
+    fn var(&self, name: &str) -> Option<String> {
+        self.inner.var(name)
+    }
+
+    fn which(&self, binary: &str) -> Option<PathBuf> {
+        if let Some(path) = self.inner.which(binary) {
+            return Some(path);
+        }
+        if self.dirs.is_empty() {
+            return None;
+        }
+        let paths = std::env::join_paths(self.dirs).ok()?;
+        let cwd = std::env::current_dir().unwrap_or_default();
+        which::which_in(binary, Some(paths), cwd).ok()
+    }
+
+    fn resolve_link(&self, link: &Path) -> Option<PathBuf> {
+        self.inner.resolve_link(link)
+    }
+}
+
+/// An in-memory environment for tests.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct FakeEnvironment {
+    vars: std::collections::HashMap<String, String>,
+    binaries: std::collections::HashMap<String, PathBuf>,
+    links: std::collections::HashMap<PathBuf, PathBuf>,
+}
+
+#[cfg(test)]
+impl FakeEnvironment {
+    /// Creates an environment with no variables and no installed binaries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets an environment variable.
+    pub fn with_var(mut self, name: &str, value: &str) -> Self {
+        self.vars.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Marks a binary as installed at `/usr/bin/<binary>`.
+    pub fn with_binary(self, binary: &str) -> Self {
+        let path = PathBuf::from("/usr/bin").join(binary);
+        self.with_binary_at(binary, path)
+    }
+
+    /// Marks a binary as installed at the given path.
+    pub fn with_binary_at(mut self, binary: &str, path: impl Into<PathBuf>) -> Self {
+        self.binaries.insert(binary.to_string(), path.into());
+        self
+    }
+
+    /// Makes `link` resolve to `target`.
+    pub fn with_link(mut self, link: impl Into<PathBuf>, target: impl Into<PathBuf>) -> Self {
+        self.links.insert(link.into(), target.into());
+        self
+    }
+}
+
+#[cfg(test)]
+impl Environment for FakeEnvironment {
+    fn var(&self, name: &str) -> Option<String> {
+        self.vars.get(name).cloned()
+    }
+
+    fn which(&self, binary: &str) -> Option<PathBuf> {
+        self.binaries.get(binary).cloned()
+    }
+
+    fn resolve_link(&self, link: &Path) -> Option<PathBuf> {
+        self.links.get(link).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn executable(dir: &std::path::Path, name: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(name);
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_search_dirs_finds_binary_outside_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = executable(dir.path(), "portable-edit");
+        let fake = FakeEnvironment::new();
+        let dirs = [dir.path().to_path_buf()];
+        let env = SearchDirs {
+            inner: &fake,
+            dirs: &dirs,
+        };
+
+        assert_eq!(env.which("portable-edit"), Some(binary));
+        assert_eq!(env.which("missing-edit"), None);
+    }
+
+    #[test]
+    fn test_search_dirs_prefers_inner_environment() {
+        let fake = FakeEnvironment::new()
+            .with_binary("vim")
+            .with_var("EDITOR", "vim");
+        let dirs = [PathBuf::from("/opt/portable")];
+        let env = SearchDirs {
+            inner: &fake,
+            dirs: &dirs,
+        };
+
+        assert_eq!(env.which("vim"), Some(PathBuf::from("/usr/bin/vim")));
+        assert_eq!(env.var("EDITOR").as_deref(), Some("vim"));
+    }
+
+    #[test]
+    fn test_cached_which_looks_up_once() {
+        let cache = WhichCache::default();
+        let lookups = std::cell::Cell::new(0);
+        let lookup = |binary: &str| {
+            lookups.set(lookups.get() + 1);
+            (binary == "vim").then(|| PathBuf::from("/usr/bin/vim"))
+        };
+
+        assert_eq!(
+            cached_which(&cache, "vim", lookup),
+            Some(PathBuf::from("/usr/bin/vim"))
+        );
+        assert_eq!(
+            cached_which(&cache, "vim", lookup),
+            Some(PathBuf::from("/usr/bin/vim"))
+        );
+        assert_eq!(lookups.get(), 1);
+
+        // Misses are remembered too
+        assert_eq!(cached_which(&cache, "zed", lookup), None);
+        assert_eq!(cached_which(&cache, "zed", lookup), None);
+        assert_eq!(lookups.get(), 2);
+
+        // Paths are always looked up
+        cached_which(&cache, "/usr/bin/vim", lookup);
+        cached_which(&cache, "/usr/bin/vim", lookup);
+        assert_eq!(lookups.get(), 4);
+
+        cache.lock().unwrap().clear();
+        cached_which(&cache, "vim", lookup);
+        assert_eq!(lookups.get(), 5);
+    }
+
+    #[test]
+    fn test_system_which_is_cached_across_threads() {
+        let name = "opensesame-test-missing-editor";
+        let threads: Vec<_> = (0..4)
+            .map(|_| std::thread::spawn(move || SystemEnvironment.which(name)))
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), None);
+        }
+        assert_eq!(WHICH_CACHE.lock().unwrap().get(name), Some(&None));
+
+        clear_editor_cache();
+        assert_eq!(WHICH_CACHE.lock().unwrap().get(name), None);
+    }
+
+    #[test]
+    fn test_search_dirs_empty_falls_through() {
+        let fake = FakeEnvironment::new();
+        let env = SearchDirs {
+            inner: &fake,
+            dirs: &[],
+        };
+        assert_eq!(env.which("vim"), None);
+    }
+}
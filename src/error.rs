@@ -3,8 +3,12 @@
 //! This module provides a rich error type that covers all failure modes
 //! when opening files in editors.
 
+use std::fmt;
 use std::path::PathBuf;
 
+use crate::config::ResolveFrom;
+use crate::kind::EditorKind;
+
 /// A specialized Result type for opensesame operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -19,6 +23,14 @@ pub enum Error {
     #[error("no editor found: set $VISUAL or $EDITOR, or install a supported editor")]
     NoEditorFound,
 
+    /// No editor could be found; records why each source failed.
+    ///
+    /// Returned instead of [`NoEditorFound`](Self::NoEditorFound) when
+    /// [`EditorBuilder::verbose_errors()`](crate::EditorBuilder::verbose_errors)
+    /// is enabled.
+    #[error("no editor found: {0}")]
+    NoEditorFoundDetailed(Box<ResolutionFailure>),
+
     /// The specified editor binary was not found in PATH.
     #[error("editor not found: '{binary}' is not installed or not in PATH")]
     EditorNotFound {
@@ -26,6 +38,16 @@ pub enum Error {
         binary: String,
     },
 
+    /// A config names an editor that isn't installed, and strict mode is on.
+    ///
+    /// Returned instead of falling back to other sources when
+    /// [`EditorBuilder::strict()`](crate::EditorBuilder::strict) is enabled.
+    #[error("configured editor not found: '{binary}' is not installed or not in PATH")]
+    ConfigEditorNotFound {
+        /// The configured binary that was searched for.
+        binary: String,
+    },
+
     /// The specified file does not exist.
     #[error("file not found: {}", path.display())]
     FileNotFound {
@@ -71,6 +93,50 @@ pub enum Error {
     #[error("invalid position: line and column numbers must be >= 1")]
     InvalidPosition,
 
+    /// A column was specified without a line.
+    #[error("invalid position: column {column} was set without a line; use .line() as well")]
+    ColumnWithoutLine {
+        /// The column that was set.
+        column: u32,
+    },
+
+    /// Waiting was required but the editor cannot block until the file is closed.
+    #[error("editor {editor} does not support waiting for the file to be closed")]
+    WaitUnsupported {
+        /// The editor that cannot wait.
+        editor: EditorKind,
+    },
+
+    /// The file is on a remote host but the editor cannot open remote files.
+    #[error("editor {editor} cannot open remote files")]
+    RemoteUnsupported {
+        /// The editor that lacks remote support.
+        editor: EditorKind,
+    },
+
+    /// The editor has no batch mode for running commands headless.
+    #[error("editor {editor} cannot run commands headless")]
+    HeadlessUnsupported {
+        /// The editor that lacks a batch mode.
+        editor: EditorKind,
+    },
+
+    /// No line of the file contains the requested anchor.
+    #[error("anchor '{anchor}' not found in {}", path.display())]
+    AnchorNotFound {
+        /// The anchor text that was searched for.
+        anchor: String,
+        /// Path to the file that was searched.
+        path: PathBuf,
+    },
+
+    /// The file has no git conflict markers.
+    #[error("no conflict markers found in {}", path.display())]
+    ConflictNotFound {
+        /// Path to the file that was searched.
+        path: PathBuf,
+    },
+
     /// Invalid configuration was provided.
     #[error("invalid editor configuration: {message}")]
     InvalidConfig {
@@ -82,7 +148,23 @@ pub enum Error {
 impl Error {
     /// Returns `true` if this error indicates the editor was not found.
     pub const fn is_editor_not_found(&self) -> bool {
-        matches!(self, Self::NoEditorFound | Self::EditorNotFound { .. })
+        matches!(
+            self,
+            Self::NoEditorFound
+                | Self::NoEditorFoundDetailed(_)
+                | Self::EditorNotFound { .. }
+                | Self::ConfigEditorNotFound { .. }
+        )
+    }
+
+    /// Returns the sources tried before giving up on finding an editor.
+    ///
+    /// Empty unless this is [`NoEditorFoundDetailed`](Self::NoEditorFoundDetailed).
+    pub fn tried(&self) -> &[(ResolveFrom, String)] {
+        match self {
+            Self::NoEditorFoundDetailed(failure) => &failure.tried,
+            _ => &[],
+        }
     }
 
     /// Returns `true` if this error indicates the file was not found.
@@ -98,12 +180,42 @@ impl Error {
         )
     }
 
+    /// Returns `true` if this error indicates the editor cannot wait.
+    pub const fn is_wait_unsupported(&self) -> bool {
+        matches!(self, Self::WaitUnsupported { .. })
+    }
+
     /// Returns `true` if this error indicates invalid configuration.
     pub const fn is_invalid_config(&self) -> bool {
         matches!(self, Self::InvalidConfig { .. })
     }
 }
 
+/// Why editor resolution failed, source by source.
+///
+/// Carried by [`Error::NoEditorFoundDetailed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionFailure {
+    /// Each source that was checked, in order, with the reason it found no
+    /// editor (e.g., "$VISUAL is not set").
+    pub tried: Vec<(ResolveFrom, String)>,
+}
+
+impl fmt::Display for ResolutionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.tried.is_empty() {
+            return write!(f, "no sources to check");
+        }
+        for (i, (_, reason)) in self.tried.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{reason}")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,25 +234,64 @@ mod tests {
             path: PathBuf::from("/tmp/test.txt"),
         };
         assert!(err.to_string().contains("/tmp/test.txt"));
+
+        let err = Error::ColumnWithoutLine { column: 10 };
+        assert!(err.to_string().contains("column 10 was set without a line"));
     }
 
     #[test]
     fn test_error_predicates() {
         assert!(Error::NoEditorFound.is_editor_not_found());
-        assert!(Error::EditorNotFound {
-            binary: "vim".to_string()
-        }
-        .is_editor_not_found());
+        assert!(
+            Error::EditorNotFound {
+                binary: "vim".to_string()
+            }
+            .is_editor_not_found()
+        );
 
-        assert!(Error::FileNotFound {
-            path: PathBuf::from("/tmp/test.txt")
-        }
-        .is_file_not_found());
+        assert!(
+            Error::FileNotFound {
+                path: PathBuf::from("/tmp/test.txt")
+            }
+            .is_file_not_found()
+        );
 
-        assert!(Error::InvalidConfig {
-            message: "test error".to_string()
-        }
-        .is_invalid_config());
+        assert!(
+            Error::InvalidConfig {
+                message: "test error".to_string()
+            }
+            .is_invalid_config()
+        );
+    }
+
+    #[test]
+    fn test_no_editor_found_detailed() {
+        let err = Error::NoEditorFoundDetailed(Box::new(ResolutionFailure {
+            tried: vec![
+                (ResolveFrom::Visual, "$VISUAL is not set".to_string()),
+                (
+                    ResolveFrom::PathSearch,
+                    "no known editor found in PATH".to_string(),
+                ),
+            ],
+        }));
+        assert!(err.is_editor_not_found());
+        assert_eq!(err.tried().len(), 2);
+        assert_eq!(
+            err.to_string(),
+            "no editor found: $VISUAL is not set; no known editor found in PATH"
+        );
+        assert!(Error::NoEditorFound.tried().is_empty());
+    }
+
+    #[test]
+    fn test_wait_unsupported_display() {
+        let err = Error::WaitUnsupported {
+            editor: EditorKind::Notepad,
+        };
+        assert!(err.is_wait_unsupported());
+        assert!(err.to_string().contains("Notepad"));
+        assert!(err.to_string().contains("waiting"));
     }
 
     #[test]
@@ -151,4 +302,24 @@ mod tests {
         assert!(err.to_string().contains("invalid editor configuration"));
         assert!(err.to_string().contains("editor field is empty"));
     }
+
+    #[test]
+    fn test_conflict_not_found_display() {
+        let err = Error::ConflictNotFound {
+            path: PathBuf::from("src/lib.rs"),
+        };
+        assert_eq!(err.to_string(), "no conflict markers found in src/lib.rs");
+    }
+
+    #[test]
+    fn test_anchor_not_found_display() {
+        let err = Error::AnchorNotFound {
+            anchor: "ANCHOR: setup".to_string(),
+            path: PathBuf::from("src/lib.rs"),
+        };
+        assert_eq!(
+            err.to_string(),
+            "anchor 'ANCHOR: setup' not found in src/lib.rs"
+        );
+    }
 }
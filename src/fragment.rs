@@ -0,0 +1,110 @@
+//! GitHub-style line fragments.
+//!
+//! Code hosts link to lines with a URL fragment: `src/main.rs#L42` for a
+//! single line and `src/main.rs#L42-L50` for a range. These helpers split
+//! such references into a path and a line range.
+
+/// An inclusive, 1-indexed range of lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LineRange {
+    /// The first line of the range.
+    pub start: u32,
+    /// The last line of the range (equal to `start` for a single line).
+    pub end: u32,
+}
+
+/// Splits a `#L<line>` or `#L<start>-L<end>` fragment off a file reference.
+///
+/// Returns the path without the fragment and the parsed range. References
+/// without a valid fragment are returned unchanged with `None`, so file
+/// names that merely contain `#` are left alone. Reversed ranges are
+/// normalized so that `start <= end`.
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::{parse_line_fragment, LineRange};
+///
+/// assert_eq!(
+///     parse_line_fragment("src/main.rs#L42-L50"),
+///     ("src/main.rs", Some(LineRange { start: 42, end: 50 }))
+/// );
+/// assert_eq!(parse_line_fragment("notes#1.md"), ("notes#1.md", None));
+/// ```
+pub fn parse_line_fragment(reference: &str) -> (&str, Option<LineRange>) {
+    let Some((path, fragment)) = reference.rsplit_once('#') else {
+        return (reference, None);
+    };
+    match parse_fragment(fragment) {
+        Some(range) if !path.is_empty() => (path, Some(range)),
+        _ => (reference, None),
+    }
+}
+
+/// Parses `L42` or `L42-L50` (the second `L` is optional).
+fn parse_fragment(fragment: &str) -> Option<LineRange> {
+    let fragment = fragment.strip_prefix('L')?;
+    let (start, end) = match fragment.split_once('-') {
+        Some((start, end)) => (start, end.strip_prefix('L').unwrap_or(end)),
+        None => (fragment, fragment),
+    };
+    let start = parse_line(start)?;
+    let end = parse_line(end)?;
+    Some(LineRange {
+        start: start.min(end),
+        end: start.max(end),
+    })
+}
+
+/// Parses a 1-indexed line number.
+fn parse_line(s: &str) -> Option<u32> {
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok().filter(|&line| line > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_line_fragment() {
+        assert_eq!(
+            parse_line_fragment("src/main.rs#L42"),
+            ("src/main.rs", Some(LineRange { start: 42, end: 42 }))
+        );
+    }
+
+    #[test]
+    fn test_range_fragment() {
+        assert_eq!(
+            parse_line_fragment("src/main.rs#L42-L50"),
+            ("src/main.rs", Some(LineRange { start: 42, end: 50 }))
+        );
+        assert_eq!(
+            parse_line_fragment("src/main.rs#L42-50"),
+            ("src/main.rs", Some(LineRange { start: 42, end: 50 }))
+        );
+        assert_eq!(
+            parse_line_fragment("src/main.rs#L50-L42"),
+            ("src/main.rs", Some(LineRange { start: 42, end: 50 }))
+        );
+    }
+
+    #[test]
+    fn test_invalid_fragments_are_kept() {
+        for reference in [
+            "src/main.rs",
+            "notes#1.md",
+            "src/main.rs#L",
+            "src/main.rs#L0",
+            "src/main.rs#L+4",
+            "src/main.rs#L42-",
+            "src/main.rs#readme",
+            "#L42",
+        ] {
+            assert_eq!(parse_line_fragment(reference), (reference, None));
+        }
+    }
+}
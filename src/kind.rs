@@ -0,0 +1,1963 @@
+//! Editor kinds and their CLI conventions.
+//!
+//! [`EditorKind`] identifies an editor and describes how it is driven from
+//! the command line: positioning syntax, supported flags, and capabilities.
+//! [`EditorFamily`] groups kinds that share a CLI, and [`Capabilities`]
+//! collects the capability checks in one value.
+//! Like the argument builder, it only needs `core` and `alloc` (see
+//! [`args`](crate::args)).
+
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+use core::fmt;
+
+use crate::options::ColumnSemantics;
+
+/// The canonical names ([`EditorKind::as_str()`]) of every known editor.
+pub(crate) const KIND_NAMES: &[&str] = &[
+    "VsCode",
+    "VsCodeInsiders",
+    "VSCodium",
+    "Cursor",
+    "Windsurf",
+    "Vim",
+    "NeoVim",
+    "Vi",
+    "GVim",
+    "Neovide",
+    "NvimQt",
+    "VimR",
+    "Emacs",
+    "EmacsClient",
+    "Sublime",
+    "Zed",
+    "Helix",
+    "Atom",
+    "Kate",
+    "Nano",
+    "Ex",
+    "Ed",
+    "Kakoune",
+    "TextMate",
+    "Xcode",
+    "NotepadPlusPlus",
+    "Notepad",
+    "IntelliJ",
+    "WebStorm",
+    "PhpStorm",
+    "PyCharm",
+    "RubyMine",
+    "GoLand",
+    "CLion",
+    "Rider",
+    "DataGrip",
+    "AndroidStudio",
+];
+
+/// Known text editor types.
+///
+/// This enum represents all the text editors that opensesame knows how to
+/// invoke with proper line:column positioning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EditorKind {
+    // VS Code family
+    /// Visual Studio Code
+    VsCode,
+    /// VS Code Insiders
+    VsCodeInsiders,
+    /// VSCodium (open source VS Code)
+    VSCodium,
+    /// Cursor (AI-powered VS Code fork)
+    Cursor,
+    /// Windsurf (Codeium's editor)
+    Windsurf,
+
+    // Vim family
+    /// Vim
+    Vim,
+    /// NeoVim
+    NeoVim,
+    /// Vi
+    Vi,
+    /// GVim (graphical Vim)
+    GVim,
+    /// Neovide (graphical NeoVim frontend)
+    Neovide,
+    /// nvim-qt (Qt NeoVim frontend)
+    NvimQt,
+    /// VimR (macOS NeoVim frontend)
+    VimR,
+
+    // Emacs family
+    /// GNU Emacs
+    Emacs,
+    /// Emacs Client
+    EmacsClient,
+
+    // Modern GUI editors
+    /// Sublime Text
+    Sublime,
+    /// Zed
+    Zed,
+    /// Helix
+    Helix,
+    /// Atom (deprecated but still used)
+    Atom,
+    /// Kate (KDE)
+    Kate,
+
+    // Terminal editors
+    /// GNU Nano
+    Nano,
+    /// Ex (the POSIX line editor behind Vi)
+    Ex,
+    /// Ed (the POSIX line editor)
+    ///
+    /// Ed has no command-line option for a starting line, so it only ever
+    /// gets the file; opening at a line is not supported.
+    Ed,
+    /// Kakoune
+    Kakoune,
+
+    // macOS editors
+    /// TextMate
+    TextMate,
+    /// Xcode
+    Xcode,
+
+    // Windows editors
+    /// Notepad++ (Windows)
+    NotepadPlusPlus,
+    /// Notepad (Windows, no line support)
+    Notepad,
+
+    // JetBrains family
+    /// IntelliJ IDEA
+    IntelliJ,
+    /// WebStorm
+    WebStorm,
+    /// PhpStorm
+    PhpStorm,
+    /// PyCharm
+    PyCharm,
+    /// RubyMine
+    RubyMine,
+    /// GoLand
+    GoLand,
+    /// CLion
+    CLion,
+    /// Rider
+    Rider,
+    /// DataGrip
+    DataGrip,
+    /// Android Studio
+    AndroidStudio,
+
+    /// Unknown editor (will just pass file path)
+    Unknown,
+}
+
+/// A group of editors that share a command-line interface.
+///
+/// Returned by [`EditorKind::family()`]. Members of a family take the same
+/// positioning syntax, so settings can be offered once per family.
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::{EditorFamily, EditorKind};
+///
+/// assert_eq!(EditorKind::Cursor.family(), EditorFamily::VsCode);
+/// assert_eq!(EditorKind::Neovide.family(), EditorFamily::Vim);
+/// assert_eq!(EditorKind::Helix.family(), EditorFamily::Other);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EditorFamily {
+    /// VS Code and its forks (Insiders, VSCodium, Cursor, Windsurf)
+    VsCode,
+    /// Vim, NeoVim, Vi, and their graphical frontends
+    Vim,
+    /// Emacs and Emacs Client
+    Emacs,
+    /// JetBrains IDEs, including Android Studio
+    JetBrains,
+    /// Sublime Text
+    Sublime,
+    /// Editors with a CLI of their own, and unknown editors
+    Other,
+}
+
+/// What an editor can do from the command line, all at once.
+///
+/// Returned by [`EditorKind::capabilities()`]. Each field matches the
+/// `EditorKind` method of the same name; new fields are added as
+/// capabilities are, so the struct can't be built outside opensesame.
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::EditorKind;
+///
+/// let caps = EditorKind::VsCode.capabilities();
+/// assert!(caps.supports_column && caps.supports_wait && caps.is_gui);
+/// assert!(!EditorKind::Notepad.capabilities().supports_line);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)] // Independent capabilities
+pub struct Capabilities {
+    /// Can open at a line ([`EditorKind::supports_line()`]).
+    pub supports_line: bool,
+    /// Can open at a column ([`EditorKind::supports_column()`]).
+    pub supports_column: bool,
+    /// Can be made to wait for the file to close ([`EditorKind::supports_wait()`]).
+    pub supports_wait: bool,
+    /// Runs in the terminal ([`EditorKind::is_terminal_editor()`]).
+    pub is_terminal: bool,
+    /// Runs in its own window ([`EditorKind::is_gui_editor()`]).
+    pub is_gui: bool,
+    /// Can open a folder ([`EditorKind::supports_folders()`]).
+    pub supports_folder: bool,
+    /// Can be told to reuse or open a window ([`EditorKind::supports_window_mode()`]).
+    pub supports_reuse_window: bool,
+}
+
+impl EditorKind {
+    /// Parses an `EditorKind` from its string name.
+    ///
+    /// Accepts names like "VsCode", "NeoVim", "Vim", etc. The matching
+    /// is case-insensitive and supports common variations.
+    ///
+    /// Returns `None` for unrecognized names.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::EditorKind;
+    ///
+    /// assert_eq!(EditorKind::from_name("NeoVim"), Some(EditorKind::NeoVim));
+    /// assert_eq!(EditorKind::from_name("vscode"), Some(EditorKind::VsCode));
+    /// assert_eq!(EditorKind::from_name("unknown"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        // Normalize: lowercase and remove common separators. Non-ASCII input
+        // takes the allocating path, since Unicode lowercasing can produce
+        // ASCII (the Kelvin sign lowercases to 'k').
+        if !name.is_ascii() {
+            return Self::match_name(&name.to_lowercase().replace(['-', '_'], ""));
+        }
+        let mut buf = [0; NAME_BUF_LEN];
+        Self::match_name(normalize_ascii(name, true, &mut buf)?)
+    }
+
+    /// Matches a lowercased name with separators removed.
+    fn match_name(normalized: &str) -> Option<Self> {
+        match normalized {
+            // VS Code family
+            "vscode" | "visualstudiocode" | "code" => Some(Self::VsCode),
+            "vscodeinsiders" | "codeinsiders" => Some(Self::VsCodeInsiders),
+            "vscodium" | "codium" => Some(Self::VSCodium),
+            "cursor" => Some(Self::Cursor),
+            "windsurf" => Some(Self::Windsurf),
+
+            // Vim family
+            "vim" => Some(Self::Vim),
+            "neovim" | "nvim" => Some(Self::NeoVim),
+            "vi" => Some(Self::Vi),
+            "gvim" | "mvim" => Some(Self::GVim),
+            "neovide" => Some(Self::Neovide),
+            "nvimqt" => Some(Self::NvimQt),
+            "vimr" => Some(Self::VimR),
+
+            // Emacs family
+            "emacs" | "gnuemacs" | "xemacs" => Some(Self::Emacs),
+            "emacsclient" => Some(Self::EmacsClient),
+
+            // Modern GUI editors
+            "sublime" | "sublimetext" | "subl" => Some(Self::Sublime),
+            "zed" => Some(Self::Zed),
+            "helix" | "hx" => Some(Self::Helix),
+            "atom" => Some(Self::Atom),
+            "kate" => Some(Self::Kate),
+
+            // Terminal editors
+            "nano" => Some(Self::Nano),
+            "ex" => Some(Self::Ex),
+            "ed" => Some(Self::Ed),
+            "kakoune" | "kak" => Some(Self::Kakoune),
+
+            // macOS editors
+            "textmate" | "mate" => Some(Self::TextMate),
+            "xcode" | "xed" => Some(Self::Xcode),
+
+            // Windows editors
+            "notepadplusplus" | "notepad++" | "npp" => Some(Self::NotepadPlusPlus),
+            "notepad" => Some(Self::Notepad),
+
+            // JetBrains family
+            "intellij" | "intellijidea" | "idea" => Some(Self::IntelliJ),
+            "webstorm" => Some(Self::WebStorm),
+            "phpstorm" | "pstorm" => Some(Self::PhpStorm),
+            "pycharm" | "charm" => Some(Self::PyCharm),
+            "rubymine" | "mine" => Some(Self::RubyMine),
+            "goland" => Some(Self::GoLand),
+            "clion" => Some(Self::CLion),
+            "rider" => Some(Self::Rider),
+            "datagrip" => Some(Self::DataGrip),
+            "androidstudio" | "studio" => Some(Self::AndroidStudio),
+
+            _ => None,
+        }
+    }
+
+    /// Returns the canonical string name for this editor kind.
+    ///
+    /// This is the preferred name for display and serialization.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::EditorKind;
+    ///
+    /// assert_eq!(EditorKind::NeoVim.as_str(), "NeoVim");
+    /// assert_eq!(EditorKind::VsCode.as_str(), "VsCode");
+    /// ```
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::VsCode => "VsCode",
+            Self::VsCodeInsiders => "VsCodeInsiders",
+            Self::VSCodium => "VSCodium",
+            Self::Cursor => "Cursor",
+            Self::Windsurf => "Windsurf",
+            Self::Vim => "Vim",
+            Self::NeoVim => "NeoVim",
+            Self::Vi => "Vi",
+            Self::GVim => "GVim",
+            Self::Neovide => "Neovide",
+            Self::NvimQt => "NvimQt",
+            Self::VimR => "VimR",
+            Self::Emacs => "Emacs",
+            Self::EmacsClient => "EmacsClient",
+            Self::Sublime => "Sublime",
+            Self::Zed => "Zed",
+            Self::Helix => "Helix",
+            Self::Atom => "Atom",
+            Self::Kate => "Kate",
+            Self::Nano => "Nano",
+            Self::Ex => "Ex",
+            Self::Ed => "Ed",
+            Self::Kakoune => "Kakoune",
+            Self::TextMate => "TextMate",
+            Self::Xcode => "Xcode",
+            Self::NotepadPlusPlus => "NotepadPlusPlus",
+            Self::Notepad => "Notepad",
+            Self::IntelliJ => "IntelliJ",
+            Self::WebStorm => "WebStorm",
+            Self::PhpStorm => "PhpStorm",
+            Self::PyCharm => "PyCharm",
+            Self::RubyMine => "RubyMine",
+            Self::GoLand => "GoLand",
+            Self::CLion => "CLion",
+            Self::Rider => "Rider",
+            Self::DataGrip => "DataGrip",
+            Self::AndroidStudio => "AndroidStudio",
+            Self::Unknown => "Unknown",
+        }
+    }
+
+    /// Returns every editor kind, including [`EditorKind::Unknown`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::EditorKind;
+    ///
+    /// assert!(EditorKind::all().contains(&EditorKind::Helix));
+    /// ```
+    pub const fn all() -> &'static [Self] {
+        &[
+            Self::VsCode,
+            Self::VsCodeInsiders,
+            Self::VSCodium,
+            Self::Cursor,
+            Self::Windsurf,
+            Self::Vim,
+            Self::NeoVim,
+            Self::Vi,
+            Self::GVim,
+            Self::Neovide,
+            Self::NvimQt,
+            Self::VimR,
+            Self::Emacs,
+            Self::EmacsClient,
+            Self::Sublime,
+            Self::Zed,
+            Self::Helix,
+            Self::Atom,
+            Self::Kate,
+            Self::Nano,
+            Self::Ex,
+            Self::Ed,
+            Self::Kakoune,
+            Self::TextMate,
+            Self::Xcode,
+            Self::NotepadPlusPlus,
+            Self::Notepad,
+            Self::IntelliJ,
+            Self::WebStorm,
+            Self::PhpStorm,
+            Self::PyCharm,
+            Self::RubyMine,
+            Self::GoLand,
+            Self::CLion,
+            Self::Rider,
+            Self::DataGrip,
+            Self::AndroidStudio,
+            Self::Unknown,
+        ]
+    }
+
+    /// Returns every known editor kind, without [`EditorKind::Unknown`].
+    ///
+    /// Use this to offer a choice of editors, e.g. in a settings dropdown or
+    /// shell completions, or to validate user input without hardcoding the
+    /// list.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::EditorKind;
+    ///
+    /// for kind in EditorKind::known() {
+    ///     println!("{kind} ({}): {}", kind.as_str(), kind.default_binary());
+    /// }
+    /// assert!(!EditorKind::known().contains(&EditorKind::Unknown));
+    /// ```
+    pub const fn known() -> &'static [Self] {
+        // `Unknown` is last in `all()`
+        match Self::all().split_last() {
+            Some((_, known)) => known,
+            None => &[],
+        }
+    }
+
+    /// Detects the editor kind from a binary name.
+    ///
+    /// This handles both bare binary names (`vim`) and full paths
+    /// (`/usr/bin/vim`), extracting just the filename for comparison.
+    pub fn from_binary(binary: &str) -> Self {
+        let name = file_name(binary);
+
+        if !name.is_ascii() {
+            return Self::match_binary(strip_script_extension(&name.to_lowercase()));
+        }
+        let mut buf = [0; NAME_BUF_LEN];
+        normalize_ascii(name, false, &mut buf).map_or(Self::Unknown, |name| {
+            Self::match_binary(strip_script_extension(name))
+        })
+    }
+
+    /// Matches a lowercased binary file name without its extension.
+    fn match_binary(name: &str) -> Self {
+        match name {
+            // VS Code family
+            "code" | "vscode" => Self::VsCode,
+            "code-insiders" => Self::VsCodeInsiders,
+            "codium" | "vscodium" | "code-oss" => Self::VSCodium,
+            "cursor" => Self::Cursor,
+            "windsurf" => Self::Windsurf,
+
+            // Vim family
+            "vim" => Self::Vim,
+            "nvim" | "neovim" => Self::NeoVim,
+            "vi" => Self::Vi,
+            "gvim" | "mvim" => Self::GVim,
+            "neovide" => Self::Neovide,
+            "nvim-qt" => Self::NvimQt,
+            "vimr" => Self::VimR,
+
+            // Emacs family
+            "emacs" | "xemacs" => Self::Emacs,
+            "emacsclient" => Self::EmacsClient,
+
+            // Modern GUI editors
+            "subl" | "sublime" | "sublime_text" => Self::Sublime,
+            "zed" => Self::Zed,
+            "hx" | "helix" => Self::Helix,
+            "atom" => Self::Atom,
+            "kate" => Self::Kate,
+
+            // Terminal editors
+            "nano" => Self::Nano,
+            "ex" => Self::Ex,
+            "ed" => Self::Ed,
+            "kak" | "kakoune" => Self::Kakoune,
+
+            // macOS editors
+            "mate" | "textmate" => Self::TextMate,
+            "xed" | "xcode" => Self::Xcode,
+
+            // Windows editors
+            "notepad++" => Self::NotepadPlusPlus,
+            "notepad" => Self::Notepad,
+
+            // JetBrains family
+            "idea" | "intellij" | "idea64" => Self::IntelliJ,
+            "webstorm" | "webstorm64" => Self::WebStorm,
+            "pstorm" | "phpstorm" | "phpstorm64" => Self::PhpStorm,
+            "pycharm" | "pycharm64" | "charm" => Self::PyCharm,
+            "rubymine" | "mine" => Self::RubyMine,
+            "goland" | "goland64" => Self::GoLand,
+            "clion" | "clion64" => Self::CLion,
+            "rider" | "rider64" => Self::Rider,
+            "datagrip" | "datagrip64" => Self::DataGrip,
+            "studio" | "studio64" | "android-studio" => Self::AndroidStudio,
+
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Returns the default binary name for this editor kind.
+    pub const fn default_binary(&self) -> &'static str {
+        match self {
+            Self::VsCode => "code",
+            Self::VsCodeInsiders => "code-insiders",
+            Self::VSCodium => "codium",
+            Self::Cursor => "cursor",
+            Self::Windsurf => "windsurf",
+            Self::Vim => "vim",
+            Self::NeoVim => "nvim",
+            Self::Vi => "vi",
+            Self::GVim => "gvim",
+            Self::Neovide => "neovide",
+            Self::NvimQt => "nvim-qt",
+            Self::VimR => "vimr",
+            Self::Emacs => "emacs",
+            Self::EmacsClient => "emacsclient",
+            Self::Sublime => "subl",
+            Self::Zed => "zed",
+            Self::Helix => "hx",
+            Self::Atom => "atom",
+            Self::Kate => "kate",
+            Self::Nano => "nano",
+            Self::Ex => "ex",
+            Self::Ed => "ed",
+            Self::Kakoune => "kak",
+            Self::TextMate => "mate",
+            Self::Xcode => "xed",
+            Self::NotepadPlusPlus => "notepad++",
+            Self::Notepad => "notepad",
+            Self::IntelliJ => "idea",
+            Self::WebStorm => "webstorm",
+            Self::PhpStorm => "pstorm",
+            Self::PyCharm => "pycharm",
+            Self::RubyMine => "rubymine",
+            Self::GoLand => "goland",
+            Self::CLion => "clion",
+            Self::Rider => "rider",
+            Self::DataGrip => "datagrip",
+            Self::AndroidStudio => "studio",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// Returns `true` if this editor runs in the terminal (requires TTY).
+    pub const fn is_terminal_editor(&self) -> bool {
+        matches!(
+            self,
+            Self::Vim
+                | Self::NeoVim
+                | Self::Vi
+                | Self::Nano
+                | Self::Ex
+                | Self::Ed
+                | Self::Emacs
+                | Self::Helix
+                | Self::Kakoune
+        )
+    }
+
+    /// Returns `true` if this editor opens its own window instead of running
+    /// in the terminal.
+    ///
+    /// The inverse of [`is_terminal_editor()`](Self::is_terminal_editor),
+    /// except that [`Unknown`](Self::Unknown) is neither.
+    pub const fn is_gui_editor(&self) -> bool {
+        !self.is_terminal_editor() && !self.is_unknown()
+    }
+
+    /// Returns `true` if this editor runs in the terminal, using `binary` to
+    /// fill in for [`Unknown`](Self::Unknown).
+    ///
+    /// An unknown editor counts as a terminal editor when its binary is a
+    /// common terminal editor opensesame has no dedicated kind for (joe,
+    /// micro, mg, ...).
+    pub fn is_terminal_editor_hinted(&self, binary: Option<&str>) -> bool {
+        self.is_terminal_editor() || takes_plus_line(*self, binary)
+    }
+
+    /// Returns `true` for [`Unknown`](Self::Unknown).
+    ///
+    /// The binary of an unknown editor is kept in
+    /// [`DetectedEditor::binary`](crate::DetectedEditor::binary); pass it as
+    /// the hint to the `*_hinted` and [`format_position()`](Self::format_position)
+    /// methods.
+    pub const fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown)
+    }
+
+    /// Returns the family this editor belongs to.
+    ///
+    /// The families follow the groupings of the argument builder: every
+    /// member of a family except [`EditorFamily::Other`] is positioned the
+    /// same way.
+    pub const fn family(&self) -> EditorFamily {
+        match self {
+            Self::VsCode
+            | Self::VsCodeInsiders
+            | Self::VSCodium
+            | Self::Cursor
+            | Self::Windsurf => EditorFamily::VsCode,
+            Self::Vim
+            | Self::NeoVim
+            | Self::Vi
+            | Self::GVim
+            | Self::Neovide
+            | Self::NvimQt
+            | Self::VimR => EditorFamily::Vim,
+            Self::Emacs | Self::EmacsClient => EditorFamily::Emacs,
+            Self::IntelliJ
+            | Self::WebStorm
+            | Self::PhpStorm
+            | Self::PyCharm
+            | Self::RubyMine
+            | Self::GoLand
+            | Self::CLion
+            | Self::Rider
+            | Self::DataGrip
+            | Self::AndroidStudio => EditorFamily::JetBrains,
+            Self::Sublime => EditorFamily::Sublime,
+            _ => EditorFamily::Other,
+        }
+    }
+
+    /// Returns all of this editor's capabilities at once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::EditorKind;
+    ///
+    /// let caps = EditorKind::Helix.capabilities();
+    /// assert!(caps.is_terminal && caps.supports_column);
+    /// assert!(!caps.supports_wait);
+    /// ```
+    pub const fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_line: self.supports_line(),
+            supports_column: self.supports_column(),
+            supports_wait: self.supports_wait(),
+            is_terminal: self.is_terminal_editor(),
+            is_gui: self.is_gui_editor(),
+            supports_folder: self.supports_folders(),
+            supports_reuse_window: self.supports_window_mode(),
+        }
+    }
+
+    /// Returns `true` if this editor can open a file at a line.
+    ///
+    /// Ed, Notepad, and unknown editors are only given the file, except for
+    /// unknown editors whose binary is known to accept `+LINE` (see
+    /// [`format_position()`](Self::format_position) and
+    /// [`EditorBuilder::plus_line_fallback()`](crate::EditorBuilder::plus_line_fallback)).
+    pub const fn supports_line(&self) -> bool {
+        !matches!(self, Self::Ed | Self::Notepad | Self::Unknown)
+    }
+
+    /// Returns `true` if this editor supports column positioning.
+    pub const fn supports_column(&self) -> bool {
+        matches!(
+            self,
+            Self::VsCode
+                | Self::VsCodeInsiders
+                | Self::VSCodium
+                | Self::Cursor
+                | Self::Windsurf
+                | Self::Vim
+                | Self::NeoVim
+                | Self::Vi
+                | Self::GVim
+                | Self::Neovide
+                | Self::NvimQt
+                | Self::VimR
+                | Self::Emacs
+                | Self::EmacsClient
+                | Self::Sublime
+                | Self::Zed
+                | Self::Helix
+                | Self::Kakoune
+                | Self::Atom
+                | Self::Kate
+                | Self::Nano
+                | Self::NotepadPlusPlus
+                | Self::IntelliJ
+                | Self::WebStorm
+                | Self::PhpStorm
+                | Self::PyCharm
+                | Self::RubyMine
+                | Self::GoLand
+                | Self::CLion
+                | Self::Rider
+                | Self::DataGrip
+                | Self::AndroidStudio
+        )
+    }
+
+    /// Returns how this editor counts columns on its command line.
+    ///
+    /// - Vim family: bytes (`cursor()` takes a byte index)
+    /// - Emacs family: display cells with 8-wide tabs (`move-to-column`)
+    /// - Everything else: characters
+    pub const fn column_semantics(&self) -> ColumnSemantics {
+        match self {
+            Self::Vim
+            | Self::NeoVim
+            | Self::Vi
+            | Self::GVim
+            | Self::Neovide
+            | Self::NvimQt
+            | Self::VimR => ColumnSemantics::Byte,
+            Self::Emacs | Self::EmacsClient => ColumnSemantics::DisplayCell { tab_width: 8 },
+            _ => ColumnSemantics::Char,
+        }
+    }
+
+    /// Returns `true` if this editor can lay out several files in splits
+    /// from the CLI (see [`EditorBuilder::split()`](crate::EditorBuilder::split)).
+    pub const fn supports_split(&self) -> bool {
+        matches!(self, Self::Vim | Self::NeoVim | Self::GVim | Self::Helix)
+    }
+
+    /// Returns `true` if this editor can be told whether to reuse a window
+    /// from the CLI (see [`EditorBuilder::window_mode()`](crate::EditorBuilder::window_mode)).
+    pub const fn supports_window_mode(&self) -> bool {
+        matches!(
+            self,
+            Self::VsCode
+                | Self::VsCodeInsiders
+                | Self::VSCodium
+                | Self::Cursor
+                | Self::Windsurf
+                | Self::Sublime
+        )
+    }
+
+    /// Returns `true` if this editor can scroll the target line into a given
+    /// place from the CLI (see [`EditorBuilder::scroll()`](crate::EditorBuilder::scroll)).
+    pub const fn supports_scroll(&self) -> bool {
+        matches!(self, Self::Vim | Self::GVim | Self::NeoVim)
+    }
+
+    /// Returns `true` if this editor can select a range of lines from the
+    /// CLI (see [`EditorBuilder::file_ref()`](crate::EditorBuilder::file_ref)).
+    pub const fn supports_selection(&self) -> bool {
+        matches!(self, Self::Vim | Self::GVim | Self::NeoVim)
+    }
+
+    /// Returns `true` if this editor accepts several `file:line:column`
+    /// targets in one invocation.
+    ///
+    /// The VS Code family accepts repeated `--goto file:line:column` groups.
+    /// Each one opens or reveals its location; this does not create
+    /// multiple cursors, and several targets in one file leave the cursor at
+    /// the last of them.
+    pub const fn supports_multiple_positions(&self) -> bool {
+        matches!(
+            self,
+            Self::VsCode | Self::VsCodeInsiders | Self::VSCodium | Self::Cursor | Self::Windsurf
+        )
+    }
+
+    /// Returns `true` if this editor accepts several files on one command line.
+    ///
+    /// Nearly every editor does: Vim and Emacs load them as buffers, GUI
+    /// editors as tabs or windows. Ed, Notepad, and unknown editors are
+    /// assumed to take a single file.
+    pub const fn supports_multiple_files(&self) -> bool {
+        !matches!(self, Self::Ed | Self::Notepad | Self::Unknown)
+    }
+
+    /// Returns `true` if this editor can open a folder (see
+    /// [`EditorBuilder::folder()`](crate::EditorBuilder::folder)).
+    ///
+    /// GUI editors open it as a workspace or project, Vim and Emacs in their
+    /// directory browsers (netrw, dired), and Helix in its file picker.
+    pub const fn supports_folders(&self) -> bool {
+        matches!(
+            self,
+            Self::VsCode
+                | Self::VsCodeInsiders
+                | Self::VSCodium
+                | Self::Cursor
+                | Self::Windsurf
+                | Self::Vim
+                | Self::NeoVim
+                | Self::GVim
+                | Self::Neovide
+                | Self::NvimQt
+                | Self::VimR
+                | Self::Emacs
+                | Self::EmacsClient
+                | Self::Sublime
+                | Self::Zed
+                | Self::Helix
+                | Self::Atom
+                | Self::TextMate
+                | Self::Xcode
+                | Self::IntelliJ
+                | Self::WebStorm
+                | Self::PhpStorm
+                | Self::PyCharm
+                | Self::RubyMine
+                | Self::GoLand
+                | Self::CLion
+                | Self::Rider
+                | Self::DataGrip
+                | Self::AndroidStudio
+        )
+    }
+
+    /// Returns `true` if this editor can open files on a remote host over SSH.
+    ///
+    /// Vim, NeoVim, and GVim use netrw's `scp://` URLs; the VS Code family
+    /// uses `--remote ssh-remote+host` (requires the Remote - SSH extension).
+    pub const fn supports_remote(&self) -> bool {
+        matches!(
+            self,
+            Self::VsCode
+                | Self::VsCodeInsiders
+                | Self::VSCodium
+                | Self::Cursor
+                | Self::Windsurf
+                | Self::Vim
+                | Self::NeoVim
+                | Self::GVim
+        )
+    }
+
+    /// Returns the flags that open a file read-only in this editor.
+    ///
+    /// | Editor | Flag |
+    /// |--------|------|
+    /// | Vim, NeoVim, Vi, GVim | `-R` |
+    /// | Nano | `-v` (view mode) |
+    /// | Ex | `-R` |
+    /// | Kakoune | `-ro` |
+    /// | Notepad++ | `-ro` |
+    ///
+    /// Returns an empty slice for editors without a read-only CLI flag
+    /// (e.g., VS Code, Kate).
+    pub const fn read_only_args(&self) -> &'static [&'static str] {
+        match self {
+            Self::Vim | Self::NeoVim | Self::Vi | Self::GVim | Self::Ex => &["-R"],
+            Self::Nano => &["-v"],
+            Self::Kakoune | Self::NotepadPlusPlus => &["-ro"],
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` if this editor can open files read-only from the CLI.
+    pub const fn supports_read_only(&self) -> bool {
+        !self.read_only_args().is_empty()
+    }
+
+    /// Returns the CLI flags that open a file without taking focus.
+    ///
+    /// Returns an empty slice for editors without such a flag; only Sublime
+    /// Text (`--background`) has one.
+    pub const fn background_args(&self) -> &'static [&'static str] {
+        match self {
+            Self::Sublime => &["--background"],
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` if this editor can open files without taking focus
+    /// (see [`EditorBuilder::focus()`](crate::EditorBuilder::focus)).
+    pub const fn supports_background(&self) -> bool {
+        !self.background_args().is_empty()
+    }
+
+    /// Returns `true` if this editor can be launched without loading the
+    /// user's config files (see [`EditorBuilder::isolated()`](crate::EditorBuilder::isolated)).
+    pub const fn supports_isolation(&self) -> bool {
+        matches!(
+            self,
+            Self::VsCode
+                | Self::VsCodeInsiders
+                | Self::VSCodium
+                | Self::Cursor
+                | Self::Windsurf
+                | Self::Vim
+                | Self::GVim
+                | Self::NeoVim
+                | Self::Emacs
+                | Self::Nano
+        )
+    }
+
+    /// Returns `true` if this editor can open a file in a given character
+    /// encoding (see [`EditorBuilder::encoding()`](crate::EditorBuilder::encoding)).
+    pub const fn supports_encoding(&self) -> bool {
+        matches!(
+            self,
+            Self::VsCode
+                | Self::VsCodeInsiders
+                | Self::VSCodium
+                | Self::Cursor
+                | Self::Windsurf
+                | Self::Vim
+                | Self::GVim
+                | Self::NeoVim
+        )
+    }
+
+    /// Returns `true` if this editor opens a path that doesn't exist yet as a
+    /// new, empty file.
+    ///
+    /// Terminal editors, the VS Code family, Sublime Text, Zed, Atom, Kate,
+    /// TextMate, and the Notepads all do. JetBrains IDEs refuse a missing
+    /// path outside an open project, and Xcode's `xed` fails without its
+    /// `--create` flag, so these return `false`. Unknown editors are assumed
+    /// to cope.
+    ///
+    /// This is advisory: opening never consults it. A missing file is an
+    /// error unless
+    /// [`EditorBuilder::create_if_missing()`](crate::EditorBuilder::create_if_missing)
+    /// is set, which creates it for every editor. Callers that would rather
+    /// hand a new path straight to editors that cope can check this first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::EditorKind;
+    ///
+    /// // JetBrains IDEs need the file to exist before they open it
+    /// let create = !EditorKind::IntelliJ.opens_nonexistent();
+    /// assert!(create);
+    /// ```
+    pub const fn opens_nonexistent(&self) -> bool {
+        !matches!(
+            self,
+            Self::Xcode
+                | Self::IntelliJ
+                | Self::WebStorm
+                | Self::PhpStorm
+                | Self::PyCharm
+                | Self::RubyMine
+                | Self::GoLand
+                | Self::CLion
+                | Self::Rider
+                | Self::DataGrip
+                | Self::AndroidStudio
+        )
+    }
+
+    /// Returns `true` if this editor can run commands on a file without a UI
+    /// (see [`Editor::run_headless()`](crate::Editor::run_headless)).
+    pub const fn supports_headless(&self) -> bool {
+        matches!(self, Self::Vim | Self::NeoVim)
+    }
+
+    /// Returns the CLI flags opensesame emits for this editor, by capability.
+    ///
+    /// Each entry maps a capability label (`"goto"`, `"wait"`, `"position"`,
+    /// `"read_only"`, ...) to the flag or argument form used for it. Forms
+    /// containing `{file}`, `{line}`, `{column}`, `{end}`, or `{encoding}` are templates showing
+    /// where the values go. This is intended for help screens and
+    /// documentation generators.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use opensesame::EditorKind;
+    ///
+    /// for (capability, flag) in EditorKind::VsCode.known_flags() {
+    ///     println!("{capability}: {flag}");
+    /// }
+    /// ```
+    #[allow(clippy::too_many_lines)] // One table per editor
+    pub const fn known_flags(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::VsCode
+            | Self::VsCodeInsiders
+            | Self::VSCodium
+            | Self::Cursor
+            | Self::Windsurf => &[
+                ("goto", "--goto"),
+                ("position", "{file}:{line}:{column}"),
+                ("wait", "--wait"),
+                ("remote", "--remote"),
+                ("isolated", "--user-data-dir"),
+                ("encoding", "--encoding"),
+                ("reuse_window", "--reuse-window"),
+                ("new_window", "--new-window"),
+            ],
+            Self::Vim | Self::GVim => &[
+                ("line", "+{line}"),
+                ("position", "+call cursor({line},{column})"),
+                ("position_normal", "+{line} -c normal! {column}|"),
+                ("read_only", "-R"),
+                ("split_right", "-O"),
+                ("split_below", "-o"),
+                ("isolated", "-u NONE -U NONE"),
+                ("encoding", "-c e ++enc={encoding}"),
+                ("select_lines", "-c normal! V{end}Go"),
+                ("scroll", "-c normal! zz"),
+                ("scroll_top", "-c normal! zt"),
+                ("scroll_bottom", "-c normal! zb"),
+            ],
+            Self::NeoVim => &[
+                ("line", "+{line}"),
+                ("position", "+call cursor({line},{column})"),
+                ("position_normal", "+{line} -c normal! {column}|"),
+                ("read_only", "-R"),
+                ("split_right", "-O"),
+                ("split_below", "-o"),
+                ("isolated", "--clean"),
+                ("encoding", "-c e ++enc={encoding}"),
+                ("select_lines", "-c normal! V{end}Go"),
+                ("scroll", "-c normal! zz"),
+                ("scroll_top", "-c normal! zt"),
+                ("scroll_bottom", "-c normal! zb"),
+            ],
+            Self::Neovide => &[
+                ("no_fork", "--no-fork"),
+                ("line", "{file} -- +{line}"),
+                ("position", "{file} -- +call cursor({line},{column})"),
+            ],
+            Self::NvimQt => &[
+                ("no_fork", "--nofork"),
+                ("line", "{file} -- +{line}"),
+                ("position", "{file} -- +call cursor({line},{column})"),
+            ],
+            Self::VimR => &[
+                ("line", "--nvim +{line}"),
+                ("position", "--nvim +call cursor({line},{column})"),
+                ("wait", "--wait"),
+            ],
+            Self::Vi => &[
+                ("line", "+{line}"),
+                ("position", "+call cursor({line},{column})"),
+                ("position_normal", "+{line} -c normal! {column}|"),
+                ("read_only", "-R"),
+            ],
+            Self::Emacs => &[
+                ("line", "+{line}"),
+                ("position", "+{line}:{column}"),
+                ("wait", "--eval"),
+                ("isolated", "-Q"),
+            ],
+            Self::EmacsClient => &[
+                ("line", "+{line}"),
+                ("position", "+{line}:{column}"),
+                ("no_wait", "-n"),
+                ("start_daemon", "-a ''"),
+            ],
+            Self::Sublime => &[
+                ("position", "{file}:{line}:{column}"),
+                ("wait", "--wait"),
+                ("new_window", "--new-window"),
+                ("background", "--background"),
+            ],
+            Self::Zed | Self::Atom => &[("position", "{file}:{line}:{column}"), ("wait", "--wait")],
+            Self::Helix => &[
+                ("position", "{file}:{line}:{column}"),
+                ("split_right", "--vsplit"),
+                ("split_below", "--hsplit"),
+            ],
+            Self::Nano => &[
+                ("line", "+{line}"),
+                ("position", "+{line},{column}"),
+                ("read_only", "-v"),
+                ("isolated", "--ignorercfiles"),
+            ],
+            Self::Ex => &[("line", "+{line}"), ("read_only", "-R")],
+            Self::Kakoune => &[
+                ("line", "+{line}"),
+                ("position", "+{line}:{column}"),
+                ("read_only", "-ro"),
+            ],
+            Self::TextMate | Self::Xcode => &[("line", "--line"), ("wait", "--wait")],
+            Self::NotepadPlusPlus => &[
+                ("line", "-n{line}"),
+                ("column", "-c{column}"),
+                ("read_only", "-ro"),
+            ],
+            Self::Kate => &[("line", "--line"), ("column", "--column")],
+            Self::IntelliJ
+            | Self::WebStorm
+            | Self::PhpStorm
+            | Self::PyCharm
+            | Self::RubyMine
+            | Self::GoLand
+            | Self::CLion
+            | Self::Rider
+            | Self::DataGrip
+            | Self::AndroidStudio => &[
+                ("line", "{file}:{line}"),
+                ("position", "--line {line} --column {column}"),
+                ("wait", "--wait"),
+            ],
+            Self::Ed | Self::Notepad | Self::Unknown => &[],
+        }
+    }
+
+    /// Returns `true` if launching this editor returns before the file is closed.
+    ///
+    /// - VS Code and its forks, Sublime Text, Zed, Atom, TextMate, Xcode
+    ///   (`xed`), VimR, and the JetBrains launchers hand the file to a
+    ///   running (or newly started) app and exit straight away.
+    /// - `emacsclient` hands the file to the Emacs server; opensesame passes
+    ///   `-n` unless waiting, so it returns straight away too.
+    /// - GVim forks into the background unless given `-f`, which
+    ///   opensesame never passes. Neovide and nvim-qt are always passed
+    ///   `--no-fork`/`--nofork`, so they stay in the foreground.
+    /// - Terminal editors, Emacs, Kate, Notepad, and Notepad++ stay in the
+    ///   foreground until the file is closed. Kate and Notepad++ only exit
+    ///   early when an instance is already running, so they're treated as
+    ///   blocking.
+    /// - Unknown editors are assumed to block.
+    ///
+    /// For a forking editor, [`supports_wait()`](Self::supports_wait) tells
+    /// whether it can be made to block anyway.
+    pub const fn forks_on_launch(&self) -> bool {
+        matches!(
+            self,
+            Self::VsCode
+                | Self::VsCodeInsiders
+                | Self::VSCodium
+                | Self::Cursor
+                | Self::Windsurf
+                | Self::EmacsClient
+                | Self::GVim
+                | Self::VimR
+                | Self::Sublime
+                | Self::Zed
+                | Self::Atom
+                | Self::TextMate
+                | Self::Xcode
+                | Self::IntelliJ
+                | Self::WebStorm
+                | Self::PhpStorm
+                | Self::PyCharm
+                | Self::RubyMine
+                | Self::GoLand
+                | Self::CLion
+                | Self::Rider
+                | Self::DataGrip
+                | Self::AndroidStudio
+        )
+    }
+
+    /// Returns `true` if [`EditorBuilder::open()`](crate::EditorBuilder::open)
+    /// blocks until the user closes the file.
+    ///
+    /// Terminal and unknown editors always block. GUI editors block only
+    /// when `wait` is requested: those that [fork](Self::forks_on_launch)
+    /// need to [support it](Self::supports_wait), and the others are left
+    /// running in the foreground instead of being detached.
+    pub const fn will_block(&self, wait: bool) -> bool {
+        if !self.is_gui_editor() {
+            return true;
+        }
+        wait && (self.supports_wait() || !self.forks_on_launch())
+    }
+
+    /// Returns the flag that makes this editor wait for the file to close,
+    /// if it has one.
+    ///
+    /// `emacsclient` waits by default, so it has none.
+    pub const fn wait_flag(&self) -> Option<&'static str> {
+        if self.supports_wait() && !matches!(self, Self::EmacsClient) {
+            Some("--wait")
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if this editor supports the `--wait` flag.
+    ///
+    /// `emacsclient` waits by default and is passed `-n` when not waiting.
+    pub const fn supports_wait(&self) -> bool {
+        matches!(
+            self,
+            Self::VsCode
+                | Self::VsCodeInsiders
+                | Self::VSCodium
+                | Self::Cursor
+                | Self::Windsurf
+                | Self::EmacsClient
+                | Self::VimR
+                | Self::Sublime
+                | Self::Zed
+                | Self::Atom
+                | Self::TextMate
+                | Self::Xcode
+                | Self::IntelliJ
+                | Self::WebStorm
+                | Self::PhpStorm
+                | Self::PyCharm
+                | Self::RubyMine
+                | Self::GoLand
+                | Self::CLion
+                | Self::Rider
+                | Self::DataGrip
+                | Self::AndroidStudio
+        )
+    }
+}
+
+/// Stack buffer size for normalized names; longer than any known name plus
+/// a Windows extension, so names that don't fit can't match.
+const NAME_BUF_LEN: usize = 32;
+
+/// Lowercases an ASCII name into `buf`, optionally dropping `-` and `_`.
+///
+/// Returns `None` if the result doesn't fit in the buffer.
+fn normalize_ascii<'a>(
+    name: &str,
+    strip_separators: bool,
+    buf: &'a mut [u8; NAME_BUF_LEN],
+) -> Option<&'a str> {
+    let mut len = 0;
+    for byte in name.bytes() {
+        if strip_separators && matches!(byte, b'-' | b'_') {
+            continue;
+        }
+        *buf.get_mut(len)? = byte.to_ascii_lowercase();
+        len += 1;
+    }
+    core::str::from_utf8(&buf[..len]).ok()
+}
+
+/// Terminal editors without a dedicated kind that accept `+LINE file`.
+const PLUS_LINE_EDITORS: &[&str] = &[
+    "jed", "joe", "jmacs", "jpico", "jstar", "mcedit", "mg", "micro", "ne", "pico",
+];
+
+/// Returns `true` if `kind` is [`Unknown`](EditorKind::Unknown) and
+/// `binary` names one of [`PLUS_LINE_EDITORS`], so `+LINE file` positions it.
+pub(crate) fn takes_plus_line(kind: EditorKind, binary: Option<&str>) -> bool {
+    kind.is_unknown() && binary.is_some_and(is_plus_line_editor)
+}
+
+/// Returns `true` if `binary` names one of [`PLUS_LINE_EDITORS`].
+fn is_plus_line_editor(binary: &str) -> bool {
+    let name = file_name(binary);
+    let mut buf = [0; NAME_BUF_LEN];
+    normalize_ascii(name, false, &mut buf)
+        .is_some_and(|name| PLUS_LINE_EDITORS.contains(&strip_script_extension(name)))
+}
+
+/// Returns the last component of `path`, like [`std::path::Path::file_name()`].
+///
+/// `/` separates components everywhere, and `\` does too on Windows.
+fn file_name(path: &str) -> &str {
+    let is_separator = |c| c == '/' || (cfg!(windows) && c == '\\');
+    path.rsplit(is_separator)
+        .find(|part| !part.is_empty())
+        .unwrap_or(path)
+}
+
+/// Strips common Windows executable extensions.
+fn strip_script_extension(name: &str) -> &str {
+    name.strip_suffix(".exe")
+        .or_else(|| name.strip_suffix(".cmd"))
+        .or_else(|| name.strip_suffix(".bat"))
+        .unwrap_or(name)
+}
+
+impl fmt::Display for EditorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::VsCode => "VS Code",
+            Self::VsCodeInsiders => "VS Code Insiders",
+            Self::VSCodium => "VSCodium",
+            Self::Cursor => "Cursor",
+            Self::Windsurf => "Windsurf",
+            Self::Vim => "Vim",
+            Self::NeoVim => "NeoVim",
+            Self::Vi => "Vi",
+            Self::GVim => "GVim",
+            Self::Neovide => "Neovide",
+            Self::NvimQt => "Neovim Qt",
+            Self::VimR => "VimR",
+            Self::Emacs => "Emacs",
+            Self::EmacsClient => "Emacs Client",
+            Self::Sublime => "Sublime Text",
+            Self::Zed => "Zed",
+            Self::Helix => "Helix",
+            Self::Atom => "Atom",
+            Self::Kate => "Kate",
+            Self::Nano => "Nano",
+            Self::Ex => "Ex",
+            Self::Ed => "Ed",
+            Self::Kakoune => "Kakoune",
+            Self::TextMate => "TextMate",
+            Self::Xcode => "Xcode",
+            Self::NotepadPlusPlus => "Notepad++",
+            Self::Notepad => "Notepad",
+            Self::IntelliJ => "IntelliJ IDEA",
+            Self::WebStorm => "WebStorm",
+            Self::PhpStorm => "PhpStorm",
+            Self::PyCharm => "PyCharm",
+            Self::RubyMine => "RubyMine",
+            Self::GoLand => "GoLand",
+            Self::CLion => "CLion",
+            Self::Rider => "Rider",
+            Self::DataGrip => "DataGrip",
+            Self::AndroidStudio => "Android Studio",
+            Self::Unknown => "Unknown Editor",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Serializes as the canonical name from [`EditorKind::as_str()`].
+#[cfg(feature = "serde")]
+impl serde::Serialize for EditorKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Deserializes any name [`EditorKind::from_name()`] accepts, so `"nvim"`
+/// and `"NeoVim"` both work. `Unknown` is not accepted.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for EditorKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = alloc::string::String::deserialize(deserializer)?;
+        Self::from_name(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, KIND_NAMES))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_kind_from_binary() {
+        assert_eq!(EditorKind::from_binary("code"), EditorKind::VsCode);
+        assert_eq!(EditorKind::from_binary("code.exe"), EditorKind::VsCode);
+        assert_eq!(EditorKind::from_binary("code.cmd"), EditorKind::VsCode);
+        assert_eq!(EditorKind::from_binary("/usr/bin/code"), EditorKind::VsCode);
+        assert_eq!(EditorKind::from_binary("vim"), EditorKind::Vim);
+        assert_eq!(EditorKind::from_binary("nvim"), EditorKind::NeoVim);
+        assert_eq!(EditorKind::from_binary("emacs"), EditorKind::Emacs);
+        assert_eq!(EditorKind::from_binary("subl"), EditorKind::Sublime);
+        assert_eq!(EditorKind::from_binary("zed"), EditorKind::Zed);
+        assert_eq!(EditorKind::from_binary("hx"), EditorKind::Helix);
+        assert_eq!(EditorKind::from_binary("nano"), EditorKind::Nano);
+        assert_eq!(EditorKind::from_binary("/bin/ed"), EditorKind::Ed);
+        assert_eq!(EditorKind::from_binary("ex"), EditorKind::Ex);
+        assert_eq!(EditorKind::from_binary("cursor"), EditorKind::Cursor);
+        assert_eq!(EditorKind::from_binary("windsurf"), EditorKind::Windsurf);
+        assert_eq!(
+            EditorKind::from_binary("notepad++"),
+            EditorKind::NotepadPlusPlus
+        );
+        assert_eq!(EditorKind::from_binary("idea"), EditorKind::IntelliJ);
+        assert_eq!(
+            EditorKind::from_binary("unknown-editor"),
+            EditorKind::Unknown
+        );
+    }
+
+    /// The allocating implementations `from_name`/`from_binary` replaced.
+    fn reference_from_name(name: &str) -> Option<EditorKind> {
+        EditorKind::match_name(&name.to_lowercase().replace(['-', '_'], ""))
+    }
+
+    fn reference_from_binary(binary: &str) -> EditorKind {
+        let name = std::path::Path::new(binary)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(binary)
+            .to_lowercase();
+        EditorKind::match_binary(strip_script_extension(&name))
+    }
+
+    /// Names covering every known spelling plus case, separator, extension,
+    /// length, and non-ASCII edge cases.
+    fn parity_inputs() -> Vec<String> {
+        let mut inputs: Vec<String> = [
+            "",
+            "-",
+            "_",
+            "VS-Code",
+            "vs_code",
+            "Code.EXE",
+            "CODE.cmd",
+            "nvim.bat",
+            "vim.exe.exe",
+            "/usr/local/bin/NVIM",
+            "C:\\Tools\\code.exe",
+            "code-insiders",
+            "codeinsiders",
+            "Notepad++.exe",
+            "android-studio",
+            "android_studio",
+            "sublime_text",
+            "sublime-text",
+            "\u{212A}ate",
+            "K\u{0130}te",
+            "\u{00C9}macs",
+            "v\u{0131}m",
+            "idea64",
+            "IDEA64.EXE",
+            "visual-studio-code-but-much-longer-than-any-editor-name",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        for kind in EditorKind::all() {
+            for name in [kind.as_str(), kind.default_binary()] {
+                inputs.push(name.to_string());
+                inputs.push(name.to_uppercase());
+                inputs.push(format!("{name}.exe"));
+                inputs.push(format!("/opt/bin/{name}"));
+            }
+        }
+        inputs
+    }
+
+    #[test]
+    fn test_editor_kind_from_name_matches_reference() {
+        for input in parity_inputs() {
+            assert_eq!(
+                EditorKind::from_name(&input),
+                reference_from_name(&input),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_editor_kind_from_binary_matches_reference() {
+        for input in parity_inputs() {
+            assert_eq!(
+                EditorKind::from_binary(&input),
+                reference_from_binary(&input),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_editor_kind_non_ascii_lowercasing() {
+        // The Kelvin sign lowercases to ASCII 'k'
+        assert_eq!(EditorKind::from_name("\u{212A}ate"), Some(EditorKind::Kate));
+        assert_eq!(EditorKind::from_binary("\u{212A}ate"), EditorKind::Kate);
+    }
+
+    #[test]
+    fn test_editor_kind_is_unknown() {
+        assert!(EditorKind::Unknown.is_unknown());
+        assert!(EditorKind::from_binary("micro").is_unknown());
+        assert!(!EditorKind::Vim.is_unknown());
+    }
+
+    #[test]
+    fn test_unknown_format_position_with_binary() {
+        let kind = EditorKind::Unknown;
+        assert_eq!(
+            kind.format_position(Some("/usr/bin/micro"), "test.rs", Some(42), Some(7), false),
+            vec!["+42", "test.rs"]
+        );
+        assert_eq!(
+            kind.format_position(Some("joe"), "test.rs", None, None, false),
+            vec!["test.rs"]
+        );
+        assert_eq!(
+            kind.format_position(Some("my-editor"), "test.rs", Some(42), None, false),
+            vec!["test.rs"]
+        );
+        assert_eq!(
+            kind.format_position(None, "test.rs", Some(42), None, false),
+            vec!["test.rs"]
+        );
+
+        // Known kinds ignore the hint
+        assert_eq!(
+            EditorKind::Vim.format_position(Some("micro"), "test.rs", Some(42), None, false),
+            EditorKind::Vim.position_args("test.rs", Some(42), None, false)
+        );
+    }
+
+    #[test]
+    fn test_unknown_terminal_editor_with_binary() {
+        assert!(EditorKind::Unknown.is_terminal_editor_hinted(Some("MICRO.EXE")));
+        assert!(!EditorKind::Unknown.is_terminal_editor_hinted(Some("my-editor")));
+        assert!(!EditorKind::Unknown.is_terminal_editor_hinted(None));
+        assert!(EditorKind::Vim.is_terminal_editor_hinted(None));
+        assert!(!EditorKind::VsCode.is_terminal_editor_hinted(Some("micro")));
+    }
+
+    #[test]
+    fn test_editor_kind_display() {
+        assert_eq!(EditorKind::VsCode.to_string(), "VS Code");
+        assert_eq!(EditorKind::NeoVim.to_string(), "NeoVim");
+        assert_eq!(EditorKind::Helix.to_string(), "Helix");
+    }
+
+    #[test]
+    fn test_editor_kind_properties() {
+        assert!(EditorKind::Vim.is_terminal_editor());
+        assert!(EditorKind::NeoVim.is_terminal_editor());
+        assert!(EditorKind::Nano.is_terminal_editor());
+        assert!(EditorKind::Ex.is_terminal_editor());
+        assert!(EditorKind::Ed.is_terminal_editor());
+        assert!(!EditorKind::VsCode.is_terminal_editor());
+
+        assert!(EditorKind::VsCode.supports_column());
+        assert!(EditorKind::Vim.supports_column());
+        assert!(!EditorKind::TextMate.supports_column());
+        assert!(EditorKind::IntelliJ.supports_column());
+        assert!(EditorKind::Rider.supports_column());
+        assert!(!EditorKind::Ex.supports_column());
+        assert!(!EditorKind::Ed.supports_column());
+
+        assert!(EditorKind::VsCode.supports_wait());
+        assert!(!EditorKind::Vim.supports_wait());
+
+        assert!(EditorKind::NeoVim.supports_split());
+        assert!(EditorKind::Helix.supports_split());
+        assert!(!EditorKind::VsCode.supports_split());
+        assert!(!EditorKind::Vi.supports_split());
+    }
+
+    #[test]
+    fn test_editor_kind_forks_on_launch() {
+        assert!(EditorKind::VsCode.forks_on_launch());
+        assert!(EditorKind::GVim.forks_on_launch());
+        assert!(!EditorKind::Vim.forks_on_launch());
+        assert!(EditorKind::EmacsClient.forks_on_launch());
+        assert!(!EditorKind::Unknown.forks_on_launch());
+
+        // Terminal editors never fork
+        for kind in EditorKind::all() {
+            assert!(
+                !(kind.is_terminal_editor() && kind.forks_on_launch()),
+                "{kind}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_editor_kind_will_block() {
+        assert!(EditorKind::VsCode.will_block(true));
+        assert!(!EditorKind::VsCode.will_block(false));
+        assert!(!EditorKind::GVim.will_block(true));
+        assert!(EditorKind::Vim.will_block(false));
+        assert!(EditorKind::Unknown.will_block(false));
+        assert!(EditorKind::Kate.will_block(true));
+        assert!(!EditorKind::Kate.will_block(false));
+    }
+
+    #[test]
+    fn test_editor_kind_wait_flag() {
+        assert_eq!(EditorKind::VsCode.wait_flag(), Some("--wait"));
+        assert_eq!(EditorKind::IntelliJ.wait_flag(), Some("--wait"));
+        assert_eq!(EditorKind::EmacsClient.wait_flag(), None);
+        assert_eq!(EditorKind::Vim.wait_flag(), None);
+    }
+
+    #[test]
+    fn test_editor_kind_is_gui_editor() {
+        assert!(EditorKind::VsCode.is_gui_editor());
+        assert!(EditorKind::GVim.is_gui_editor());
+        assert!(EditorKind::Kate.is_gui_editor());
+        assert!(!EditorKind::Vim.is_gui_editor());
+        assert!(!EditorKind::Helix.is_gui_editor());
+        assert!(!EditorKind::Unknown.is_gui_editor());
+    }
+
+    #[test]
+    fn test_editor_kind_supports_multiple_files() {
+        assert!(EditorKind::Vim.supports_multiple_files());
+        assert!(EditorKind::Nano.supports_multiple_files());
+        assert!(EditorKind::VsCode.supports_multiple_files());
+        assert!(!EditorKind::Ed.supports_multiple_files());
+        assert!(!EditorKind::Notepad.supports_multiple_files());
+        assert!(!EditorKind::Unknown.supports_multiple_files());
+    }
+
+    #[test]
+    fn test_editor_kind_opens_nonexistent() {
+        assert!(EditorKind::Vim.opens_nonexistent());
+        assert!(EditorKind::VsCode.opens_nonexistent());
+        assert!(EditorKind::Unknown.opens_nonexistent());
+        assert!(!EditorKind::IntelliJ.opens_nonexistent());
+        assert!(!EditorKind::AndroidStudio.opens_nonexistent());
+        assert!(!EditorKind::Xcode.opens_nonexistent());
+    }
+
+    #[test]
+    fn test_editor_kind_supports_folders() {
+        assert!(EditorKind::VsCode.supports_folders());
+        assert!(EditorKind::Zed.supports_folders());
+        assert!(EditorKind::NeoVim.supports_folders());
+        assert!(EditorKind::IntelliJ.supports_folders());
+        assert!(!EditorKind::Nano.supports_folders());
+        assert!(!EditorKind::Ed.supports_folders());
+        assert!(!EditorKind::Unknown.supports_folders());
+    }
+
+    #[test]
+    fn test_editor_kind_supports_remote() {
+        assert!(EditorKind::Vim.supports_remote());
+        assert!(EditorKind::VsCode.supports_remote());
+        assert!(!EditorKind::Nano.supports_remote());
+        assert!(!EditorKind::Sublime.supports_remote());
+    }
+
+    #[test]
+    fn test_editor_kind_background_args() {
+        assert_eq!(EditorKind::Sublime.background_args(), &["--background"]);
+        assert!(EditorKind::Sublime.supports_background());
+        assert!(EditorKind::VsCode.background_args().is_empty());
+        assert!(!EditorKind::Vim.supports_background());
+    }
+
+    #[test]
+    fn test_editor_kind_read_only_args() {
+        assert_eq!(EditorKind::Vim.read_only_args(), &["-R"]);
+        assert_eq!(EditorKind::NeoVim.read_only_args(), &["-R"]);
+        assert_eq!(EditorKind::Nano.read_only_args(), &["-v"]);
+        assert!(EditorKind::VsCode.read_only_args().is_empty());
+        assert!(EditorKind::Kate.read_only_args().is_empty());
+
+        assert!(EditorKind::Vim.supports_read_only());
+        assert!(!EditorKind::VsCode.supports_read_only());
+    }
+
+    #[test]
+    fn test_editor_kind_known_flags_vscode() {
+        let flags = EditorKind::VsCode.known_flags();
+        assert!(flags.contains(&("goto", "--goto")));
+        assert!(flags.contains(&("wait", "--wait")));
+        assert!(flags.contains(&("remote", "--remote")));
+        assert_eq!(EditorKind::Cursor.known_flags(), flags);
+        assert!(EditorKind::Notepad.known_flags().is_empty());
+    }
+
+    #[test]
+    fn test_editor_kind_supports_isolation() {
+        assert!(EditorKind::Vim.supports_isolation());
+        assert!(EditorKind::NeoVim.supports_isolation());
+        assert!(EditorKind::VsCode.supports_isolation());
+        assert!(!EditorKind::Vi.supports_isolation());
+        assert!(!EditorKind::Kate.supports_isolation());
+
+        for kind in EditorKind::all() {
+            let documented = kind
+                .known_flags()
+                .iter()
+                .any(|(capability, _)| *capability == "isolated");
+            assert_eq!(documented, kind.supports_isolation(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_editor_kind_supports_scroll() {
+        assert!(EditorKind::Vim.supports_scroll());
+        assert!(EditorKind::NeoVim.supports_scroll());
+        assert!(!EditorKind::VsCode.supports_scroll());
+
+        for kind in EditorKind::all() {
+            let documented = kind
+                .known_flags()
+                .iter()
+                .any(|(capability, _)| *capability == "scroll");
+            assert_eq!(documented, kind.supports_scroll(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_editor_kind_supports_selection() {
+        assert!(EditorKind::Vim.supports_selection());
+        assert!(!EditorKind::VsCode.supports_selection());
+
+        for kind in EditorKind::all() {
+            let documented = kind
+                .known_flags()
+                .iter()
+                .any(|(capability, _)| *capability == "select_lines");
+            assert_eq!(documented, kind.supports_selection(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_editor_kind_supports_encoding() {
+        assert!(EditorKind::VsCode.supports_encoding());
+        assert!(EditorKind::Vim.supports_encoding());
+        assert!(!EditorKind::Vi.supports_encoding());
+        assert!(!EditorKind::Nano.supports_encoding());
+
+        for kind in EditorKind::all() {
+            let documented = kind
+                .known_flags()
+                .iter()
+                .any(|(capability, _)| *capability == "encoding");
+            assert_eq!(documented, kind.supports_encoding(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_editor_kind_known_flags_match_read_only_args() {
+        for kind in [
+            EditorKind::Vim,
+            EditorKind::Nano,
+            EditorKind::NotepadPlusPlus,
+            EditorKind::VsCode,
+        ] {
+            let documented = kind
+                .known_flags()
+                .iter()
+                .find(|(capability, _)| *capability == "read_only")
+                .map(|(_, flag)| *flag);
+            assert_eq!(
+                documented,
+                kind.read_only_args().first().copied(),
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_editor_kind_from_name() {
+        // Case insensitive
+        assert_eq!(EditorKind::from_name("NeoVim"), Some(EditorKind::NeoVim));
+        assert_eq!(EditorKind::from_name("neovim"), Some(EditorKind::NeoVim));
+        assert_eq!(EditorKind::from_name("NEOVIM"), Some(EditorKind::NeoVim));
+
+        // With separators
+        assert_eq!(EditorKind::from_name("vs-code"), Some(EditorKind::VsCode));
+        assert_eq!(EditorKind::from_name("vs_code"), Some(EditorKind::VsCode));
+
+        // Aliases
+        assert_eq!(EditorKind::from_name("nvim"), Some(EditorKind::NeoVim));
+        assert_eq!(EditorKind::from_name("code"), Some(EditorKind::VsCode));
+        assert_eq!(EditorKind::from_name("subl"), Some(EditorKind::Sublime));
+        assert_eq!(EditorKind::from_name("hx"), Some(EditorKind::Helix));
+        assert_eq!(EditorKind::from_name("idea"), Some(EditorKind::IntelliJ));
+
+        // Unknown returns None
+        assert_eq!(EditorKind::from_name("unknown"), None);
+        assert_eq!(EditorKind::from_name(""), None);
+    }
+
+    #[test]
+    fn test_editor_kind_as_str() {
+        assert_eq!(EditorKind::VsCode.as_str(), "VsCode");
+        assert_eq!(EditorKind::NeoVim.as_str(), "NeoVim");
+        assert_eq!(EditorKind::Helix.as_str(), "Helix");
+        assert_eq!(EditorKind::IntelliJ.as_str(), "IntelliJ");
+        assert_eq!(EditorKind::Unknown.as_str(), "Unknown");
+    }
+
+    #[test]
+    fn test_editor_kind_roundtrip() {
+        // Test that from_name(as_str()) returns the same kind
+        let kinds = [
+            EditorKind::VsCode,
+            EditorKind::NeoVim,
+            EditorKind::Vim,
+            EditorKind::Emacs,
+            EditorKind::Sublime,
+            EditorKind::Zed,
+            EditorKind::Helix,
+            EditorKind::Cursor,
+            EditorKind::Windsurf,
+            EditorKind::IntelliJ,
+        ];
+
+        for kind in kinds {
+            let name = kind.as_str();
+            let parsed = EditorKind::from_name(name);
+            assert_eq!(parsed, Some(kind), "roundtrip failed for {kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_editor_kind_from_str() {
+        assert_eq!("nvim".parse::<EditorKind>().unwrap(), EditorKind::NeoVim);
+        assert_eq!(
+            "Visual-Studio-Code".parse::<EditorKind>().unwrap(),
+            EditorKind::VsCode
+        );
+
+        let err = "notepad2".parse::<EditorKind>().unwrap_err();
+        assert!(err.is_invalid_config());
+        let message = err.to_string();
+        assert!(message.contains("unknown editor 'notepad2'"), "{message}");
+        assert!(message.contains("NeoVim, Vi, GVim"), "{message}");
+        assert!("unknown".parse::<EditorKind>().is_err());
+    }
+
+    #[test]
+    fn test_nvim_gui_frontends() {
+        assert_eq!(EditorKind::from_binary("neovide"), EditorKind::Neovide);
+        assert_eq!(EditorKind::from_binary("nvim-qt.exe"), EditorKind::NvimQt);
+        assert_eq!(
+            EditorKind::from_binary("/usr/local/bin/vimr"),
+            EditorKind::VimR
+        );
+        assert_eq!(EditorKind::from_name("nvim-qt"), Some(EditorKind::NvimQt));
+        assert_eq!(EditorKind::from_name("Neovide"), Some(EditorKind::Neovide));
+        assert_eq!(EditorKind::from_name("VimR"), Some(EditorKind::VimR));
+
+        for kind in [EditorKind::Neovide, EditorKind::NvimQt, EditorKind::VimR] {
+            assert!(!kind.is_terminal_editor(), "{kind:?}");
+            assert!(kind.is_gui_editor(), "{kind:?}");
+            assert!(kind.supports_column(), "{kind:?}");
+            assert_eq!(kind.column_semantics(), ColumnSemantics::Byte);
+            assert!(!kind.will_block(false), "{kind:?}");
+            assert!(kind.will_block(true), "{kind:?}");
+        }
+        // Neovide and nvim-qt are kept in the foreground; VimR hands off
+        assert!(!EditorKind::Neovide.forks_on_launch());
+        assert!(EditorKind::VimR.forks_on_launch());
+        assert_eq!(EditorKind::VimR.wait_flag(), Some("--wait"));
+    }
+
+    #[test]
+    fn test_kakoune() {
+        assert_eq!(EditorKind::from_name("kakoune"), Some(EditorKind::Kakoune));
+        assert_eq!(EditorKind::from_name("kak"), Some(EditorKind::Kakoune));
+        assert_eq!(EditorKind::from_binary("/usr/bin/kak"), EditorKind::Kakoune);
+        assert_eq!(EditorKind::Kakoune.default_binary(), "kak");
+        assert!(EditorKind::Kakoune.is_terminal_editor());
+        assert!(EditorKind::Kakoune.supports_column());
+        assert_eq!(EditorKind::Kakoune.read_only_args(), ["-ro"]);
+    }
+
+    #[test]
+    fn test_family() {
+        assert_eq!(EditorKind::Cursor.family(), EditorFamily::VsCode);
+        assert_eq!(EditorKind::Windsurf.family(), EditorFamily::VsCode);
+        assert_eq!(EditorKind::PyCharm.family(), EditorFamily::JetBrains);
+        assert_eq!(EditorKind::GoLand.family(), EditorFamily::JetBrains);
+        assert_eq!(EditorKind::AndroidStudio.family(), EditorFamily::JetBrains);
+        assert_eq!(EditorKind::VimR.family(), EditorFamily::Vim);
+        assert_eq!(EditorKind::EmacsClient.family(), EditorFamily::Emacs);
+        assert_eq!(EditorKind::Sublime.family(), EditorFamily::Sublime);
+        assert_eq!(EditorKind::Zed.family(), EditorFamily::Other);
+        assert_eq!(EditorKind::Unknown.family(), EditorFamily::Other);
+
+        // Members of a family share the capabilities that follow the argument builder
+        for kind in EditorKind::all() {
+            match kind.family() {
+                EditorFamily::VsCode => assert!(kind.supports_multiple_positions(), "{kind:?}"),
+                EditorFamily::Vim => {
+                    assert_eq!(kind.column_semantics(), ColumnSemantics::Byte, "{kind:?}");
+                }
+                EditorFamily::JetBrains => assert!(kind.supports_wait(), "{kind:?}"),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let caps = |line, column, wait, terminal, folder, reuse_window| Capabilities {
+            supports_line: line,
+            supports_column: column,
+            supports_wait: wait,
+            is_terminal: terminal,
+            is_gui: !terminal,
+            supports_folder: folder,
+            supports_reuse_window: reuse_window,
+        };
+        assert_eq!(
+            EditorKind::VsCode.capabilities(),
+            caps(true, true, true, false, true, true)
+        );
+        assert_eq!(
+            EditorKind::Vim.capabilities(),
+            caps(true, true, false, true, true, false)
+        );
+        assert_eq!(
+            EditorKind::IntelliJ.capabilities(),
+            caps(true, true, true, false, true, false)
+        );
+        assert_eq!(
+            EditorKind::GoLand.capabilities(),
+            EditorKind::IntelliJ.capabilities()
+        );
+        assert_eq!(
+            EditorKind::Notepad.capabilities(),
+            caps(false, false, false, false, false, false)
+        );
+
+        let unknown = EditorKind::Unknown.capabilities();
+        assert!(!unknown.supports_line && !unknown.is_terminal && !unknown.is_gui);
+    }
+
+    #[test]
+    fn test_kind_names_match_all() {
+        let names: Vec<_> = EditorKind::known().iter().map(EditorKind::as_str).collect();
+        assert_eq!(names, KIND_NAMES);
+    }
+
+    #[test]
+    fn test_all_lists_every_variant_in_order() {
+        // Discriminants count the variants: a variant missing from `all()`
+        // shifts every later one, including `Unknown`
+        for (index, kind) in EditorKind::all().iter().enumerate() {
+            assert_eq!(*kind as usize, index, "{kind:?}");
+        }
+        assert_eq!(EditorKind::Unknown as usize + 1, EditorKind::all().len());
+
+        assert_eq!(EditorKind::known().len() + 1, EditorKind::all().len());
+        assert!(EditorKind::known().iter().all(|kind| !kind.is_unknown()));
+        assert!(
+            EditorKind::known()
+                .iter()
+                .all(|kind| EditorKind::from_name(kind.as_str()) == Some(*kind))
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_kind_serde_roundtrip() {
+        for kind in EditorKind::known() {
+            let json = serde_json::to_string(kind).unwrap();
+            assert_eq!(json, format!("\"{}\"", kind.as_str()));
+            assert_eq!(serde_json::from_str::<EditorKind>(&json).unwrap(), *kind);
+        }
+
+        assert_eq!(
+            serde_json::from_str::<EditorKind>("\"nvim\"").unwrap(),
+            EditorKind::NeoVim
+        );
+        let err = serde_json::from_str::<EditorKind>("\"notepad2\"").unwrap_err();
+        assert!(
+            err.to_string().contains("unknown variant `notepad2`"),
+            "{err}"
+        );
+    }
+}
@@ -79,14 +79,16 @@
 //! | Windsurf | `windsurf` | ✓ |
 //! | Vim | `vim` | ✓ |
 //! | NeoVim | `nvim` | ✓ |
+//! | Neovide, Neovim Qt, VimR | `neovide`, `nvim-qt`, `vimr` | ✓ |
 //! | Emacs | `emacs` | ✓ |
 //! | Sublime Text | `subl` | ✓ |
 //! | Zed | `zed` | ✓ |
 //! | Helix | `hx` | ✓ |
 //! | Nano | `nano` | ✓ |
+//! | Kakoune | `kak` | ✓ |
 //! | TextMate | `mate` | Line only |
 //! | Notepad++ | `notepad++` | ✓ |
-//! | JetBrains IDEs | `idea`, `webstorm`, etc. | Line only |
+//! | JetBrains IDEs | `idea`, `webstorm`, etc. | ✓ |
 //! | Xcode | `xed` | Line only |
 //!
 //! ## Configuration
@@ -132,15 +134,79 @@
 //! [dependencies]
 //! opensesame = { version = "0.1", features = ["serde"] }
 //! ```
+//!
+//! ### Pattern Positioning
+//!
+//! Enable the `regex` feature to open at the first match of a regex within a
+//! line range, via `EditorBuilder::pattern_in_range()`.
+//!
+//! ### Editor Cache
+//!
+//! Enable the `cache` feature to have [`Editor::detect()`] reuse the editor
+//! found by a previous process, stored in the user cache directory. See
+//! `EditorCache` for the location, TTL, and invalidation rules.
+//!
+//! ### Tracing
+//!
+//! Enable the `tracing` feature to emit [`tracing`](https://docs.rs/tracing)
+//! events while resolving and launching editors: each source checked, each
+//! candidate binary and whether it was found, and the final command line.
+//! Without the feature, the instrumentation is compiled out.
+
+extern crate alloc;
+
+/// Emits a `tracing` event at the given level, e.g.
+/// `trace_event!(debug, binary, found, "message")`.
+///
+/// Expands to nothing without the `tracing` feature, so the arguments are
+/// not evaluated.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
 
+mod anchor;
+mod args;
+#[cfg(feature = "cache")]
+mod cache;
+mod column;
 mod command;
 mod config;
 mod detect;
+mod diagnose;
 mod editor;
+mod env;
 mod error;
+mod fragment;
+mod kind;
+mod options;
+#[cfg(feature = "regex")]
+mod pattern;
+mod remote;
+mod uri;
 
+#[cfg(feature = "cache")]
+pub use cache::EditorCache;
+pub use column::{
+    byte_column_to_char_column, char_column_to_byte_column, char_column_to_display_column,
+};
 pub use config::{
-    EditorConfig, EditorKindConfig, ResolveFrom, DEFAULT_RESOLVE_ORDER, ENV_ONLY_RESOLVE_ORDER,
+    DEFAULT_RESOLVE_ORDER, ENV_ONLY_RESOLVE_ORDER, EditorConfig, EditorKindConfig,
+    GIT_RESOLVE_ORDER, ResolveFrom,
+};
+pub use detect::{DetectedEditor, EditorSource};
+pub use diagnose::{EditorDiagnostics, SourceReport};
+pub use editor::{Editor, EditorBuilder, ResolvedPlan};
+pub use env::clear_editor_cache;
+pub use error::{Error, ResolutionFailure, Result};
+pub use fragment::{LineRange, parse_line_fragment};
+pub use kind::{Capabilities, EditorFamily, EditorKind};
+pub use options::{
+    ColumnSemantics, IndexBase, MultiFilePolicy, ScrollPosition, Split, TerminalLauncher,
+    VimPositionStyle, WindowMode,
 };
-pub use editor::{Editor, EditorBuilder, EditorKind};
-pub use error::{Error, Result};
+#[cfg(feature = "regex")]
+pub use pattern::PatternMiss;
+pub use uri::{UriLocation, parse_file_uri};
@@ -0,0 +1,300 @@
+//! Option types for customizing how files are opened.
+//!
+//! These types are passed to [`EditorBuilder`](crate::EditorBuilder) methods
+//! to adjust positioning and launch behavior.
+
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+/// The numbering base used for incoming line and column values.
+///
+/// opensesame and every supported editor count lines and columns from 1.
+/// Many tools (LSP servers, tree-sitter, most parsers) report 0-indexed
+/// positions instead; use [`IndexBase::ZeroBased`] to have opensesame do the
+/// `+1` conversion for you.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use opensesame::{Editor, IndexBase};
+///
+/// // Position reported by an LSP server (0-indexed)
+/// Editor::builder()
+///     .file("src/main.rs")
+///     .index_base(IndexBase::ZeroBased)
+///     .line(41)
+///     .column(9)
+///     .open()?;
+/// # Ok::<(), opensesame::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum IndexBase {
+    /// Lines and columns start at 1 (the default).
+    #[default]
+    OneBased,
+    /// Lines and columns start at 0 and are shifted by one before use.
+    ZeroBased,
+}
+
+impl IndexBase {
+    /// Converts a value in this base to a 1-indexed value.
+    ///
+    /// Returns `None` if the value cannot be represented (0 in one-based
+    /// numbering, or `u32::MAX` in zero-based numbering).
+    pub const fn to_one_based(self, value: u32) -> Option<u32> {
+        match self {
+            Self::OneBased => {
+                if value == 0 {
+                    None
+                } else {
+                    Some(value)
+                }
+            }
+            Self::ZeroBased => value.checked_add(1),
+        }
+    }
+}
+
+/// How several files opened together are laid out in splits.
+///
+/// The CLI split flags only arrange the files given on the command line, so
+/// this needs more than one file (see
+/// [`EditorBuilder::files()`](crate::EditorBuilder::files)); a single file is
+/// opened normally. Only editors with a documented CLI split flag honor this
+/// (see [`EditorKind::supports_split()`](crate::EditorKind::supports_split));
+/// other editors ignore it.
+///
+/// | Editor | `Right` | `Below` |
+/// |--------|---------|---------|
+/// | Vim, NeoVim, GVim | `-O` | `-o` |
+/// | Helix | `--vsplit` | `--hsplit` |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Split {
+    /// Side by side, in vertical splits.
+    Right,
+    /// Stacked, in horizontal splits.
+    Below,
+}
+
+/// Whether a GUI editor opens the file in an existing window or a new one.
+///
+/// Only editors with a CLI flag for this honor it (see
+/// [`EditorKind::supports_window_mode()`](crate::EditorKind::supports_window_mode));
+/// other editors ignore it.
+///
+/// | Editor | `Reuse` | `New` |
+/// |--------|---------|-------|
+/// | VS Code family | `--reuse-window` | `--new-window` |
+/// | Sublime Text | (default) | `--new-window` |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum WindowMode {
+    /// Leave it to the editor's own setting.
+    #[default]
+    Default,
+    /// Open the file in the last active window.
+    Reuse,
+    /// Open the file in a new window.
+    New,
+}
+
+/// Where the target line appears in the editor window.
+///
+/// Only editors that can scroll from the CLI honor this (see
+/// [`EditorKind::supports_scroll()`](crate::EditorKind::supports_scroll));
+/// other editors, including VS Code, ignore it.
+///
+/// | Editor | `Center` | `Top` | `Bottom` |
+/// |--------|----------|-------|----------|
+/// | Vim, NeoVim, GVim | `-c "normal! zz"` | `-c "normal! zt"` | `-c "normal! zb"` |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ScrollPosition {
+    /// Scroll the line to the middle of the window.
+    Center,
+    /// Scroll the line to the top of the window.
+    Top,
+    /// Scroll the line to the bottom of the window.
+    Bottom,
+}
+
+/// How an editor counts columns within a line.
+///
+/// Columns passed to [`EditorBuilder::column()`](crate::EditorBuilder::column)
+/// are character columns. When the editor counts differently, opensesame
+/// reads the target line and converts the column before building arguments.
+/// Each [`EditorKind`](crate::EditorKind) has a native convention (see
+/// [`EditorKind::column_semantics()`](crate::EditorKind::column_semantics)),
+/// which [`EditorBuilder::column_semantics()`](crate::EditorBuilder::column_semantics)
+/// can override.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ColumnSemantics {
+    /// Columns are UTF-8 byte offsets (e.g., Vim's `cursor()`).
+    Byte,
+    /// Columns are characters (most GUI editors).
+    Char,
+    /// Columns are display cells, with tabs expanded to the next tab stop.
+    DisplayCell {
+        /// Width of a tab stop in cells.
+        tab_width: u32,
+    },
+}
+
+impl ColumnSemantics {
+    /// Converts a 1-indexed character column in `line_text` to this convention.
+    ///
+    /// Returns `None` if the column is 0 or past the end of the line. The
+    /// column one past the last character (end of line) is valid.
+    pub fn from_char_column(self, line_text: &str, char_col: u32) -> Option<u32> {
+        match self {
+            Self::Byte => crate::column::char_column_to_byte_column(line_text, char_col),
+            Self::Char => {
+                let end = line_text.chars().count() + 1;
+                usize::try_from(char_col)
+                    .is_ok_and(|col| (1..=end).contains(&col))
+                    .then_some(char_col)
+            }
+            Self::DisplayCell { tab_width } => {
+                crate::column::char_column_to_display_column(line_text, char_col, tab_width)
+            }
+        }
+    }
+}
+
+/// How Vim-family editors are told where to place the cursor.
+///
+/// Used with [`EditorBuilder::vim_position_style()`](crate::EditorBuilder::vim_position_style).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum VimPositionStyle {
+    /// `+call cursor(LINE,COL)` (the default).
+    ///
+    /// Columns are byte offsets. Autocommands that run after startup
+    /// commands, such as plugins restoring the last position, can move the
+    /// cursor again.
+    #[default]
+    CursorCall,
+    /// `+LINE` followed by `-c "normal! COL|"`.
+    ///
+    /// Moves with a normal-mode motion after jumping to the line, which
+    /// holds up better against plugins. `|` counts screen columns, so tabs
+    /// are expanded using Vim's default 8-wide tab stop.
+    GotoLineNormal,
+}
+
+/// How several files are handed to the editor.
+///
+/// Used with [`EditorBuilder::multi_file_policy()`](crate::EditorBuilder::multi_file_policy)
+/// when opening more than one file at once. Terminal editors take over the
+/// terminal until they exit, so they can only open several files in a
+/// single process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum MultiFilePolicy {
+    /// Pass every file to one invocation (the default).
+    ///
+    /// Editors that accept several files (see
+    /// [`EditorKind::supports_multiple_files()`](crate::EditorKind::supports_multiple_files))
+    /// open them together, e.g. as Vim buffers. GUI editors that don't are
+    /// started once per file instead; terminal editors that don't are an
+    /// error.
+    #[default]
+    SingleProcess,
+    /// Start the editor once per file.
+    ///
+    /// Only GUI editors can do this; terminal editors are an error.
+    SeparateProcesses,
+}
+
+/// How terminal editors (Vim, Nano, Helix, ...) are hosted.
+///
+/// GUI editors are always launched directly; this only affects editors
+/// where [`EditorKind::is_terminal_editor()`](crate::EditorKind::is_terminal_editor)
+/// is `true`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum TerminalLauncher {
+    /// Run in the current terminal, inheriting stdio (the default).
+    #[default]
+    Inline,
+    /// Open in a new tmux window (`tmux new-window`).
+    ///
+    /// Falls back to `Inline` when not running inside tmux (`$TMUX` unset).
+    TmuxWindow,
+    /// Open in a new tmux pane beside the current one (`tmux split-window -h`).
+    ///
+    /// Falls back to `Inline` when not running inside tmux (`$TMUX` unset).
+    TmuxSplit,
+    /// Open in a new Kitty OS window (`kitty vim file`).
+    Kitty,
+    /// Open in a new WezTerm OS window (`wezterm start -- vim file`).
+    WezTerm,
+    /// Open in a new Alacritty window (`alacritty -e vim file`).
+    Alacritty,
+    /// Open in a new GNOME Terminal window (`gnome-terminal -- vim file`).
+    GnomeTerminal,
+    /// Open in the system's default terminal emulator on Debian-based
+    /// systems (`x-terminal-emulator -e vim file`).
+    XTerminalEmulator,
+    /// Open in a new Terminal.app window on macOS, via `osascript`.
+    ///
+    /// Terminal.app runs the editor through the user's shell, in the
+    /// current directory.
+    TerminalApp,
+    /// Open in a new console window on Windows (`conhost.exe vim file`).
+    WindowsConsole,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_base_default_is_one_based() {
+        assert_eq!(IndexBase::default(), IndexBase::OneBased);
+    }
+
+    #[test]
+    fn test_column_semantics_from_char_column() {
+        let line = "\tλx";
+        assert_eq!(ColumnSemantics::Char.from_char_column(line, 3), Some(3));
+        assert_eq!(ColumnSemantics::Byte.from_char_column(line, 3), Some(4));
+        assert_eq!(
+            ColumnSemantics::DisplayCell { tab_width: 8 }.from_char_column(line, 3),
+            Some(10)
+        );
+        assert_eq!(
+            ColumnSemantics::DisplayCell { tab_width: 4 }.from_char_column(line, 3),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn test_column_semantics_past_end_of_line() {
+        let line = "\tλx";
+        for semantics in [
+            ColumnSemantics::Char,
+            ColumnSemantics::Byte,
+            ColumnSemantics::DisplayCell { tab_width: 8 },
+        ] {
+            assert!(
+                semantics.from_char_column(line, 4).is_some(),
+                "{semantics:?}"
+            );
+            assert_eq!(semantics.from_char_column(line, 5), None, "{semantics:?}");
+            assert_eq!(semantics.from_char_column(line, 0), None, "{semantics:?}");
+        }
+        assert_eq!(ColumnSemantics::Char.from_char_column(line, 4), Some(4));
+    }
+
+    #[test]
+    fn test_index_base_to_one_based() {
+        assert_eq!(IndexBase::OneBased.to_one_based(0), None);
+        assert_eq!(IndexBase::OneBased.to_one_based(42), Some(42));
+        assert_eq!(IndexBase::ZeroBased.to_one_based(0), Some(1));
+        assert_eq!(IndexBase::ZeroBased.to_one_based(41), Some(42));
+        assert_eq!(IndexBase::ZeroBased.to_one_based(u32::MAX), None);
+    }
+}
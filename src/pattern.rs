@@ -0,0 +1,97 @@
+//! Regex-based cursor placement.
+//!
+//! Tools often know a region (a function, a hunk) rather than an exact
+//! position. These helpers find the first regex match within a line range so
+//! the editor can be opened on it.
+//!
+//! Requires the `regex` feature.
+
+use std::io::BufRead;
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::column::byte_column_to_char_column;
+
+/// What to do when a pattern has no match in its line range.
+///
+/// Used with [`EditorBuilder::pattern_in_range()`](crate::EditorBuilder::pattern_in_range).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum PatternMiss {
+    /// Fail with [`Error::InvalidPosition`](crate::Error::InvalidPosition) (the default).
+    #[default]
+    Error,
+    /// Open at the first line of the range.
+    RangeStart,
+}
+
+/// Finds the first match of `regex` within lines `start..=end` of a file.
+///
+/// Lines are 1-indexed and the returned `(line, column)` uses 1-indexed
+/// character columns. Each line is searched separately, without its line
+/// terminator. Returns `Ok(None)` if nothing matches.
+pub fn find_in_range(
+    path: &Path,
+    regex: &Regex,
+    start: u32,
+    end: u32,
+) -> std::io::Result<Option<(u32, u32)>> {
+    let file = std::fs::File::open(path)?;
+    let lines = std::io::BufReader::new(file).lines();
+
+    for (line_number, line) in (1..=end).zip(lines) {
+        let line = line?;
+        if line_number < start {
+            continue;
+        }
+        if let Some(found) = regex.find(&line) {
+            let byte_col = u32::try_from(found.start() + 1).unwrap_or(u32::MAX);
+            let column = byte_column_to_char_column(&line, byte_col).unwrap_or(1);
+            return Ok(Some((line_number, column)));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(contents: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_find_in_range_match() {
+        let (_dir, path) = write("// TODO: top\nfn run() {\n    let é = 1; // TODO: fix\n}\n");
+        let todo = Regex::new("TODO").unwrap();
+
+        // Skips the match before the range; 'é' is one character
+        assert_eq!(find_in_range(&path, &todo, 2, 4).unwrap(), Some((3, 19)));
+        assert_eq!(find_in_range(&path, &todo, 1, 4).unwrap(), Some((1, 4)));
+    }
+
+    #[test]
+    fn test_find_in_range_no_match() {
+        let (_dir, path) = write("// TODO: top\nfn run() {\n}\n// TODO: bottom\n");
+        let todo = Regex::new("TODO").unwrap();
+
+        assert_eq!(find_in_range(&path, &todo, 2, 3).unwrap(), None);
+        assert_eq!(
+            find_in_range(&path, &Regex::new("FIXME").unwrap(), 1, 100).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_in_range_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let todo = Regex::new("TODO").unwrap();
+        assert!(find_in_range(&dir.path().join("missing.rs"), &todo, 1, 10).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Remote file targets.
+//!
+//! Files on other machines can be given as `scp://[user@]host/path` URLs or
+//! in the scp-like `user@host:path` form. Editors with remote support open
+//! them through their own mechanism (Vim's netrw, VS Code's Remote - SSH).
+
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// A file on a remote host reachable over SSH.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteFile {
+    /// The SSH user, if given.
+    pub user: Option<String>,
+    /// The remote host name.
+    pub host: String,
+    /// The path on the remote host (absolute, or relative to the home directory).
+    pub path: String,
+}
+
+impl RemoteFile {
+    /// Parses a remote target.
+    ///
+    /// Accepts `scp://[user@]host/path` (where `scp://host//abs` denotes an
+    /// absolute path, as in Vim's netrw) and `user@host:path`. The short
+    /// form requires a user so it can't be confused with Windows drive
+    /// letters or `file:line` suffixes.
+    pub fn parse(target: &str) -> Option<Self> {
+        if let Some(rest) = target.strip_prefix("scp://") {
+            let (authority, path) = rest.split_once('/')?;
+            let (user, host) = split_user(authority);
+            if host.is_empty() || path.is_empty() {
+                return None;
+            }
+            return Some(Self {
+                user,
+                host: host.to_string(),
+                path: path.to_string(),
+            });
+        }
+
+        if target.contains("://") {
+            return None;
+        }
+
+        let (authority, path) = target.split_once(':')?;
+        let (user, host) = split_user(authority);
+        if user.is_none() || host.is_empty() || path.is_empty() || host.contains(['/', '\\']) {
+            return None;
+        }
+        Some(Self {
+            user,
+            host: host.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    /// Returns `user@host`, or just `host` when no user is set.
+    pub fn authority(&self) -> String {
+        match self.user {
+            Some(ref user) => format!("{user}@{}", self.host),
+            None => self.host.clone(),
+        }
+    }
+
+    /// Returns the target as a netrw `scp://` URL.
+    ///
+    /// Absolute paths use netrw's double-slash form (`scp://host//etc/hosts`).
+    pub fn to_scp_url(&self) -> String {
+        format!("scp://{}/{}", self.authority(), self.path)
+    }
+}
+
+/// Splits `user@host` into its parts.
+fn split_user(authority: &str) -> (Option<String>, &str) {
+    match authority.split_once('@') {
+        Some((user, host)) if !user.is_empty() => (Some(user.to_string()), host),
+        _ => (None, authority),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scp_short_form() {
+        let remote = RemoteFile::parse("alice@devbox:/srv/app/main.rs").unwrap();
+        assert_eq!(remote.user.as_deref(), Some("alice"));
+        assert_eq!(remote.host, "devbox");
+        assert_eq!(remote.path, "/srv/app/main.rs");
+
+        let remote = RemoteFile::parse("alice@devbox:notes.txt").unwrap();
+        assert_eq!(remote.path, "notes.txt");
+    }
+
+    #[test]
+    fn test_parse_scp_url() {
+        let remote = RemoteFile::parse("scp://alice@devbox//srv/app/main.rs").unwrap();
+        assert_eq!(remote.user.as_deref(), Some("alice"));
+        assert_eq!(remote.host, "devbox");
+        assert_eq!(remote.path, "/srv/app/main.rs");
+
+        let remote = RemoteFile::parse("scp://devbox/notes.txt").unwrap();
+        assert_eq!(remote.user, None);
+        assert_eq!(remote.path, "notes.txt");
+    }
+
+    #[test]
+    fn test_parse_rejects_local_paths() {
+        assert_eq!(RemoteFile::parse("src/main.rs"), None);
+        assert_eq!(RemoteFile::parse("/home/alice/main.rs"), None);
+        assert_eq!(RemoteFile::parse("C:\\src\\main.rs"), None);
+        assert_eq!(RemoteFile::parse("devbox:main.rs"), None);
+        assert_eq!(RemoteFile::parse("https://example.com/main.rs"), None);
+        assert_eq!(RemoteFile::parse("scp://devbox"), None);
+        assert_eq!(RemoteFile::parse("alice@devbox:"), None);
+    }
+
+    #[test]
+    fn test_to_scp_url() {
+        let remote = RemoteFile::parse("alice@devbox:/srv/app/main.rs").unwrap();
+        assert_eq!(remote.to_scp_url(), "scp://alice@devbox//srv/app/main.rs");
+
+        let remote = RemoteFile::parse("scp://devbox/notes.txt").unwrap();
+        assert_eq!(remote.to_scp_url(), "scp://devbox/notes.txt");
+        assert_eq!(remote.authority(), "devbox");
+    }
+}
@@ -0,0 +1,198 @@
+//! `file://` URIs with position fragments.
+//!
+//! LSP clients and tools such as rust-analyzer refer to locations as
+//! `file:///path/to/file.rs#L10,5`. These helpers turn such a URI into a
+//! local path and a 1-indexed line and column.
+
+use std::path::PathBuf;
+
+use crate::fragment::parse_line_fragment;
+
+/// A location parsed from a `file://` URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriLocation {
+    /// The local file path, percent-decoded.
+    pub path: PathBuf,
+    /// The 1-indexed line from the fragment, if any.
+    pub line: Option<u32>,
+    /// The 1-indexed column from the fragment, if any.
+    pub column: Option<u32>,
+}
+
+/// Parses a `file://` URI with an optional `#L<line>[,<column>]` fragment.
+///
+/// The host must be empty or `localhost`. The path is percent-decoded, and
+/// Windows paths written as `file:///C:/...` lose the leading slash. A
+/// `#L<start>-L<end>` range fragment gives the start line. Fragments that
+/// aren't positions are ignored.
+///
+/// Returns `None` if `uri` isn't a local `file://` URI or its path doesn't
+/// decode to UTF-8.
+///
+/// # Example
+///
+/// ```rust
+/// use opensesame::parse_file_uri;
+/// use std::path::Path;
+///
+/// let location = parse_file_uri("file:///home/me/my%20crate/src/lib.rs#L10,5").unwrap();
+/// assert_eq!(location.path, Path::new("/home/me/my crate/src/lib.rs"));
+/// assert_eq!((location.line, location.column), (Some(10), Some(5)));
+/// ```
+pub fn parse_file_uri(uri: &str) -> Option<UriLocation> {
+    let scheme = uri.get(..7)?;
+    if !scheme.eq_ignore_ascii_case("file://") {
+        return None;
+    }
+    let rest = &uri[7..];
+
+    let (rest, fragment) = match rest.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (rest, None),
+    };
+    let rest = rest.split_once('?').map_or(rest, |(rest, _)| rest);
+
+    // Authority: empty or localhost
+    let slash = rest.find('/')?;
+    let (host, path) = rest.split_at(slash);
+    if !host.is_empty() && !host.eq_ignore_ascii_case("localhost") {
+        return None;
+    }
+
+    let path = percent_decode(path)?;
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => path[1..].to_string(),
+        _ => path,
+    };
+
+    let (line, column) = fragment.and_then(parse_position).unzip();
+    Some(UriLocation {
+        path: PathBuf::from(path),
+        line,
+        column: column.flatten(),
+    })
+}
+
+/// Parses `L10,5`, `L10`, or a `L10-L20` range.
+fn parse_position(fragment: &str) -> Option<(u32, Option<u32>)> {
+    if let Some((line, column)) = fragment
+        .strip_prefix('L')
+        .and_then(|rest| rest.split_once(','))
+    {
+        return Some((parse_number(line)?, Some(parse_number(column)?)));
+    }
+    // Reuse the GitHub-style fragment parser for `L10` and ranges
+    let (_, range) = parse_line_fragment(&format!("_#{fragment}"));
+    range.map(|range| (range.start, None))
+}
+
+/// Parses a 1-indexed number.
+fn parse_number(s: &str) -> Option<u32> {
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok().filter(|&n| n > 0)
+}
+
+/// Decodes `%XX` escapes, requiring the result to be UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            // `from_str_radix` alone would also accept a sign, as in `%+1`
+            let hex = tail
+                .get(..2)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+            let hex = core::str::from_utf8(hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(path: &str, line: Option<u32>, column: Option<u32>) -> UriLocation {
+        UriLocation {
+            path: PathBuf::from(path),
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn test_parse_file_uri_with_position() {
+        assert_eq!(
+            parse_file_uri("file:///src/main.rs#L10,5"),
+            Some(location("/src/main.rs", Some(10), Some(5)))
+        );
+        assert_eq!(
+            parse_file_uri("file:///src/main.rs#L10"),
+            Some(location("/src/main.rs", Some(10), None))
+        );
+        assert_eq!(
+            parse_file_uri("file:///src/main.rs#L10-L20"),
+            Some(location("/src/main.rs", Some(10), None))
+        );
+        assert_eq!(
+            parse_file_uri("file:///src/main.rs"),
+            Some(location("/src/main.rs", None, None))
+        );
+    }
+
+    #[test]
+    fn test_parse_file_uri_percent_decodes_path() {
+        assert_eq!(
+            parse_file_uri("file:///home/me/my%20crate/%C3%A9t%C3%A9%23.rs#L3,1"),
+            Some(location("/home/me/my crate/été#.rs", Some(3), Some(1)))
+        );
+        assert_eq!(parse_file_uri("file:///bad%2"), None);
+        assert_eq!(parse_file_uri("file:///bad%zz"), None);
+        assert_eq!(parse_file_uri("file:///bad%ff"), None);
+        assert_eq!(parse_file_uri("file:///bad%+1"), None);
+        assert_eq!(parse_file_uri("file:///bad%-1"), None);
+    }
+
+    #[test]
+    fn test_parse_file_uri_hosts_and_drives() {
+        assert_eq!(
+            parse_file_uri("FILE://localhost/etc/hosts"),
+            Some(location("/etc/hosts", None, None))
+        );
+        assert_eq!(
+            parse_file_uri("file:///C:/Users/me/a.rs#L2,4"),
+            Some(location("C:/Users/me/a.rs", Some(2), Some(4)))
+        );
+        assert_eq!(parse_file_uri("file://server/share/a.rs"), None);
+    }
+
+    #[test]
+    fn test_parse_file_uri_ignores_other_fragments() {
+        assert_eq!(
+            parse_file_uri("file:///a.rs#readme"),
+            Some(location("/a.rs", None, None))
+        );
+        assert_eq!(
+            parse_file_uri("file:///a.rs#L0,5"),
+            Some(location("/a.rs", None, None))
+        );
+        assert_eq!(
+            parse_file_uri("file:///a.rs#L5,x"),
+            Some(location("/a.rs", None, None))
+        );
+    }
+
+    #[test]
+    fn test_parse_file_uri_rejects_other_schemes() {
+        assert_eq!(parse_file_uri("src/main.rs"), None);
+        assert_eq!(parse_file_uri("scp://host/a.rs"), None);
+        assert_eq!(parse_file_uri("file:"), None);
+    }
+}
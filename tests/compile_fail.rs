@@ -0,0 +1,7 @@
+//! Compile-fail tests for API misuse that should be caught at build time.
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,35 @@
+//! Checks that argument building and editor kinds stay free of `std`.
+//!
+//! `tests/no_std/lib.rs` includes the std-free modules into a `#![no_std]`
+//! crate, so any use of `std`, or of a module that needs it (`env`,
+//! `error`, ...), fails the build.
+
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn pure_modules_build_without_std() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let out_dir = tempfile::tempdir().unwrap();
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+
+    let output = Command::new(rustc)
+        .args([
+            "--edition",
+            "2024",
+            "--crate-type",
+            "rlib",
+            "--crate-name",
+            "opensesame_core",
+        ])
+        .args(["-A", "dead_code", "--out-dir"])
+        .arg(out_dir.path())
+        .arg(root.join("tests").join("no_std").join("lib.rs"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
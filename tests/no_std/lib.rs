@@ -0,0 +1,17 @@
+//! The std-free core of opensesame, built as a `no_std` crate by
+//! `tests/no_std.rs`. Each module is compiled from the main crate's source.
+
+#![no_std]
+
+extern crate alloc;
+
+#[path = "../../src/args.rs"]
+pub mod args;
+#[path = "../../src/column.rs"]
+pub mod column;
+#[path = "../../src/kind.rs"]
+pub mod kind;
+#[path = "../../src/options.rs"]
+pub mod options;
+#[path = "../../src/remote.rs"]
+pub mod remote;
@@ -0,0 +1,7 @@
+#![deny(unused_must_use)]
+
+use opensesame::Editor;
+
+fn main() {
+    Editor::builder();
+}
@@ -0,0 +1,9 @@
+#![deny(unused_must_use)]
+
+use opensesame::Editor;
+
+fn main() {
+    let builder = Editor::builder().file("src/main.rs");
+    // Dropping the returned builder means the line is never applied
+    builder.line(5);
+}